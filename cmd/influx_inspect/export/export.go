@@ -11,12 +11,12 @@ import (
 	"os"
 	"path/filepath"
 	"sort"
-	"strconv"
 	"strings"
 	"sync"
 	"time"
 
-	"github.com/influxdata/influxdb/models"
+	"github.com/influxdata/influxdb/cmd/influx_inspect/internal/lineprotocol"
+	"github.com/influxdata/influxdb/cmd/influx_inspect/internal/wal"
 	"github.com/influxdata/influxdb/pkg/escape"
 	"github.com/influxdata/influxdb/tsdb/engine/tsm1"
 	"github.com/influxdata/influxql"
@@ -167,30 +167,12 @@ func (cmd *Command) walkTSMFiles() error {
 }
 
 func (cmd *Command) walkWALFiles() error {
-	return filepath.Walk(cmd.walDir, func(path string, f os.FileInfo, err error) error {
-		if err != nil {
-			return err
-		}
-
-		// check to see if this is a wal file
-		fileName := filepath.Base(path)
-		if filepath.Ext(path) != "."+tsm1.WALFileExtension || !strings.HasPrefix(fileName, tsm1.WALFilePrefix) {
-			return nil
-		}
-
-		relPath, err := filepath.Rel(cmd.walDir, path)
-		if err != nil {
-			return err
-		}
-		dirs := strings.Split(relPath, string(byte(os.PathSeparator)))
-		if len(dirs) < 2 {
-			return fmt.Errorf("invalid directory structure for %s", path)
-		}
-		if dirs[0] == cmd.database || cmd.database == "" {
-			if dirs[1] == cmd.retentionPolicy || cmd.retentionPolicy == "" {
-				key := filepath.Join(dirs[0], dirs[1])
+	return wal.WalkFiles(cmd.walDir, func(f wal.File) error {
+		if f.Database == cmd.database || cmd.database == "" {
+			if f.RetentionPolicy == cmd.retentionPolicy || cmd.retentionPolicy == "" {
+				key := filepath.Join(f.Database, f.RetentionPolicy)
 				cmd.manifest[key] = struct{}{}
-				cmd.walFiles[key] = append(cmd.walFiles[key], path)
+				cmd.walFiles[key] = append(cmd.walFiles[key], f.Path)
 			}
 		}
 		return nil
@@ -368,8 +350,8 @@ func (cmd *Command) exportTSMFile(tsmFilePath string, w io.Writer) error {
 		measurement, field := tsm1.SeriesAndFieldFromCompositeKey(key)
 		field = escape.Bytes(field)
 
-		if err := cmd.writeValues(w, measurement, string(field), values); err != nil {
-			// An error from writeValues indicates an IO error, which should be returned.
+		if err := lineprotocol.WriteValues(w, measurement, field, values, cmd.startTime, cmd.endTime); err != nil {
+			// An error from WriteValues indicates an IO error, which should be returned.
 			return err
 		}
 	}
@@ -403,95 +385,10 @@ or manually editing the exported file.
 	return nil
 }
 
-// exportWAL reads every WAL entry from r and exports it to w.
+// exportWALFile reads every WAL entry from the WAL file and exports it to w.
 func (cmd *Command) exportWALFile(walFilePath string, w io.Writer, warnDelete func()) error {
-	f, err := os.Open(walFilePath)
-	if err != nil {
-		if os.IsNotExist(err) {
-			fmt.Fprintf(cmd.Stderr, "skipped missing file: %s", walFilePath)
-			return nil
-		}
-		return err
-	}
-	defer f.Close()
-
-	r := tsm1.NewWALSegmentReader(f)
-	defer r.Close()
-
-	for r.Next() {
-		entry, err := r.Read()
-		if err != nil {
-			n := r.Count()
-			fmt.Fprintf(cmd.Stderr, "file %s corrupt at position %d: %v", walFilePath, n, err)
-			break
-		}
-
-		switch t := entry.(type) {
-		case *tsm1.DeleteWALEntry, *tsm1.DeleteRangeWALEntry:
-			warnDelete()
-			continue
-		case *tsm1.WriteWALEntry:
-			for key, values := range t.Values {
-				measurement, field := tsm1.SeriesAndFieldFromCompositeKey([]byte(key))
-				// measurements are stored escaped, field names are not
-				field = escape.Bytes(field)
-
-				if err := cmd.writeValues(w, measurement, string(field), values); err != nil {
-					// An error from writeValues indicates an IO error, which should be returned.
-					return err
-				}
-			}
-		}
-	}
-	return nil
-}
-
-// writeValues writes every value in values to w, using the given series key and field name.
-// If any call to w.Write fails, that error is returned.
-func (cmd *Command) writeValues(w io.Writer, seriesKey []byte, field string, values []tsm1.Value) error {
-	buf := []byte(string(seriesKey) + " " + field + "=")
-	prefixLen := len(buf)
-
-	for _, value := range values {
-		ts := value.UnixNano()
-		if (ts < cmd.startTime) || (ts > cmd.endTime) {
-			continue
-		}
-
-		// Re-slice buf to be "<series_key> <field>=".
-		buf = buf[:prefixLen]
-
-		// Append the correct representation of the value.
-		switch v := value.Value().(type) {
-		case float64:
-			buf = strconv.AppendFloat(buf, v, 'g', -1, 64)
-		case int64:
-			buf = strconv.AppendInt(buf, v, 10)
-			buf = append(buf, 'i')
-		case uint64:
-			buf = strconv.AppendUint(buf, v, 10)
-			buf = append(buf, 'u')
-		case bool:
-			buf = strconv.AppendBool(buf, v)
-		case string:
-			buf = append(buf, '"')
-			buf = append(buf, models.EscapeStringField(v)...)
-			buf = append(buf, '"')
-		default:
-			// This shouldn't be possible, but we'll format it anyway.
-			buf = append(buf, fmt.Sprintf("%v", v)...)
-		}
-
-		// Now buf has "<series_key> <field>=<value>".
-		// Append the timestamp and a newline, then write it.
-		buf = append(buf, ' ')
-		buf = strconv.AppendInt(buf, ts, 10)
-		buf = append(buf, '\n')
-		if _, err := w.Write(buf); err != nil {
-			// Underlying IO error needs to be returned.
-			return err
-		}
-	}
-
-	return nil
+	return wal.ReadFile(walFilePath, cmd.Stderr, warnDelete, func(seriesKey, field []byte, values []tsm1.Value) error {
+		// An error from WriteValues indicates an IO error, which should be returned.
+		return lineprotocol.WriteValues(w, seriesKey, field, values, cmd.startTime, cmd.endTime)
+	})
 }
@@ -39,6 +39,7 @@ The commands are:
     buildtsi             generates tsi1 indexes from tsm1 data
     help                 display this help message
     merge-schema         merge a set of schema files from the check-schema command
+    replaywal            replays the writes in WAL files to an InfluxDB server
     report               displays a shard level cardinality report
     report-db            estimates cloud 2 cardinality for a database
     report-disk          displays a shard level disk usage report
@@ -0,0 +1,67 @@
+// Package lineprotocol formats the values read from TSM and WAL files as
+// line protocol.
+package lineprotocol
+
+import (
+	"fmt"
+	"io"
+	"strconv"
+
+	"github.com/influxdata/influxdb/models"
+	"github.com/influxdata/influxdb/tsdb/engine/tsm1"
+)
+
+// AppendValue appends the representation of value, its timestamp and a
+// newline to buf, which holds the "<series key> <field>=" start of the line.
+func AppendValue(buf []byte, value tsm1.Value) []byte {
+	switch v := value.Value().(type) {
+	case float64:
+		buf = strconv.AppendFloat(buf, v, 'g', -1, 64)
+	case int64:
+		buf = strconv.AppendInt(buf, v, 10)
+		buf = append(buf, 'i')
+	case uint64:
+		buf = strconv.AppendUint(buf, v, 10)
+		buf = append(buf, 'u')
+	case bool:
+		buf = strconv.AppendBool(buf, v)
+	case string:
+		buf = append(buf, '"')
+		buf = append(buf, models.EscapeStringField(v)...)
+		buf = append(buf, '"')
+	default:
+		// This shouldn't be possible, but we'll format it anyway.
+		buf = append(buf, fmt.Sprintf("%v", v)...)
+	}
+
+	buf = append(buf, ' ')
+	buf = strconv.AppendInt(buf, value.UnixNano(), 10)
+	return append(buf, '\n')
+}
+
+// WriteValues writes every value in values with a timestamp between min and
+// max to w, using the given series key and field name.
+// If any call to w.Write fails, that error is returned.
+func WriteValues(w io.Writer, seriesKey, field []byte, values []tsm1.Value, min, max int64) error {
+	buf := make([]byte, 0, len(seriesKey)+len(field)+2)
+	buf = append(buf, seriesKey...)
+	buf = append(buf, ' ')
+	buf = append(buf, field...)
+	buf = append(buf, '=')
+	prefixLen := len(buf)
+
+	for _, value := range values {
+		if ts := value.UnixNano(); ts < min || ts > max {
+			continue
+		}
+
+		// Re-slice buf to be "<series_key> <field>=".
+		buf = AppendValue(buf[:prefixLen], value)
+		if _, err := w.Write(buf); err != nil {
+			// Underlying IO error needs to be returned.
+			return err
+		}
+	}
+
+	return nil
+}
@@ -0,0 +1,116 @@
+// Package wal reads the writes held in the TSM WAL files of a WAL directory.
+package wal
+
+import (
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"sort"
+	"strconv"
+	"strings"
+
+	"github.com/influxdata/influxdb/pkg/escape"
+	"github.com/influxdata/influxdb/tsdb/engine/tsm1"
+)
+
+// File is a WAL segment file of a shard.
+type File struct {
+	Database        string
+	RetentionPolicy string
+	ShardID         uint64
+	Segment         int
+	Path            string
+}
+
+// WalkFiles calls fn for each WAL segment file under dir, which is laid out as
+// <database>/<retention policy>/<shard>/_<segment>.wal.
+func WalkFiles(dir string, fn func(f File) error) error {
+	return filepath.Walk(dir, func(path string, info os.FileInfo, err error) error {
+		if err != nil {
+			return err
+		}
+
+		// check to see if this is a wal file
+		fileName := filepath.Base(path)
+		if filepath.Ext(path) != "."+tsm1.WALFileExtension || !strings.HasPrefix(fileName, tsm1.WALFilePrefix) {
+			return nil
+		}
+
+		relPath, err := filepath.Rel(dir, path)
+		if err != nil {
+			return err
+		}
+		dirs := strings.Split(relPath, string(byte(os.PathSeparator)))
+		if len(dirs) < 4 {
+			return fmt.Errorf("invalid directory structure for %s", path)
+		}
+		shardID, err := strconv.ParseUint(dirs[2], 10, 64)
+		if err != nil {
+			return fmt.Errorf("invalid shard directory for %s", path)
+		}
+		segment, err := strconv.Atoi(strings.TrimSuffix(strings.TrimPrefix(fileName, tsm1.WALFilePrefix), "."+tsm1.WALFileExtension))
+		if err != nil {
+			return fmt.Errorf("invalid wal filename %s", path)
+		}
+
+		return fn(File{
+			Database:        dirs[0],
+			RetentionPolicy: dirs[1],
+			ShardID:         shardID,
+			Segment:         segment,
+			Path:            path,
+		})
+	})
+}
+
+// ReadFile calls fn with the series key, field name and values of each write
+// held in the WAL file at path, in key order, and onDelete for each delete.
+// Series keys are stored escaped and field names are escaped before fn is
+// called, so both can be written as line protocol. A missing file is skipped
+// and a corrupt one is read up to the corruption, both reported to stderr.
+func ReadFile(path string, stderr io.Writer, onDelete func(), fn func(seriesKey, field []byte, values []tsm1.Value) error) error {
+	f, err := os.Open(path)
+	if err != nil {
+		if os.IsNotExist(err) {
+			fmt.Fprintf(stderr, "skipped missing file: %s\n", path)
+			return nil
+		}
+		return err
+	}
+	defer f.Close()
+
+	r := tsm1.NewWALSegmentReader(f)
+	defer r.Close()
+
+	for r.Next() {
+		entry, err := r.Read()
+		if err != nil {
+			n := r.Count()
+			fmt.Fprintf(stderr, "file %s corrupt at position %d: %v\n", path, n, err)
+			break
+		}
+
+		switch t := entry.(type) {
+		case *tsm1.DeleteWALEntry, *tsm1.DeleteRangeWALEntry:
+			onDelete()
+		case *tsm1.WriteWALEntry:
+			keys := make([]string, 0, len(t.Values))
+			for k := range t.Values {
+				keys = append(keys, k)
+			}
+			sort.Strings(keys)
+
+			for _, key := range keys {
+				seriesKey, field := tsm1.SeriesAndFieldFromCompositeKey([]byte(key))
+				// measurements are stored escaped, field names are not
+				field = escape.Bytes(field)
+
+				if err := fn(seriesKey, field, t.Values[key]); err != nil {
+					return err
+				}
+			}
+		}
+	}
+	return nil
+}
@@ -16,6 +16,7 @@ import (
 	"github.com/influxdata/influxdb/cmd/influx_inspect/dumptsmwal"
 	"github.com/influxdata/influxdb/cmd/influx_inspect/export"
 	"github.com/influxdata/influxdb/cmd/influx_inspect/help"
+	"github.com/influxdata/influxdb/cmd/influx_inspect/replaywal"
 	"github.com/influxdata/influxdb/cmd/influx_inspect/report"
 	"github.com/influxdata/influxdb/cmd/influx_inspect/reportdisk"
 	"github.com/influxdata/influxdb/cmd/influx_inspect/reporttsi"
@@ -96,6 +97,11 @@ func (m *Main) Run(args ...string) error {
 		if err := name.Run(args...); err != nil {
 			return fmt.Errorf("export: %w", err)
 		}
+	case "replaywal":
+		name := replaywal.NewCommand()
+		if err := name.Run(args...); err != nil {
+			return fmt.Errorf("replaywal: %w", err)
+		}
 	case "buildtsi":
 		name := buildtsi.NewCommand()
 		if err := name.Run(args...); err != nil {
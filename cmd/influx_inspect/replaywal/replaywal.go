@@ -0,0 +1,322 @@
+// Package replaywal replays the writes held in TSM WAL files to an InfluxDB server.
+package replaywal
+
+import (
+	"bytes"
+	"flag"
+	"fmt"
+	"io"
+	"math"
+	"net/http"
+	"net/url"
+	"os"
+	"path/filepath"
+	"sort"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/influxdata/influxdb/cmd/influx_inspect/internal/lineprotocol"
+	"github.com/influxdata/influxdb/cmd/influx_inspect/internal/wal"
+	"github.com/influxdata/influxdb/models"
+	"github.com/influxdata/influxdb/tsdb/engine/tsm1"
+)
+
+// Command represents the program execution for "influx_inspect replaywal".
+type Command struct {
+	// Standard input/output, overridden for testing.
+	Stderr io.Writer
+	Stdout io.Writer
+
+	walDir          string
+	host            string
+	username        string
+	password        string
+	database        string
+	retentionPolicy string
+	destDatabase    string
+	destRetention   string
+	shardID         uint64
+	minSegment      int
+	maxSegment      int
+	measurement     string
+	startTime       int64
+	endTime         int64
+	batchSize       int
+	dryRun          bool
+
+	client *http.Client
+}
+
+// NewCommand returns a new instance of Command.
+func NewCommand() *Command {
+	return &Command{
+		Stderr: os.Stderr,
+		Stdout: os.Stdout,
+		client: &http.Client{Timeout: time.Minute},
+	}
+}
+
+// Run executes the command.
+func (cmd *Command) Run(args ...string) error {
+	var start, end string
+	fs := flag.NewFlagSet("replaywal", flag.ExitOnError)
+	fs.StringVar(&cmd.walDir, "waldir", os.Getenv("HOME")+"/.influxdb/wal", "WAL storage path")
+	fs.StringVar(&cmd.host, "host", "http://localhost:8086", "URL of the server to write to")
+	fs.StringVar(&cmd.username, "username", "", "Optional: username to write with")
+	fs.StringVar(&cmd.password, "password", "", "Optional: password to write with")
+	fs.StringVar(&cmd.database, "database", "", "Optional: the database to replay")
+	fs.StringVar(&cmd.retentionPolicy, "retention", "", "Optional: the retention policy to replay (requires -database)")
+	fs.StringVar(&cmd.destDatabase, "dest-database", "", "Optional: the database to write to, if different from the source (requires -database)")
+	fs.StringVar(&cmd.destRetention, "dest-retention", "", "Optional: the retention policy to write to, if different from the source (requires -retention)")
+	fs.Uint64Var(&cmd.shardID, "shard", 0, "Optional: the shard to replay")
+	fs.IntVar(&cmd.minSegment, "min-segment", 0, "Optional: the lowest WAL segment number to replay")
+	fs.IntVar(&cmd.maxSegment, "max-segment", 0, "Optional: the highest WAL segment number to replay")
+	fs.StringVar(&cmd.measurement, "measurement", "", "Optional: the measurement to replay")
+	fs.StringVar(&start, "start", "", "Optional: the start time to replay (RFC3339 format)")
+	fs.StringVar(&end, "end", "", "Optional: the end time to replay (RFC3339 format)")
+	fs.IntVar(&cmd.batchSize, "batch-size", 5000, "Number of points written per request")
+	fs.BoolVar(&cmd.dryRun, "dry-run", false, "Print the line protocol that would be written instead of writing it")
+
+	fs.SetOutput(cmd.Stdout)
+	fs.Usage = func() {
+		fmt.Fprintf(cmd.Stdout, "Replays the writes in TSM WAL files to an InfluxDB server.\n\n")
+		fmt.Fprintf(cmd.Stdout, "Usage: %s replaywal [flags]\n\n", filepath.Base(os.Args[0]))
+		fs.PrintDefaults()
+	}
+
+	if err := fs.Parse(args); err != nil {
+		return err
+	}
+
+	cmd.startTime, cmd.endTime = math.MinInt64, math.MaxInt64
+	if start != "" {
+		s, err := time.Parse(time.RFC3339, start)
+		if err != nil {
+			return err
+		}
+		cmd.startTime = s.UnixNano()
+	}
+	if end != "" {
+		e, err := time.Parse(time.RFC3339, end)
+		if err != nil {
+			return err
+		}
+		cmd.endTime = e.UnixNano()
+	}
+
+	if err := cmd.validate(); err != nil {
+		return err
+	}
+
+	return cmd.replay()
+}
+
+func (cmd *Command) validate() error {
+	if cmd.retentionPolicy != "" && cmd.database == "" {
+		return fmt.Errorf("must specify a db")
+	}
+	if cmd.destDatabase != "" && cmd.database == "" {
+		return fmt.Errorf("must specify a db to write to a different db")
+	}
+	if cmd.destRetention != "" && cmd.retentionPolicy == "" {
+		return fmt.Errorf("must specify a retention policy to write to a different retention policy")
+	}
+	if cmd.maxSegment != 0 && cmd.maxSegment < cmd.minSegment {
+		return fmt.Errorf("max segment before min segment")
+	}
+	if cmd.endTime < cmd.startTime {
+		return fmt.Errorf("end time before start time")
+	}
+	if cmd.batchSize <= 0 {
+		return fmt.Errorf("batch size must be positive")
+	}
+	if !cmd.dryRun {
+		if _, err := url.Parse(cmd.host); err != nil {
+			return fmt.Errorf("invalid host: %s", err)
+		}
+	}
+	return nil
+}
+
+// walShard is the set of WAL segments of a single shard.
+type walShard struct {
+	database        string
+	retentionPolicy string
+	id              uint64
+	segments        []walSegment
+}
+
+// walSegment is a single WAL segment file.
+type walSegment struct {
+	id   int
+	path string
+}
+
+func (cmd *Command) replay() error {
+	shards, err := cmd.walkWALFiles()
+	if err != nil {
+		return err
+	}
+
+	for _, sh := range shards {
+		database, retentionPolicy := sh.database, sh.retentionPolicy
+		if cmd.destDatabase != "" {
+			database = cmd.destDatabase
+		}
+		if cmd.destRetention != "" {
+			retentionPolicy = cmd.destRetention
+		}
+
+		fmt.Fprintf(cmd.Stderr, "replaying shard %d of %s/%s to %s/%s...", sh.id, sh.database, sh.retentionPolicy, database, retentionPolicy)
+		b := &batch{cmd: cmd, database: database, retentionPolicy: retentionPolicy}
+		for _, seg := range sh.segments {
+			if err := cmd.replayWALFile(seg.path, b); err != nil {
+				return err
+			}
+		}
+		if err := b.flush(); err != nil {
+			return err
+		}
+		fmt.Fprintf(cmd.Stderr, "complete, %d points.\n", b.written)
+	}
+	return nil
+}
+
+// walkWALFiles returns the shards and segments selected for replay, in the
+// order they were written.
+func (cmd *Command) walkWALFiles() ([]*walShard, error) {
+	shards := make(map[string]*walShard)
+	if err := wal.WalkFiles(cmd.walDir, func(f wal.File) error {
+		if cmd.database != "" && f.Database != cmd.database {
+			return nil
+		} else if cmd.retentionPolicy != "" && f.RetentionPolicy != cmd.retentionPolicy {
+			return nil
+		} else if cmd.shardID != 0 && f.ShardID != cmd.shardID {
+			return nil
+		} else if f.Segment < cmd.minSegment || (cmd.maxSegment != 0 && f.Segment > cmd.maxSegment) {
+			return nil
+		}
+
+		key := filepath.Join(f.Database, f.RetentionPolicy, strconv.FormatUint(f.ShardID, 10))
+		sh := shards[key]
+		if sh == nil {
+			sh = &walShard{database: f.Database, retentionPolicy: f.RetentionPolicy, id: f.ShardID}
+			shards[key] = sh
+		}
+		sh.segments = append(sh.segments, walSegment{id: f.Segment, path: f.Path})
+		return nil
+	}); err != nil {
+		return nil, err
+	}
+
+	a := make([]*walShard, 0, len(shards))
+	for _, sh := range shards {
+		// we need to make sure we replay in the order that the wal received the data
+		sort.Slice(sh.segments, func(i, j int) bool { return sh.segments[i].id < sh.segments[j].id })
+		a = append(a, sh)
+	}
+	sort.Slice(a, func(i, j int) bool { return a[i].id < a[j].id })
+	return a, nil
+}
+
+// replayWALFile reads every write entry from a WAL file and adds it to b.
+func (cmd *Command) replayWALFile(walFilePath string, b *batch) error {
+	warnDelete := func() {
+		fmt.Fprintf(cmd.Stderr, "WARNING: skipped delete in %s, deleted series may be written again\n", walFilePath)
+	}
+	return wal.ReadFile(walFilePath, cmd.Stderr, warnDelete, func(seriesKey, field []byte, values []tsm1.Value) error {
+		if cmd.measurement != "" && string(models.ParseName(seriesKey)) != cmd.measurement {
+			return nil
+		}
+		for _, v := range values {
+			if ts := v.UnixNano(); ts < cmd.startTime || ts > cmd.endTime {
+				continue
+			}
+			if err := b.add(seriesKey, field, v); err != nil {
+				return err
+			}
+		}
+		return nil
+	})
+}
+
+// batch accumulates line protocol for a database and retention policy and
+// writes it once it holds the configured number of points.
+type batch struct {
+	cmd             *Command
+	database        string
+	retentionPolicy string
+
+	buf     []byte
+	n       int
+	written int
+}
+
+// add appends a value as a line of line protocol.
+func (b *batch) add(seriesKey, field []byte, value tsm1.Value) error {
+	b.buf = append(b.buf, seriesKey...)
+	b.buf = append(b.buf, ' ')
+	b.buf = append(b.buf, field...)
+	b.buf = append(b.buf, '=')
+	b.buf = lineprotocol.AppendValue(b.buf, value)
+
+	if b.n++; b.n >= b.cmd.batchSize {
+		return b.flush()
+	}
+	return nil
+}
+
+// flush writes the accumulated points.
+func (b *batch) flush() error {
+	if b.n == 0 {
+		return nil
+	}
+	defer func() {
+		b.buf = b.buf[:0]
+		b.n = 0
+	}()
+
+	if b.cmd.dryRun {
+		fmt.Fprintf(b.cmd.Stdout, "# CONTEXT-DATABASE:%s\n", b.database)
+		fmt.Fprintf(b.cmd.Stdout, "# CONTEXT-RETENTION-POLICY:%s\n", b.retentionPolicy)
+		if _, err := b.cmd.Stdout.Write(b.buf); err != nil {
+			return err
+		}
+		b.written += b.n
+		return nil
+	}
+
+	u, err := url.Parse(b.cmd.host)
+	if err != nil {
+		return err
+	}
+	u.Path = strings.TrimSuffix(u.Path, "/") + "/write"
+	params := url.Values{}
+	params.Set("db", b.database)
+	params.Set("rp", b.retentionPolicy)
+	params.Set("precision", "ns")
+	u.RawQuery = params.Encode()
+
+	req, err := http.NewRequest("POST", u.String(), bytes.NewReader(b.buf))
+	if err != nil {
+		return err
+	}
+	req.Header.Set("Content-Type", "text/plain; charset=utf-8")
+	if b.cmd.username != "" {
+		req.SetBasicAuth(b.cmd.username, b.cmd.password)
+	}
+
+	resp, err := b.cmd.client.Do(req)
+	if err != nil {
+		return err
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusNoContent {
+		body, _ := io.ReadAll(io.LimitReader(resp.Body, 1024))
+		return fmt.Errorf("write to %s/%s failed: %s: %s", b.database, b.retentionPolicy, resp.Status, strings.TrimSpace(string(body)))
+	}
+	b.written += b.n
+	return nil
+}
@@ -0,0 +1,116 @@
+package replaywal
+
+import (
+	"io"
+	"math"
+	"net/http"
+	"net/http/httptest"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/golang/snappy"
+	"github.com/influxdata/influxdb/tsdb/engine/tsm1"
+)
+
+func TestCommand_Replay(t *testing.T) {
+	walDir := t.TempDir()
+	mustWriteWALFile(t, filepath.Join(walDir, "db0", "rp0", "1", "_00001.wal"), map[string][]tsm1.Value{
+		tsm1.SeriesFieldKey("cpu,host=a", "value"): {tsm1.NewValue(1, 1.5)},
+	})
+	mustWriteWALFile(t, filepath.Join(walDir, "db0", "rp0", "1", "_00002.wal"), map[string][]tsm1.Value{
+		tsm1.SeriesFieldKey("cpu,host=a", "value"): {tsm1.NewValue(2, 2.5), tsm1.NewValue(3, 3.5)},
+		tsm1.SeriesFieldKey("mem,host=a", "used"):  {tsm1.NewValue(2, int64(10))},
+	})
+	mustWriteWALFile(t, filepath.Join(walDir, "db1", "rp0", "2", "_00001.wal"), map[string][]tsm1.Value{
+		tsm1.SeriesFieldKey("cpu,host=b", "value"): {tsm1.NewValue(1, 1.5)},
+	})
+
+	var requests []string
+	var bodies []string
+	ts := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		body, _ := io.ReadAll(r.Body)
+		requests = append(requests, r.URL.String())
+		bodies = append(bodies, string(body))
+		w.WriteHeader(http.StatusNoContent)
+	}))
+	defer ts.Close()
+
+	cmd := &Command{
+		Stderr:       io.Discard,
+		Stdout:       io.Discard,
+		walDir:       walDir,
+		host:         ts.URL,
+		database:     "db0",
+		destDatabase: "restored",
+		minSegment:   2,
+		measurement:  "cpu",
+		startTime:    math.MinInt64,
+		endTime:      math.MaxInt64,
+		batchSize:    1,
+		client:       http.DefaultClient,
+	}
+	if err := cmd.validate(); err != nil {
+		t.Fatal(err)
+	} else if err := cmd.replay(); err != nil {
+		t.Fatal(err)
+	}
+
+	if len(requests) != 2 {
+		t.Fatalf("unexpected requests: %v", requests)
+	}
+	for _, req := range requests {
+		if exp := "/write?db=restored&precision=ns&rp=rp0"; req != exp {
+			t.Fatalf("unexpected request:\n\texp=%s\n\tgot=%s", exp, req)
+		}
+	}
+	if exp := []string{"cpu,host=a value=2.5 2\n", "cpu,host=a value=3.5 3\n"}; bodies[0] != exp[0] || bodies[1] != exp[1] {
+		t.Fatalf("unexpected bodies:\n\texp=%q\n\tgot=%q", exp, bodies)
+	}
+}
+
+func TestCommand_Replay_WriteError(t *testing.T) {
+	walDir := t.TempDir()
+	mustWriteWALFile(t, filepath.Join(walDir, "db0", "rp0", "1", "_00001.wal"), map[string][]tsm1.Value{
+		tsm1.SeriesFieldKey("cpu,host=a", "value"): {tsm1.NewValue(1, 1.5)},
+	})
+
+	ts := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		http.Error(w, `{"error":"database not found"}`, http.StatusNotFound)
+	}))
+	defer ts.Close()
+
+	cmd := NewCommand()
+	cmd.Stderr, cmd.Stdout = io.Discard, io.Discard
+	if err := cmd.Run("-waldir", walDir, "-host", ts.URL); err == nil {
+		t.Fatal("expected error")
+	}
+}
+
+// mustWriteWALFile writes values as a single entry of a new WAL file at path.
+func mustWriteWALFile(t *testing.T, path string, values map[string][]tsm1.Value) {
+	t.Helper()
+
+	if err := os.MkdirAll(filepath.Dir(path), 0777); err != nil {
+		t.Fatal(err)
+	}
+
+	e := &tsm1.WriteWALEntry{Values: values}
+	b, err := e.Encode(nil)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	f, err := os.Create(path)
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer f.Close()
+
+	w := tsm1.NewWALSegmentWriter(f)
+	if err := w.Write(e.Type(), snappy.Encode(nil, b)); err != nil {
+		t.Fatal(err)
+	} else if err := w.Flush(); err != nil {
+		t.Fatal(err)
+	}
+}
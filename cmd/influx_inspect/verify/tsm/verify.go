@@ -2,10 +2,12 @@
 package tsm
 
 import (
+	"bytes"
 	"flag"
 	"fmt"
 	"hash/crc32"
 	"io"
+	"math"
 	"os"
 	"path/filepath"
 	"text/tabwriter"
@@ -39,6 +41,9 @@ func (cmd *Command) Run(args ...string) error {
 	var checkUTF8 bool
 	fs.BoolVar(&checkUTF8, "check-utf8", false, "Verify series keys are valid UTF-8")
 
+	var checkDuplicates bool
+	fs.BoolVar(&checkDuplicates, "check-duplicates", false, "Verify timestamps are not duplicated within a series")
+
 	fs.SetOutput(cmd.Stdout)
 	fs.Usage = cmd.printUsage
 
@@ -51,6 +56,8 @@ func (cmd *Command) Run(args ...string) error {
 	var runner verifier
 	if checkUTF8 {
 		runner = &verifyUTF8{}
+	} else if checkDuplicates {
+		runner = &verifyDuplicates{}
 	} else {
 		runner = &verifyChecksums{}
 	}
@@ -72,6 +79,13 @@ Usage: influx_inspect verify [flags]
     -check-utf8 
             Verify series keys are valid UTF-8.
             This check skips verification of block checksums.
+    -check-duplicates
+            Verify the timestamps of each series in a TSM file are
+            unique and sorted across its blocks, counting duplicate
+            and unordered points and unreadable blocks separately.
+            Points of a series duplicated across TSM files are
+            expected until they are compacted.
+            This check skips verification of block checksums.
  `, os.Getenv("HOME"))
 
 	fmt.Fprintf(cmd.Stdout, usage)
@@ -226,6 +240,92 @@ func (v *verifyUTF8) Run(w io.Writer, dataPath string) error {
 	return v.err
 }
 
+type verifyDuplicates struct {
+	verifyTSM
+	duplicates  int
+	unordered   int
+	totalErrors int
+	total       int
+}
+
+func (v *verifyDuplicates) Run(w io.Writer, dataPath string) error {
+	if err := v.loadFiles(dataPath); err != nil {
+		return err
+	}
+
+	v.Start()
+
+	for v.Next() {
+		f, reader := v.TSMReader()
+		if reader == nil {
+			break
+		}
+
+		var lastKey []byte
+		var lastTime int64
+		var lastBlock int
+		fileErrors := 0
+		blockItr := reader.BlockIterator()
+		for count := 0; blockItr.Next(); count++ {
+			key, _, _, _, _, buf, err := blockItr.Read()
+			if err != nil {
+				v.totalErrors++
+				fileErrors++
+				fmt.Fprintf(w, "%s: could not read key %q block %d due to error: %q\n", f, key, count, err)
+				continue
+			}
+			values, err := tsm1.DecodeBlock(buf, nil)
+			if err != nil {
+				v.totalErrors++
+				fileErrors++
+				fmt.Fprintf(w, "%s: could not decode key %q block %d due to error: %q\n", f, key, count, err)
+				continue
+			}
+
+			if !bytes.Equal(key, lastKey) {
+				lastKey, lastTime = append(lastKey[:0], key...), math.MinInt64
+			}
+
+			// Report the first duplicate and the first unordered timestamp
+			// of each block with the block holding the timestamp before it.
+			reportedDuplicate, reportedUnordered := false, false
+			for _, value := range values {
+				v.total++
+				switch ts := value.UnixNano(); {
+				case ts > lastTime:
+					lastTime, lastBlock = ts, count
+				case ts == lastTime:
+					v.duplicates++
+					if !reportedDuplicate {
+						fileErrors++
+						fmt.Fprintf(w, "%s: key %q block %d duplicates timestamp %d of block %d\n", f, key, count, ts, lastBlock)
+						reportedDuplicate = true
+					}
+				default:
+					v.unordered++
+					if !reportedUnordered {
+						fileErrors++
+						fmt.Fprintf(w, "%s: key %q block %d has timestamp %d, before timestamp %d of block %d\n", f, key, count, ts, lastTime, lastBlock)
+						reportedUnordered = true
+					}
+				}
+			}
+		}
+		if fileErrors == 0 {
+			fmt.Fprintf(w, "%s: healthy\n", f)
+		}
+		reader.Close()
+	}
+
+	fmt.Fprintf(w, "Duplicate Points: %d / %d, Unordered Points: %d / %d, Unreadable Blocks: %d, in %vs\n",
+		v.duplicates, v.total, v.unordered, v.total, v.totalErrors, v.Elapsed().Seconds())
+	if v.duplicates+v.unordered+v.totalErrors > 0 && v.err == nil {
+		v.err = errors.New("check-duplicates: failed")
+	}
+
+	return v.err
+}
+
 type verifier interface {
 	Run(w io.Writer, dataPath string) error
 }
@@ -1,3 +1,60 @@
 package tsm_test
 
-// TODO: write some tests
+import (
+	"bytes"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/influxdata/influxdb/cmd/influx_inspect/verify/tsm"
+	"github.com/influxdata/influxdb/tsdb/engine/tsm1"
+)
+
+func TestVerifyDuplicates(t *testing.T) {
+	dir := t.TempDir()
+	f, err := os.Create(filepath.Join(dir, "000000001-000000001."+tsm1.TSMFileExtension))
+	if err != nil {
+		t.Fatal(err)
+	}
+	w, err := tsm1.NewTSMWriter(f)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key := []byte("cpu#!~#value")
+	blocks := []tsm1.Values{
+		{tsm1.NewValue(10, 1.0), tsm1.NewValue(20, 2.0)},
+		// 20 duplicates the last timestamp of the first block and 15 is
+		// before it.
+		{tsm1.NewValue(20, 3.0), tsm1.NewValue(15, 4.0), tsm1.NewValue(30, 5.0)},
+	}
+	for _, values := range blocks {
+		if err := w.Write(key, values); err != nil {
+			t.Fatal(err)
+		}
+	}
+	if err := w.WriteIndex(); err != nil {
+		t.Fatal(err)
+	} else if err := w.Close(); err != nil {
+		t.Fatal(err)
+	}
+
+	var buf bytes.Buffer
+	cmd := tsm.NewCommand()
+	cmd.Stdout = &buf
+	if err := cmd.Run("-check-duplicates", "-dir", dir); err == nil || err.Error() != "check-duplicates: failed" {
+		t.Fatalf("unexpected error: %v", err)
+	}
+
+	out := buf.String()
+	for _, want := range []string{
+		`key "cpu#!~#value" block 1 duplicates timestamp 20 of block 0`,
+		`key "cpu#!~#value" block 1 has timestamp 15, before timestamp 20 of block 0`,
+		"Duplicate Points: 1 / 5, Unordered Points: 1 / 5, Unreadable Blocks: 0,",
+	} {
+		if !strings.Contains(out, want) {
+			t.Errorf("output does not contain %q:\n%s", want, out)
+		}
+	}
+}
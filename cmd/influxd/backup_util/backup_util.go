@@ -148,7 +148,7 @@ func (manifest *Manifest) Save(filename string) error {
 
 // LoadIncremental loads multiple manifest files from a given directory.
 func LoadIncremental(dir string) (*MetaEntry, map[uint64]*Entry, error) {
-	manifests, err := filepath.Glob(filepath.Join(dir, "*.manifest"))
+	manifests, err := loadManifests(dir)
 	if err != nil {
 		return nil, nil, err
 	}
@@ -158,32 +158,10 @@ func LoadIncremental(dir string) (*MetaEntry, map[uint64]*Entry, error) {
 		return nil, shards, nil
 	}
 
-	sort.Sort(sort.Reverse(sort.StringSlice(manifests)))
 	var metaEntry MetaEntry
 
-	for _, fileName := range manifests {
-		fi, err := os.Stat(fileName)
-		if err != nil {
-			return nil, nil, err
-		}
-
-		if fi.IsDir() {
-			continue
-		}
-
-		f, err := os.Open(fileName)
-		if err != nil {
-			return nil, nil, err
-		}
-
-		var manifest Manifest
-		err = json.NewDecoder(f).Decode(&manifest)
-		f.Close()
-		if err != nil {
-			return nil, nil, fmt.Errorf("read manifest: %v", err)
-		}
-
-		// sorted (descending) above, so first manifest is most recent
+	for _, manifest := range manifests {
+		// sorted (descending), so first manifest is most recent
 		if metaEntry.FileName == "" {
 			metaEntry = manifest.Meta
 		}
@@ -204,6 +182,74 @@ func LoadIncremental(dir string) (*MetaEntry, map[uint64]*Entry, error) {
 	return &metaEntry, shards, nil
 }
 
+// MissingShardFiles returns the manifest entries in a given directory for
+// shards that have no backup file present in that directory. Entries for
+// shards with at least one file present, such as those superseded by a later
+// incremental backup, are not reported.
+func MissingShardFiles(dir string) ([]Entry, error) {
+	manifests, err := loadManifests(dir)
+	if err != nil {
+		return nil, err
+	}
+
+	present := make(map[uint64]bool)
+	missing := make(map[uint64]Entry)
+	for _, manifest := range manifests {
+		for _, sh := range manifest.Files {
+			if _, err := os.Stat(filepath.Join(dir, sh.FileName)); err == nil {
+				present[sh.ShardID] = true
+			} else if _, ok := missing[sh.ShardID]; !ok {
+				missing[sh.ShardID] = sh
+			}
+		}
+	}
+
+	var entries []Entry
+	for id, sh := range missing {
+		if !present[id] {
+			entries = append(entries, sh)
+		}
+	}
+	sort.Slice(entries, func(i, j int) bool { return entries[i].ShardID < entries[j].ShardID })
+	return entries, nil
+}
+
+// loadManifests reads all manifest files in a given directory, most recent first.
+func loadManifests(dir string) ([]Manifest, error) {
+	fileNames, err := filepath.Glob(filepath.Join(dir, "*.manifest"))
+	if err != nil {
+		return nil, err
+	}
+
+	sort.Sort(sort.Reverse(sort.StringSlice(fileNames)))
+
+	manifests := make([]Manifest, 0, len(fileNames))
+	for _, fileName := range fileNames {
+		fi, err := os.Stat(fileName)
+		if err != nil {
+			return nil, err
+		}
+
+		if fi.IsDir() {
+			continue
+		}
+
+		f, err := os.Open(fileName)
+		if err != nil {
+			return nil, err
+		}
+
+		var manifest Manifest
+		err = json.NewDecoder(f).Decode(&manifest)
+		f.Close()
+		if err != nil {
+			return nil, fmt.Errorf("read manifest: %v", err)
+		}
+		manifests = append(manifests, manifest)
+	}
+	return manifests, nil
+}
+
 type CountingWriter struct {
 	io.Writer
 	Total int64 // Total # of bytes transferred
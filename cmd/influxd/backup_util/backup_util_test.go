@@ -0,0 +1,45 @@
+package backup_util_test
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/influxdata/influxdb/cmd/influxd/backup_util"
+	"github.com/stretchr/testify/require"
+)
+
+func TestMissingShardFiles(t *testing.T) {
+	dir := t.TempDir()
+
+	older := backup_util.Manifest{
+		Meta: backup_util.MetaEntry{FileName: "20200101T000000Z.meta"},
+		Files: []backup_util.Entry{
+			{Database: "db0", Policy: "rp0", ShardID: 1, FileName: "20200101T000000Z.s1.tar.gz"},
+			{Database: "db0", Policy: "rp0", ShardID: 2, FileName: "20200101T000000Z.s2.tar.gz"},
+		},
+	}
+	newer := backup_util.Manifest{
+		Meta: backup_util.MetaEntry{FileName: "20200102T000000Z.meta"},
+		Files: []backup_util.Entry{
+			{Database: "db0", Policy: "rp0", ShardID: 1, FileName: "20200102T000000Z.s1.tar.gz"},
+			{Database: "db0", Policy: "rp0", ShardID: 3, FileName: "20200102T000000Z.s3.tar.gz"},
+		},
+	}
+	require.NoError(t, older.Save(filepath.Join(dir, "20200101T000000Z.manifest")))
+	require.NoError(t, newer.Save(filepath.Join(dir, "20200102T000000Z.manifest")))
+
+	// Shard 1 is only missing its older file, which the newer backup supersedes.
+	for _, name := range []string{"20200102T000000Z.s1.tar.gz", "20200101T000000Z.s2.tar.gz"} {
+		require.NoError(t, os.WriteFile(filepath.Join(dir, name), []byte("data"), 0600))
+	}
+
+	missing, err := backup_util.MissingShardFiles(dir)
+	require.NoError(t, err)
+	require.Equal(t, []backup_util.Entry{newer.Files[1]}, missing)
+
+	_, shards, err := backup_util.LoadIncremental(dir)
+	require.NoError(t, err)
+	require.Len(t, shards, 2)
+	require.Equal(t, "20200102T000000Z.s1.tar.gz", shards[1].FileName)
+}
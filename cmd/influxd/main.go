@@ -100,7 +100,7 @@ func (m *Main) Run(args ...string) error {
 		select {
 		case <-signalCh:
 			cmd.Logger.Info("Second signal received, initializing hard shutdown")
-		case <-time.After(time.Second * 30):
+		case <-time.After(cmd.Server.ShutdownTimeout()):
 			cmd.Logger.Info("Time limit reached, initializing hard shutdown")
 		case <-cmd.Closed:
 			cmd.Logger.Info("Server shutdown completed")
@@ -99,6 +99,10 @@ func (cmd *Command) runOffline() error {
 }
 
 func (cmd *Command) runOnlinePortable() error {
+	if err := cmd.verifyPortable(); err != nil {
+		cmd.StderrLogger.Printf("error verifying backup files: %v", err)
+		return err
+	}
 	err := cmd.updateMetaPortable()
 	if err != nil {
 		cmd.StderrLogger.Printf("error updating meta: %v", err)
@@ -325,6 +329,36 @@ func (cmd *Command) unpackMeta() error {
 	return nil
 }
 
+// verifyPortable checks that the metastore backup and every shard file selected
+// for restore are present before the server is modified, so a restore cannot
+// leave metadata behind for shards whose data was never restored.
+func (cmd *Command) verifyPortable() error {
+	if _, err := os.Stat(filepath.Join(cmd.backupFilesPath, cmd.manifestMeta.FileName)); err != nil {
+		return fmt.Errorf("metastore backup %s: %w", cmd.manifestMeta.FileName, err)
+	}
+
+	missing, err := backup_util.MissingShardFiles(cmd.backupFilesPath)
+	if err != nil {
+		return err
+	}
+
+	var names []string
+	for _, file := range missing {
+		if cmd.sourceDatabase != "" && cmd.sourceDatabase != file.Database {
+			continue
+		} else if cmd.backupRetention != "" && cmd.backupRetention != file.Policy {
+			continue
+		} else if cmd.shard != 0 && cmd.shard != file.ShardID {
+			continue
+		}
+		names = append(names, file.FileName)
+	}
+	if len(names) > 0 {
+		return fmt.Errorf("backup files missing from %s: %s", cmd.backupFilesPath, strings.Join(names, ", "))
+	}
+	return nil
+}
+
 func (cmd *Command) updateMetaPortable() error {
 	var metaBytes []byte
 	fileName := filepath.Join(cmd.backupFilesPath, cmd.manifestMeta.FileName)
@@ -1,6 +1,7 @@
 package run
 
 import (
+	"errors"
 	"fmt"
 	"log"
 	"os"
@@ -8,6 +9,7 @@ import (
 	"path/filepath"
 	"regexp"
 	"strings"
+	"time"
 
 	"github.com/BurntSushi/toml"
 	"github.com/influxdata/influxdb/coordinator"
@@ -35,6 +37,9 @@ import (
 const (
 	// DefaultBindAddress is the default address for various RPC services.
 	DefaultBindAddress = "127.0.0.1:8088"
+
+	// DefaultShutdownTimeout is the default time allowed for a clean shutdown.
+	DefaultShutdownTimeout = 30 * time.Second
 )
 
 // Config represents the configuration format for the influxd binary.
@@ -63,6 +68,14 @@ type Config struct {
 	// BindAddress is the address that all TCP services use (Raft, Snapshot, Cluster, etc.)
 	BindAddress string `toml:"bind-address"`
 
+	// ShutdownTimeout is the time allowed for a clean shutdown after a
+	// termination signal before the process exits.
+	ShutdownTimeout itoml.Duration `toml:"shutdown-timeout"`
+
+	// ShutdownQueryDrainTimeout is the time running queries are given to
+	// finish on shutdown before they are killed.
+	ShutdownQueryDrainTimeout itoml.Duration `toml:"shutdown-query-drain-timeout"`
+
 	// TLS provides configuration options for all https endpoints.
 	TLS tlsconfig.Config `toml:"tls"`
 }
@@ -89,6 +102,7 @@ func NewConfig() *Config {
 	c.ContinuousQuery = continuous_querier.NewConfig()
 	c.Retention = retention.NewConfig()
 	c.BindAddress = DefaultBindAddress
+	c.ShutdownTimeout = itoml.Duration(DefaultShutdownTimeout)
 
 	return c
 }
@@ -159,6 +173,10 @@ func (c *Config) Validate() error {
 		return err
 	}
 
+	if err := c.Coordinator.Validate(); err != nil {
+		return err
+	}
+
 	if err := c.Monitor.Validate(); err != nil {
 		return err
 	}
@@ -195,6 +213,12 @@ func (c *Config) Validate() error {
 		return err
 	}
 
+	if c.ShutdownQueryDrainTimeout < 0 {
+		return errors.New("shutdown-query-drain-timeout must be non-negative")
+	} else if c.ShutdownTimeout > 0 && c.ShutdownQueryDrainTimeout >= c.ShutdownTimeout {
+		return errors.New("shutdown-query-drain-timeout must be less than shutdown-timeout")
+	}
+
 	return nil
 }
 
@@ -206,8 +230,10 @@ func (c *Config) ApplyEnvOverrides(getenv func(string) string) error {
 // Diagnostics returns a diagnostics representation of Config.
 func (c *Config) Diagnostics() (*diagnostics.Diagnostics, error) {
 	return diagnostics.RowFromMap(map[string]interface{}{
-		"reporting-disabled": c.ReportingDisabled,
-		"bind-address":       c.BindAddress,
+		"reporting-disabled":           c.ReportingDisabled,
+		"bind-address":                 c.BindAddress,
+		"shutdown-timeout":             c.ShutdownTimeout,
+		"shutdown-query-drain-timeout": c.ShutdownQueryDrainTimeout,
 	}), nil
 }
 
@@ -213,6 +213,64 @@ func NewServer(c *Config, buildInfo *BuildInfo) (*Server, error) {
 	s.PointsWriter = coordinator.NewPointsWriter()
 	s.PointsWriter.WriteTimeout = time.Duration(c.Coordinator.WriteTimeout)
 	s.PointsWriter.TSDBStore = s.TSDBStore
+	if len(c.Coordinator.DerivedFields) > 0 {
+		if s.PointsWriter.FieldDeriver, err = coordinator.NewFieldDeriver(c.Coordinator.DerivedFields); err != nil {
+			return nil, fmt.Errorf("derived fields: %s", err)
+		}
+	}
+	if len(c.Coordinator.WriteFreezes) > 0 {
+		if s.PointsWriter.WriteFreezes, err = coordinator.NewWriteFreezes(c.Coordinator.WriteFreezes); err != nil {
+			return nil, fmt.Errorf("write freezes: %s", err)
+		}
+	}
+	if (c.Coordinator.InvalidStrings != "" && c.Coordinator.InvalidStrings != coordinator.InvalidStringsAccept) || c.Coordinator.NormalizeStrings {
+		if s.PointsWriter.StringSanitizer, err = coordinator.NewStringSanitizer(c.Coordinator.InvalidStrings, c.Coordinator.NormalizeStrings); err != nil {
+			return nil, fmt.Errorf("invalid strings: %s", err)
+		}
+	}
+	if len(c.Coordinator.MeasurementRules) > 0 {
+		if s.PointsWriter.MeasurementRewriter, err = coordinator.NewMeasurementRewriter(c.Coordinator.MeasurementRules); err != nil {
+			return nil, fmt.Errorf("measurement rules: %s", err)
+		}
+	}
+	if len(c.Coordinator.ColumnConversions) > 0 {
+		if s.PointsWriter.ColumnConverter, err = coordinator.NewColumnConverter(c.Coordinator.ColumnConversions); err != nil {
+			return nil, fmt.Errorf("column conversions: %s", err)
+		}
+	}
+	if len(c.Coordinator.DefaultTags) > 0 {
+		if s.PointsWriter.TagInjector, err = coordinator.NewTagInjector(c.Coordinator.DefaultTags); err != nil {
+			return nil, fmt.Errorf("default tags: %s", err)
+		}
+	}
+	if len(c.Coordinator.FieldTypes) > 0 {
+		if s.PointsWriter.FieldTypeEnforcer, err = coordinator.NewFieldTypeEnforcer(c.Coordinator.FieldTypes); err != nil {
+			return nil, fmt.Errorf("field types: %s", err)
+		}
+	}
+	if len(c.Coordinator.WriteTimeBounds) > 0 {
+		if s.PointsWriter.WriteTimeBounds, err = coordinator.NewWriteTimeBounds(c.Coordinator.WriteTimeBounds); err != nil {
+			return nil, fmt.Errorf("write time bounds: %s", err)
+		}
+	}
+	if len(c.Coordinator.ValidationRules) > 0 {
+		if s.PointsWriter.PointValidator, err = coordinator.NewPointValidator(c.Coordinator.ValidationRules); err != nil {
+			return nil, fmt.Errorf("validation rules: %s", err)
+		}
+	}
+	if len(c.Coordinator.AlertRules) > 0 {
+		if s.PointsWriter.Alerter, err = coordinator.NewAlerter(c.Coordinator.AlertRules, c.Coordinator.AlertWebhookURL); err != nil {
+			return nil, fmt.Errorf("alert rules: %s", err)
+		}
+	}
+	if c.Coordinator.IngestAnomalyInterval > 0 {
+		s.PointsWriter.IngestMonitor = coordinator.NewIngestMonitor(time.Duration(c.Coordinator.IngestAnomalyInterval), c.Coordinator.IngestAnomalyThreshold)
+	}
+	var resultCache *coordinator.ResultCache
+	if c.Coordinator.QueryResultCacheSize > 0 {
+		resultCache = coordinator.NewResultCache(c.Coordinator.QueryResultCacheSize, time.Duration(c.Coordinator.QueryResultCacheTTL))
+		s.PointsWriter.ResultCache = resultCache
+	}
 
 	// Initialize query executor.
 	s.QueryExecutor = query.NewExecutor()
@@ -221,8 +279,9 @@ func NewServer(c *Config, buildInfo *BuildInfo) (*Server, error) {
 		TaskManager: s.QueryExecutor.TaskManager,
 		TSDBStore:   s.TSDBStore,
 		ShardMapper: &coordinator.LocalShardMapper{
-			MetaClient: s.MetaClient,
-			TSDBStore:  coordinator.LocalTSDBStore{Store: s.TSDBStore},
+			MetaClient:            s.MetaClient,
+			TSDBStore:             coordinator.LocalTSDBStore{Store: s.TSDBStore},
+			QueryDeletedDatabases: c.Coordinator.QueryDeletedDatabases,
 		},
 		StrictErrorHandling: s.TSDBStore.EngineOptions.Config.StrictErrorHandling,
 		Monitor:             s.Monitor,
@@ -230,11 +289,14 @@ func NewServer(c *Config, buildInfo *BuildInfo) (*Server, error) {
 		MaxSelectPointN:     c.Coordinator.MaxSelectPointN,
 		MaxSelectSeriesN:    c.Coordinator.MaxSelectSeriesN,
 		MaxSelectBucketsN:   c.Coordinator.MaxSelectBucketsN,
+		ResultCache:         resultCache,
+		SoftDeleteDatabases: c.Retention.Enabled && c.Retention.DatabaseDeleteGracePeriod > 0,
 	}
 	s.QueryExecutor.TaskManager.QueryTimeout = time.Duration(c.Coordinator.QueryTimeout)
 	s.QueryExecutor.TaskManager.LogQueriesAfter = time.Duration(c.Coordinator.LogQueriesAfter)
 	s.QueryExecutor.TaskManager.MaxConcurrentQueries = c.Coordinator.MaxConcurrentQueries
 	s.QueryExecutor.TaskManager.LogTimedoutQueries = c.Coordinator.LogTimedOutQueries
+	s.QueryExecutor.TaskManager.QueryHistorySize = c.Coordinator.QueryHistorySize
 
 	// Initialize the monitor
 	s.Monitor.Version = s.buildInfo.Version
@@ -525,36 +587,57 @@ func (s *Server) Close() error {
 	// Close services to allow any inflight requests to complete
 	// and prevent new requests from being accepted.
 	for _, service := range s.Services {
-		service.Close()
+		s.closeSubsystem(fmt.Sprintf("%T", service), service.Close)
 	}
 
 	s.config.deregisterDiagnostics(s.Monitor)
 
+	// Give running queries a chance to finish before they are killed. This
+	// happens before the points writer is closed so SELECT INTO queries can
+	// still write their results.
+	if s.QueryExecutor != nil {
+		if d := time.Duration(s.config.ShutdownQueryDrainTimeout); d > 0 {
+			if n := s.QueryExecutor.TaskManager.Drain(d); n > 0 {
+				s.Logger.Info("Killing queries still running after drain timeout", zap.Int("n", n))
+			}
+		}
+	}
+
 	if s.PointsWriter != nil {
-		s.PointsWriter.Close()
+		s.closeSubsystem("points writer", s.PointsWriter.Close)
 	}
 
 	if s.QueryExecutor != nil {
-		s.QueryExecutor.Close()
+		s.closeSubsystem("query executor", s.QueryExecutor.Close)
 	}
 
 	// Close the TSDBStore, no more reads or writes at this point
 	if s.TSDBStore != nil {
-		s.TSDBStore.Close()
+		s.closeSubsystem("tsdb store", s.TSDBStore.Close)
 	}
 
 	if s.Subscriber != nil {
-		s.Subscriber.Close()
+		s.closeSubsystem("subscriber", s.Subscriber.Close)
 	}
 
 	if s.MetaClient != nil {
-		s.MetaClient.Close()
+		s.closeSubsystem("meta client", s.MetaClient.Close)
 	}
 
 	close(s.closing)
 	return nil
 }
 
+// closeSubsystem closes a subsystem and logs how long it took and whether it failed.
+func (s *Server) closeSubsystem(name string, close func() error) {
+	start := time.Now()
+	if err := close(); err != nil {
+		s.Logger.Warn("Failed to close subsystem", zap.String("subsystem", name), zap.Duration("duration", time.Since(start)), zap.Error(err))
+		return
+	}
+	s.Logger.Info("Closed subsystem", zap.String("subsystem", name), zap.Duration("duration", time.Since(start)))
+}
+
 // startServerReporting starts periodic server reporting.
 func (s *Server) startServerReporting() {
 	s.reportServer()
@@ -685,6 +768,14 @@ func (s *Server) stopProfile() error {
 	return nil
 }
 
+// ShutdownTimeout returns the time allowed for a clean shutdown.
+func (s *Server) ShutdownTimeout() time.Duration {
+	if s != nil && s.config != nil && s.config.ShutdownTimeout > 0 {
+		return time.Duration(s.config.ShutdownTimeout)
+	}
+	return DefaultShutdownTimeout
+}
+
 func (s *Server) LogQueriesOnTermination() bool {
 	if s != nil && s.config != nil {
 		return s.config.Coordinator.TerminationQueryLog
@@ -0,0 +1,231 @@
+package coordinator
+
+import (
+	"bytes"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"net/http"
+	"sync"
+	"sync/atomic"
+	"time"
+
+	"github.com/influxdata/influxdb/models"
+	"github.com/influxdata/influxdb/query"
+	"github.com/influxdata/influxql"
+	"go.uber.org/zap"
+)
+
+const (
+	// alertQueueSize is the number of alert events waiting to be sent above
+	// which new events are dropped.
+	alertQueueSize = 1000
+
+	// alertTimeout is the timeout for sending an event to the webhook.
+	alertTimeout = 10 * time.Second
+)
+
+// AlertRule is the configuration of a threshold alert evaluated against
+// points when they are written.
+type AlertRule struct {
+	// Name identifies the rule in the events it raises.
+	Name string `toml:"name"`
+
+	// Database limits the rule to writes to a database.
+	// An empty value applies it to all databases.
+	Database string `toml:"database"`
+
+	// Measurement is the name of the measurement the rule applies to.
+	Measurement string `toml:"measurement"`
+
+	// Condition is an InfluxQL expression over the tags and fields of the
+	// point, such as "usage_idle < 10 AND host = 'server01'". An event is
+	// raised for each written point it is true for.
+	Condition string `toml:"condition"`
+}
+
+// Validate returns an error if the alert rule is invalid.
+func (r AlertRule) Validate() error {
+	if r.Name == "" {
+		return errors.New("alert rule name must be specified")
+	} else if r.Measurement == "" {
+		return fmt.Errorf("alert rule %q: measurement must be specified", r.Name)
+	}
+
+	if _, err := influxql.ParseExpr(r.Condition); err != nil {
+		return fmt.Errorf("alert rule %q: %s", r.Name, err)
+	}
+	return nil
+}
+
+// AlertEvent is sent to the alert webhook for a written point matching an
+// alert rule.
+type AlertEvent struct {
+	Rule            string                 `json:"rule"`
+	Database        string                 `json:"database"`
+	RetentionPolicy string                 `json:"retention_policy"`
+	Measurement     string                 `json:"measurement"`
+	Tags            map[string]string      `json:"tags,omitempty"`
+	Fields          map[string]interface{} `json:"fields"`
+	Time            time.Time              `json:"time"`
+}
+
+// alertRuleExpr is a parsed alert rule.
+type alertRuleExpr struct {
+	name     string
+	database string
+	cond     influxql.Expr
+}
+
+// Alerter evaluates alert rules against written points and posts the events
+// they raise to a webhook as JSON. Events are sent in the background and
+// dropped when too many are waiting so alerting never blocks writes.
+type Alerter struct {
+	rules  map[string][]alertRuleExpr // keyed by measurement
+	url    string
+	events chan AlertEvent
+
+	closing chan struct{}
+	wg      sync.WaitGroup
+	dropped int64
+
+	Client *http.Client
+	Logger *zap.Logger
+}
+
+// NewAlerter returns an Alerter evaluating rules and posting their events to url.
+func NewAlerter(rules []AlertRule, url string) (*Alerter, error) {
+	a := &Alerter{
+		rules:  make(map[string][]alertRuleExpr),
+		url:    url,
+		events: make(chan AlertEvent, alertQueueSize),
+		Client: &http.Client{Timeout: alertTimeout},
+		Logger: zap.NewNop(),
+	}
+	for _, r := range rules {
+		if err := r.Validate(); err != nil {
+			return nil, err
+		}
+		cond, _ := influxql.ParseExpr(r.Condition)
+		a.rules[r.Measurement] = append(a.rules[r.Measurement], alertRuleExpr{
+			name:     r.Name,
+			database: r.Database,
+			cond:     cond,
+		})
+	}
+	return a, nil
+}
+
+// Open starts sending events to the webhook.
+func (a *Alerter) Open() {
+	a.closing = make(chan struct{})
+	a.wg.Add(1)
+	go func() {
+		defer a.wg.Done()
+		a.run()
+	}()
+}
+
+// Close stops sending events. Events that were not sent yet are discarded.
+func (a *Alerter) Close() {
+	if a.closing == nil {
+		return
+	}
+	close(a.closing)
+	a.wg.Wait()
+	a.closing = nil
+}
+
+// Evaluate raises an event for each point written to database and
+// retentionPolicy matching an alert rule.
+func (a *Alerter) Evaluate(database, retentionPolicy string, points []models.Point) {
+	if a == nil || len(a.rules) == 0 {
+		return
+	}
+
+	for _, p := range points {
+		rules := a.rules[string(p.Name())]
+		if len(rules) == 0 {
+			continue
+		}
+
+		fields, err := p.Fields()
+		if err != nil {
+			continue
+		}
+		tags := p.Tags().Map()
+
+		m := make(map[string]interface{}, len(tags)+len(fields))
+		for k, v := range tags {
+			m[k] = v
+		}
+		for k, v := range fields {
+			m[k] = v
+		}
+		valuer := influxql.ValuerEval{
+			Valuer: influxql.MultiValuer(
+				query.MathValuer{},
+				influxql.MapValuer(m),
+			),
+			IntegerFloatDivision: true,
+		}
+
+		for _, r := range rules {
+			if r.database != "" && r.database != database {
+				continue
+			} else if !valuer.EvalBool(r.cond) {
+				continue
+			}
+
+			select {
+			case a.events <- AlertEvent{
+				Rule:            r.name,
+				Database:        database,
+				RetentionPolicy: retentionPolicy,
+				Measurement:     string(p.Name()),
+				Tags:            tags,
+				Fields:          fields,
+				Time:            p.Time().UTC(),
+			}:
+			default:
+				atomic.AddInt64(&a.dropped, 1)
+			}
+		}
+	}
+}
+
+// run sends queued events to the webhook until the alerter is closed.
+func (a *Alerter) run() {
+	for {
+		select {
+		case <-a.closing:
+			return
+		case ev := <-a.events:
+			if err := a.send(ev); err != nil {
+				a.Logger.Warn("Failed to send alert event", zap.String("rule", ev.Rule), zap.Error(err))
+			}
+			if n := atomic.SwapInt64(&a.dropped, 0); n > 0 {
+				a.Logger.Warn("Dropped alert events", zap.Int64("n", n))
+			}
+		}
+	}
+}
+
+// send posts ev to the webhook.
+func (a *Alerter) send(ev AlertEvent) error {
+	b, err := json.Marshal(ev)
+	if err != nil {
+		return err
+	}
+
+	resp, err := a.Client.Post(a.url, "application/json", bytes.NewReader(b))
+	if err != nil {
+		return err
+	}
+	resp.Body.Close()
+
+	if resp.StatusCode/100 != 2 {
+		return fmt.Errorf("unexpected status: %s", resp.Status)
+	}
+	return nil
+}
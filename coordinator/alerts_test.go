@@ -0,0 +1,71 @@
+package coordinator_test
+
+import (
+	"encoding/json"
+	"net/http"
+	"net/http/httptest"
+	"testing"
+	"time"
+
+	"github.com/influxdata/influxdb/coordinator"
+	"github.com/influxdata/influxdb/models"
+)
+
+func TestAlerter_Evaluate(t *testing.T) {
+	events := make(chan coordinator.AlertEvent, 10)
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		var ev coordinator.AlertEvent
+		if err := json.NewDecoder(r.Body).Decode(&ev); err != nil {
+			t.Errorf("unexpected error: %s", err)
+		}
+		events <- ev
+	}))
+	defer srv.Close()
+
+	a, err := coordinator.NewAlerter([]coordinator.AlertRule{
+		{Name: "idle_low", Measurement: "cpu", Condition: "usage_idle < 10 AND host = 'server01'"},
+		{Name: "other_db", Database: "other", Measurement: "cpu", Condition: "usage_idle < 10"},
+	}, srv.URL)
+	if err != nil {
+		t.Fatal(err)
+	}
+	a.Open()
+	defer a.Close()
+
+	now := time.Unix(10, 0)
+	a.Evaluate("db0", "autogen", []models.Point{
+		models.MustNewPoint("cpu", models.NewTags(map[string]string{"host": "server01"}), models.Fields{"usage_idle": 5.0}, now),
+		models.MustNewPoint("cpu", models.NewTags(map[string]string{"host": "server02"}), models.Fields{"usage_idle": 5.0}, now),
+		models.MustNewPoint("cpu", models.NewTags(map[string]string{"host": "server01"}), models.Fields{"usage_idle": 50.0}, now),
+		models.MustNewPoint("mem", models.NewTags(map[string]string{"host": "server01"}), models.Fields{"usage_idle": 5.0}, now),
+	})
+
+	select {
+	case ev := <-events:
+		if ev.Rule != "idle_low" || ev.Database != "db0" || ev.RetentionPolicy != "autogen" || ev.Measurement != "cpu" {
+			t.Fatalf("unexpected event: %+v", ev)
+		} else if ev.Tags["host"] != "server01" || ev.Fields["usage_idle"] != 5.0 || !ev.Time.Equal(now) {
+			t.Fatalf("unexpected event: %+v", ev)
+		}
+	case <-time.After(10 * time.Second):
+		t.Fatal("timeout waiting for alert event")
+	}
+
+	select {
+	case ev := <-events:
+		t.Fatalf("unexpected event: %+v", ev)
+	case <-time.After(50 * time.Millisecond):
+	}
+}
+
+func TestAlertRule_Validate(t *testing.T) {
+	for _, r := range []coordinator.AlertRule{
+		{Measurement: "m", Condition: "a > 1"},
+		{Name: "r", Condition: "a > 1"},
+		{Name: "r", Measurement: "m", Condition: "a >"},
+	} {
+		if err := r.Validate(); err == nil {
+			t.Fatalf("expected error for %+v", r)
+		}
+	}
+}
@@ -0,0 +1,179 @@
+package coordinator
+
+import (
+	"errors"
+	"fmt"
+	"strconv"
+
+	"github.com/influxdata/influxdb/models"
+)
+
+// ColumnConversion is the configuration of tags written as fields and fields
+// written as tags, so schema mistakes of the clients writing a measurement
+// can be corrected without changing them.
+type ColumnConversion struct {
+	// Database limits the conversion to writes to a database.
+	// An empty value applies it to all databases.
+	Database string `toml:"database"`
+
+	// Measurement is the name of the measurement the conversion applies to.
+	Measurement string `toml:"measurement"`
+
+	// TagsToFields are the keys of tags written as fields.
+	TagsToFields []string `toml:"tags-to-fields"`
+
+	// TagFieldTypes are the types of the fields written from tags, keyed by
+	// tag. A type is either "float", "integer", "unsigned", "string" or
+	// "boolean", and defaults to "string". Points with a value that does not
+	// parse as the type of its field are dropped.
+	TagFieldTypes map[string]string `toml:"tag-field-types"`
+
+	// FieldsToTags are the keys of fields written as tags.
+	FieldsToTags []string `toml:"fields-to-tags"`
+}
+
+// Validate returns an error if the column conversion is invalid.
+func (c ColumnConversion) Validate() error {
+	if c.Measurement == "" {
+		return errors.New("column conversion measurement must be specified")
+	} else if len(c.TagsToFields) == 0 && len(c.FieldsToTags) == 0 {
+		return fmt.Errorf("column conversion %q: tags-to-fields or fields-to-tags must be specified", c.Measurement)
+	}
+
+	keys := make(map[string]struct{})
+	for _, k := range append(append([]string(nil), c.TagsToFields...), c.FieldsToTags...) {
+		if k == "" || k == "time" {
+			return fmt.Errorf("column conversion %q: invalid key %q", c.Measurement, k)
+		} else if _, ok := keys[k]; ok {
+			return fmt.Errorf("column conversion %q: duplicate key %q", c.Measurement, k)
+		}
+		keys[k] = struct{}{}
+	}
+
+	for k, typ := range c.TagFieldTypes {
+		if !containsString(c.TagsToFields, k) {
+			return fmt.Errorf("column conversion %q: type of %q that is not in tags-to-fields", c.Measurement, k)
+		}
+		switch typ {
+		case "float", "integer", "unsigned", "string", "boolean":
+		default:
+			return fmt.Errorf("column conversion %q: unknown type %q of %q", c.Measurement, typ, k)
+		}
+	}
+	return nil
+}
+
+// ColumnConverter converts tags to fields and fields to tags before points
+// are written.
+type ColumnConverter struct {
+	conversions map[string][]ColumnConversion // keyed by measurement
+}
+
+// NewColumnConverter returns a ColumnConverter applying the given conversions.
+func NewColumnConverter(conversions []ColumnConversion) (*ColumnConverter, error) {
+	c := &ColumnConverter{conversions: make(map[string][]ColumnConversion)}
+	for _, cc := range conversions {
+		if err := cc.Validate(); err != nil {
+			return nil, err
+		}
+		c.conversions[cc.Measurement] = append(c.conversions[cc.Measurement], cc)
+	}
+	return c, nil
+}
+
+// Convert returns points with the conversions of database applied. A column
+// is left untouched if the point already has a column of the other kind with
+// its key, if a field has an empty string value or if it is the last field
+// of the point. Points with a tag value that does not parse as the type of
+// its field are dropped, as described by transformPoints.
+func (c *ColumnConverter) Convert(database string, points []models.Point) ([]models.Point, error) {
+	if c == nil || len(c.conversions) == 0 {
+		return points, nil
+	}
+	return transformPoints(points, func(t *pointTransform) error { return c.convert(database, t) }, nil)
+}
+
+// convert applies the conversions of database to the point of t. It returns
+// an error if a tag value does not parse as the type of its field.
+func (c *ColumnConverter) convert(database string, t *pointTransform) error {
+	if c == nil {
+		return nil
+	}
+	conversions := c.conversions[t.Name()]
+	if len(conversions) == 0 {
+		return nil
+	}
+
+	fields, err := t.Fields()
+	if err != nil {
+		return err
+	}
+
+	for _, cc := range conversions {
+		if cc.Database != "" && cc.Database != database {
+			continue
+		}
+
+		for _, k := range cc.TagsToFields {
+			v := t.Tags().Get([]byte(k))
+			if v == nil {
+				continue
+			} else if _, ok := fields[k]; ok {
+				continue
+			}
+			typ := cc.TagFieldTypes[k]
+			if typ == "" {
+				typ = "string"
+			}
+			fv := coerceFieldValue(string(v), typ)
+			if fv == nil {
+				return fmt.Errorf("tag %q on measurement %q is not a valid %s: %q", k, t.Name(), typ, v)
+			}
+			t.SetField(k, fv)
+			t.DeleteTag(k)
+			fields, _ = t.Fields()
+		}
+
+		for _, k := range cc.FieldsToTags {
+			v, ok := fields[k]
+			if !ok || len(fields) == 1 || t.Tags().Get([]byte(k)) != nil {
+				continue
+			}
+			s := formatFieldValue(v)
+			if s == "" {
+				continue
+			}
+			t.SetTag(k, s)
+			t.DeleteField(k)
+			fields, _ = t.Fields()
+		}
+	}
+	return nil
+}
+
+// cloneFields returns a copy of fields.
+func cloneFields(fields models.Fields) models.Fields {
+	other := make(models.Fields, len(fields))
+	for k, v := range fields {
+		other[k] = v
+	}
+	return other
+}
+
+// formatFieldValue returns the tag value of a field value.
+func formatFieldValue(v interface{}) string {
+	switch v := v.(type) {
+	case float64:
+		return strconv.FormatFloat(v, 'f', -1, 64)
+	case int64:
+		return strconv.FormatInt(v, 10)
+	case uint64:
+		return strconv.FormatUint(v, 10)
+	case bool:
+		return strconv.FormatBool(v)
+	case string:
+		return v
+	default:
+		return fmt.Sprint(v)
+	}
+}
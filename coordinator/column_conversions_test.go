@@ -0,0 +1,76 @@
+package coordinator_test
+
+import (
+	"testing"
+	"time"
+
+	"github.com/influxdata/influxdb/coordinator"
+	"github.com/influxdata/influxdb/models"
+	"github.com/influxdata/influxdb/tsdb"
+)
+
+func TestColumnConverter_Convert(t *testing.T) {
+	c, err := coordinator.NewColumnConverter([]coordinator.ColumnConversion{
+		{
+			Measurement:   "disk",
+			TagsToFields:  []string{"shard_id", "ratio", "path"},
+			TagFieldTypes: map[string]string{"shard_id": "integer", "ratio": "float"},
+			FieldsToTags:  []string{"region"},
+		},
+		{Database: "db0", Measurement: "cpu", FieldsToTags: []string{"core"}},
+	})
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	points := []models.Point{
+		models.MustNewPoint("disk", models.NewTags(map[string]string{"shard_id": "12", "ratio": "0.5", "path": "/data"}), models.Fields{"used": 1.0, "region": "us-east"}, time.Unix(0, 0)),
+		models.MustNewPoint("disk", nil, models.Fields{"region": "us-east"}, time.Unix(0, 0)),
+		models.MustNewPoint("cpu", nil, models.Fields{"value": 1.0, "core": int64(3)}, time.Unix(0, 0)),
+		// A shard_id that is not an integer drops the point.
+		models.MustNewPoint("disk", models.NewTags(map[string]string{"shard_id": "t"}), models.Fields{"used": 1.0}, time.Unix(0, 0)),
+	}
+
+	for _, tt := range []struct {
+		database string
+		exp      []string
+	}{
+		{"db0", []string{`disk,region=us-east path="/data",ratio=0.5,shard_id=12i,used=1 0`, `disk region="us-east" 0`, "cpu,core=3 value=1 0"}},
+		{"db1", []string{`disk,region=us-east path="/data",ratio=0.5,shard_id=12i,used=1 0`, `disk region="us-east" 0`, "cpu core=3i,value=1 0"}},
+	} {
+		got, err := c.Convert(tt.database, points)
+		if pwe, ok := err.(tsdb.PartialWriteError); !ok || pwe.Dropped != 1 {
+			t.Fatalf("%s: unexpected error: %v", tt.database, err)
+		} else if len(got) != len(tt.exp) {
+			t.Fatalf("%s: unexpected points: %v", tt.database, got)
+		}
+		for i, p := range got {
+			if p.String() != tt.exp[i] {
+				t.Fatalf("%s: unexpected point %d: exp %q, got %q", tt.database, i, tt.exp[i], p.String())
+			}
+		}
+		if got[1] != points[1] {
+			t.Fatalf("%s: point with a single field was replaced", tt.database)
+		}
+	}
+
+	// The points passed in are left untouched.
+	if s := points[0].String(); s != `disk,path=/data,ratio=0.5,shard_id=12 region="us-east",used=1 0` {
+		t.Fatalf("unexpected point: %q", s)
+	}
+}
+
+func TestColumnConversion_Validate(t *testing.T) {
+	for _, c := range []coordinator.ColumnConversion{
+		{TagsToFields: []string{"shard_id"}},
+		{Measurement: "disk"},
+		{Measurement: "disk", TagsToFields: []string{"time"}},
+		{Measurement: "disk", TagsToFields: []string{"region"}, FieldsToTags: []string{"region"}},
+		{Measurement: "disk", TagsToFields: []string{"shard_id"}, TagFieldTypes: map[string]string{"shard_id": "int"}},
+		{Measurement: "disk", TagsToFields: []string{"shard_id"}, TagFieldTypes: map[string]string{"region": "string"}},
+	} {
+		if err := c.Validate(); err == nil {
+			t.Fatalf("expected error for %+v", c)
+		}
+	}
+}
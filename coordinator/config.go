@@ -3,6 +3,8 @@
 package coordinator
 
 import (
+	"errors"
+	"net/url"
 	"time"
 
 	"github.com/influxdata/influxdb/monitor/diagnostics"
@@ -25,6 +27,15 @@ const (
 	// DefaultMaxSelectSeriesN is the maximum number of series a SELECT can run.
 	// A value of zero will make the maximum series count unlimited.
 	DefaultMaxSelectSeriesN = 0
+
+	// DefaultQueryResultCacheTTL is the default time the results of a SELECT
+	// are cached for when the result cache is enabled.
+	DefaultQueryResultCacheTTL = time.Minute
+
+	// DefaultIngestAnomalyThreshold is the default factor by which the points
+	// written during an interval must deviate from their baseline to be
+	// reported when ingest anomaly detection is enabled.
+	DefaultIngestAnomalyThreshold = 10.0
 )
 
 // Config represents the configuration for the coordinator service.
@@ -38,6 +49,59 @@ type Config struct {
 	MaxSelectSeriesN     int           `toml:"max-select-series"`
 	MaxSelectBucketsN    int           `toml:"max-select-buckets"`
 	TerminationQueryLog  bool          `toml:"termination-query-log"`
+	QueryResultCacheSize int           `toml:"query-result-cache-size"`
+	QueryResultCacheTTL  toml.Duration `toml:"query-result-cache-ttl"`
+	QueryHistorySize     int           `toml:"query-history-size"`
+
+	// QueryDeletedDatabases allows SELECT statements to read databases that
+	// were dropped but have not been removed yet.
+	QueryDeletedDatabases bool `toml:"query-deleted-databases"`
+
+	// WriteFreezes are databases and measurements frozen for writes.
+	WriteFreezes []WriteFreeze `toml:"write-freeze"`
+
+	// InvalidStrings is the policy for strings of the points written that are
+	// not valid UTF-8: "accept", "replace" or "reject".
+	InvalidStrings string `toml:"invalid-strings"`
+
+	// NormalizeStrings normalizes the strings of the points written to
+	// Unicode normalization form C.
+	NormalizeStrings bool `toml:"normalize-strings"`
+
+	// MeasurementRules rename the measurements of the points written.
+	MeasurementRules []MeasurementRule `toml:"measurement-rule"`
+
+	// ColumnConversions are tags written as fields and fields written as tags.
+	ColumnConversions []ColumnConversion `toml:"column-conversion"`
+
+	// DefaultTags are tags added to the points written that do not have them.
+	DefaultTags []DefaultTags `toml:"default-tags"`
+
+	// DerivedFields are fields computed from the other fields of a point when it is written.
+	DerivedFields []DerivedField `toml:"derived-field"`
+
+	// FieldTypes are the types pinned to fields of the points written.
+	FieldTypes []FieldType `toml:"field-type"`
+
+	// WriteTimeBounds limit how far from the current time the points written may be.
+	WriteTimeBounds []WriteTimeBound `toml:"write-time-bound"`
+
+	// ValidationRules are constraints checked against points before they are written.
+	ValidationRules []ValidationRule `toml:"validation-rule"`
+
+	// AlertRules are threshold alerts evaluated against points when they are written.
+	AlertRules []AlertRule `toml:"alert-rule"`
+
+	// AlertWebhookURL receives a JSON event for each written point matching an alert rule.
+	AlertWebhookURL string `toml:"alert-webhook-url"`
+
+	// IngestAnomalyInterval is the interval at which the points written are
+	// compared with their baseline. A value of 0 disables the comparison.
+	IngestAnomalyInterval toml.Duration `toml:"ingest-anomaly-interval"`
+
+	// IngestAnomalyThreshold is the factor by which the points written must
+	// deviate from their baseline to be reported.
+	IngestAnomalyThreshold float64 `toml:"ingest-anomaly-threshold"`
 }
 
 // NewConfig returns an instance of Config with defaults.
@@ -50,9 +114,84 @@ func NewConfig() Config {
 		MaxSelectSeriesN:     DefaultMaxSelectSeriesN,
 		TerminationQueryLog:  false,
 		LogTimedOutQueries:   false,
+		QueryResultCacheTTL:  toml.Duration(DefaultQueryResultCacheTTL),
+		InvalidStrings:       InvalidStringsAccept,
+
+		IngestAnomalyThreshold: DefaultIngestAnomalyThreshold,
 	}
 }
 
+// Validate returns an error if the Config is invalid.
+func (c Config) Validate() error {
+	if c.QueryResultCacheSize < 0 {
+		return errors.New("query-result-cache-size must be non-negative")
+	} else if c.QueryResultCacheSize > 0 && c.QueryResultCacheTTL <= 0 {
+		return errors.New("query-result-cache-ttl must be positive")
+	} else if c.QueryHistorySize < 0 {
+		return errors.New("query-history-size must be non-negative")
+	} else if c.IngestAnomalyInterval < 0 {
+		return errors.New("ingest-anomaly-interval must be non-negative")
+	} else if c.IngestAnomalyInterval > 0 && c.IngestAnomalyThreshold <= 1 {
+		return errors.New("ingest-anomaly-threshold must be greater than 1")
+	}
+
+	if _, err := NewWriteFreezes(c.WriteFreezes); err != nil {
+		return err
+	}
+	if _, err := NewStringSanitizer(c.InvalidStrings, c.NormalizeStrings); err != nil {
+		return err
+	}
+
+	for _, r := range c.MeasurementRules {
+		if err := r.Validate(); err != nil {
+			return err
+		}
+	}
+	for _, cc := range c.ColumnConversions {
+		if err := cc.Validate(); err != nil {
+			return err
+		}
+	}
+	for _, d := range c.DefaultTags {
+		if err := d.Validate(); err != nil {
+			return err
+		}
+	}
+	for _, f := range c.DerivedFields {
+		if err := f.Validate(); err != nil {
+			return err
+		}
+	}
+
+	for _, f := range c.FieldTypes {
+		if err := f.Validate(); err != nil {
+			return err
+		}
+	}
+	for _, b := range c.WriteTimeBounds {
+		if err := b.Validate(); err != nil {
+			return err
+		}
+	}
+	for _, r := range c.ValidationRules {
+		if err := r.Validate(); err != nil {
+			return err
+		}
+	}
+
+	if len(c.AlertRules) > 0 {
+		if u, err := url.Parse(c.AlertWebhookURL); err != nil || u.Scheme == "" || u.Host == "" {
+			return errors.New("alert-webhook-url must be a valid URL when alert rules are configured")
+		}
+	}
+	for _, r := range c.AlertRules {
+		if err := r.Validate(); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
 // Diagnostics returns a diagnostics representation of a subset of the Config.
 func (c Config) Diagnostics() (*diagnostics.Diagnostics, error) {
 	return diagnostics.RowFromMap(map[string]interface{}{
@@ -64,5 +203,15 @@ func (c Config) Diagnostics() (*diagnostics.Diagnostics, error) {
 		"max-select-point":       c.MaxSelectPointN,
 		"max-select-series":      c.MaxSelectSeriesN,
 		"max-select-buckets":     c.MaxSelectBucketsN,
+
+		"query-result-cache-size": c.QueryResultCacheSize,
+		"query-result-cache-ttl":  c.QueryResultCacheTTL,
+		"query-deleted-databases": c.QueryDeletedDatabases,
+		"query-history-size":      c.QueryHistorySize,
+
+		"alert-webhook-url": c.AlertWebhookURL,
+
+		"ingest-anomaly-interval":  c.IngestAnomalyInterval,
+		"ingest-anomaly-threshold": c.IngestAnomalyThreshold,
 	}), nil
 }
@@ -13,6 +13,11 @@ func TestConfig_Parse(t *testing.T) {
 	var c coordinator.Config
 	if _, err := toml.Decode(`
 write-timeout = "20s"
+
+[[derived-field]]
+measurement = "req"
+field = "duration_ms"
+expr = "end_ns - start_ns"
 `, &c); err != nil {
 		t.Fatal(err)
 	}
@@ -20,5 +25,9 @@ write-timeout = "20s"
 	// Validate configuration.
 	if time.Duration(c.WriteTimeout) != 20*time.Second {
 		t.Fatalf("unexpected write timeout s: %s", c.WriteTimeout)
+	} else if len(c.DerivedFields) != 1 || c.DerivedFields[0].Field != "duration_ms" {
+		t.Fatalf("unexpected derived fields: %+v", c.DerivedFields)
+	} else if err := c.Validate(); err != nil {
+		t.Fatal(err)
 	}
 }
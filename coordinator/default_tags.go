@@ -0,0 +1,76 @@
+package coordinator
+
+import (
+	"fmt"
+
+	"github.com/influxdata/influxdb/models"
+)
+
+// DefaultTags is the configuration of constant tags added to the points
+// written to a database, such as the environment or cluster they come from.
+type DefaultTags struct {
+	// Database limits the tags to writes to a database.
+	// An empty value applies them to all databases.
+	Database string `toml:"database"`
+
+	// Tags are the tags added to the points that do not already have them.
+	Tags map[string]string `toml:"tags"`
+}
+
+// Validate returns an error if the default tags are invalid.
+func (d DefaultTags) Validate() error {
+	if len(d.Tags) == 0 {
+		return fmt.Errorf("default tags %q: tags must be specified", d.Database)
+	}
+	for k, v := range d.Tags {
+		if k == "" || v == "" {
+			return fmt.Errorf("default tags %q: tag keys and values must not be empty", d.Database)
+		} else if k == "time" {
+			return fmt.Errorf("default tags %q: invalid tag key %q", d.Database, k)
+		}
+	}
+	return nil
+}
+
+// TagInjector adds default tags to points before they are written.
+type TagInjector struct {
+	tags map[string]map[string]string // keyed by database
+}
+
+// NewTagInjector returns a TagInjector adding the given tags.
+func NewTagInjector(tags []DefaultTags) (*TagInjector, error) {
+	t := &TagInjector{tags: make(map[string]map[string]string)}
+	for _, d := range tags {
+		if err := d.Validate(); err != nil {
+			return nil, err
+		} else if _, ok := t.tags[d.Database]; ok {
+			return nil, fmt.Errorf("default tags %q: duplicate database", d.Database)
+		}
+		t.tags[d.Database] = d.Tags
+	}
+	return t, nil
+}
+
+// Inject returns points with the default tags of database added. Tags of
+// the database take precedence over the tags of all databases, and tags
+// already present on a point are left untouched.
+func (t *TagInjector) Inject(database string, points []models.Point) ([]models.Point, error) {
+	if t == nil || (len(t.tags[database]) == 0 && len(t.tags[""]) == 0) {
+		return points, nil
+	}
+	return transformPoints(points, func(pt *pointTransform) error { t.inject(database, pt); return nil }, nil)
+}
+
+// inject adds the default tags of database to the point of pt.
+func (t *TagInjector) inject(database string, pt *pointTransform) {
+	if t == nil {
+		return
+	}
+	for _, m := range []map[string]string{t.tags[database], t.tags[""]} {
+		for k, v := range m {
+			if pt.Tags().Get([]byte(k)) == nil {
+				pt.SetTag(k, v)
+			}
+		}
+	}
+}
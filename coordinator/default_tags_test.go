@@ -0,0 +1,62 @@
+package coordinator_test
+
+import (
+	"testing"
+	"time"
+
+	"github.com/influxdata/influxdb/coordinator"
+	"github.com/influxdata/influxdb/models"
+)
+
+func TestTagInjector_Inject(t *testing.T) {
+	inj, err := coordinator.NewTagInjector([]coordinator.DefaultTags{
+		{Tags: map[string]string{"env": "prod", "cluster": "us-east"}},
+		{Database: "db0", Tags: map[string]string{"env": "staging"}},
+	})
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	points := []models.Point{
+		models.MustNewPoint("cpu", nil, models.Fields{"value": 1.0}, time.Unix(0, 0)),
+		models.MustNewPoint("cpu", models.NewTags(map[string]string{"env": "dev", "cluster": "eu-west"}), models.Fields{"value": 2.0}, time.Unix(1, 0)),
+	}
+
+	for _, tt := range []struct {
+		database string
+		exp      []string
+	}{
+		{"db0", []string{"cpu,cluster=us-east,env=staging value=1 0", "cpu,cluster=eu-west,env=dev value=2 1000000000"}},
+		{"db1", []string{"cpu,cluster=us-east,env=prod value=1 0", "cpu,cluster=eu-west,env=dev value=2 1000000000"}},
+	} {
+		got, err := inj.Inject(tt.database, points)
+		if err != nil {
+			t.Fatal(err)
+		}
+		for i, p := range got {
+			if p.String() != tt.exp[i] {
+				t.Fatalf("%s: unexpected point %d: exp %q, got %q", tt.database, i, tt.exp[i], p.String())
+			}
+		}
+		if got[1] != points[1] {
+			t.Fatalf("%s: point with all tags was replaced", tt.database)
+		}
+	}
+
+	// The points passed in are left untouched.
+	if s := points[0].String(); s != "cpu value=1 0" {
+		t.Fatalf("unexpected point: %q", s)
+	}
+}
+
+func TestDefaultTags_Validate(t *testing.T) {
+	for _, d := range []coordinator.DefaultTags{
+		{Database: "db0"},
+		{Database: "db0", Tags: map[string]string{"env": ""}},
+		{Database: "db0", Tags: map[string]string{"time": "x"}},
+	} {
+		if err := d.Validate(); err == nil {
+			t.Fatalf("expected error for %+v", d)
+		}
+	}
+}
@@ -0,0 +1,131 @@
+package coordinator
+
+import (
+	"errors"
+	"fmt"
+	"math"
+
+	"github.com/influxdata/influxdb/models"
+	"github.com/influxdata/influxdb/query"
+	"github.com/influxdata/influxql"
+)
+
+// DerivedField is the configuration of a field computed from the other
+// fields of a point when the point is written.
+type DerivedField struct {
+	// Database limits the derived field to writes to a database.
+	// An empty value applies it to all databases.
+	Database string `toml:"database"`
+
+	// Measurement is the name of the measurement the field is added to.
+	Measurement string `toml:"measurement"`
+
+	// Field is the name of the derived field.
+	Field string `toml:"field"`
+
+	// Expr is an InfluxQL expression over the fields of the point,
+	// such as "end_ns - start_ns" or "bytes / 1024".
+	Expr string `toml:"expr"`
+}
+
+// Validate returns an error if the derived field is invalid.
+func (f DerivedField) Validate() error {
+	if f.Measurement == "" {
+		return errors.New("derived field measurement must be specified")
+	} else if f.Field == "" {
+		return errors.New("derived field name must be specified")
+	}
+
+	if _, err := influxql.ParseExpr(f.Expr); err != nil {
+		return fmt.Errorf("derived field %q: %s", f.Field, err)
+	}
+	return nil
+}
+
+// derivedFieldExpr is a parsed derived field.
+type derivedFieldExpr struct {
+	database string
+	field    string
+	expr     influxql.Expr
+}
+
+// FieldDeriver adds derived fields to points before they are written.
+type FieldDeriver struct {
+	fields map[string][]derivedFieldExpr // keyed by measurement
+}
+
+// NewFieldDeriver returns a FieldDeriver computing the given fields.
+func NewFieldDeriver(fields []DerivedField) (*FieldDeriver, error) {
+	d := &FieldDeriver{fields: make(map[string][]derivedFieldExpr)}
+	for _, f := range fields {
+		if err := f.Validate(); err != nil {
+			return nil, err
+		}
+		expr, _ := influxql.ParseExpr(f.Expr)
+		d.fields[f.Measurement] = append(d.fields[f.Measurement], derivedFieldExpr{
+			database: f.Database,
+			field:    f.Field,
+			expr:     expr,
+		})
+	}
+	return d, nil
+}
+
+// Derive returns points with the derived fields of database added. Fields
+// already present on a point are left untouched, and a field is not added if
+// its expression evaluates to null, such as when an input field is missing,
+// or to a float that cannot be stored. Points whose fields cannot be derived
+// are dropped, as described by transformPoints.
+func (d *FieldDeriver) Derive(database string, points []models.Point) ([]models.Point, error) {
+	if d == nil || len(d.fields) == 0 {
+		return points, nil
+	}
+	return transformPoints(points, func(t *pointTransform) error { return d.derive(database, t) }, nil)
+}
+
+// derive adds the derived fields of database to the point of t.
+func (d *FieldDeriver) derive(database string, t *pointTransform) error {
+	if d == nil {
+		return nil
+	}
+	exprs := d.fields[t.Name()]
+	if len(exprs) == 0 {
+		return nil
+	}
+
+	fields, err := t.Fields()
+	if err != nil {
+		return err
+	}
+
+	valuer := influxql.ValuerEval{
+		Valuer: influxql.MultiValuer(
+			query.MathValuer{},
+			influxql.MapValuer(fields),
+		),
+		IntegerFloatDivision: true,
+	}
+
+	for _, e := range exprs {
+		if e.database != "" && e.database != database {
+			continue
+		} else if _, ok := fields[e.field]; ok {
+			continue
+		}
+
+		v := valuer.Eval(e.expr)
+		switch v := v.(type) {
+		case float64:
+			if math.IsNaN(v) || math.IsInf(v, 0) {
+				continue
+			}
+		case int64, uint64, string, bool:
+		case nil:
+			continue
+		default:
+			return fmt.Errorf("derived field %q on measurement %q: unsupported type %T", e.field, t.Name(), v)
+		}
+		t.SetField(e.field, v)
+	}
+	return nil
+}
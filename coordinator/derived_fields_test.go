@@ -0,0 +1,65 @@
+package coordinator_test
+
+import (
+	"reflect"
+	"testing"
+	"time"
+
+	"github.com/influxdata/influxdb/coordinator"
+	"github.com/influxdata/influxdb/models"
+)
+
+func TestFieldDeriver_Derive(t *testing.T) {
+	d, err := coordinator.NewFieldDeriver([]coordinator.DerivedField{
+		{Measurement: "req", Field: "duration_ms", Expr: "(end_ns - start_ns) / 1000000"},
+		{Measurement: "req", Field: "kb", Expr: "bytes / 1024"},
+		{Database: "other", Measurement: "req", Field: "ignored", Expr: "1"},
+	})
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	now := time.Unix(0, 0)
+	points := []models.Point{
+		models.MustNewPoint("req", nil, models.Fields{"start_ns": int64(1000000), "end_ns": int64(4000000), "bytes": int64(2048)}, now),
+		models.MustNewPoint("req", nil, models.Fields{"bytes": 512.0, "kb": "preset"}, now),
+		models.MustNewPoint("cpu", nil, models.Fields{"value": 1.0}, now),
+	}
+	original := points[0]
+
+	got, err := d.Derive("db0", points)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	exp := []models.Fields{
+		{"start_ns": int64(1000000), "end_ns": int64(4000000), "bytes": int64(2048), "duration_ms": 3.0, "kb": 2.0},
+		{"bytes": 512.0, "kb": "preset"},
+		{"value": 1.0},
+	}
+	for i, p := range got {
+		fields, err := p.Fields()
+		if err != nil {
+			t.Fatal(err)
+		} else if !reflect.DeepEqual(fields, exp[i]) {
+			t.Fatalf("%d. unexpected fields:\n\texp=%v\n\tgot=%v", i, exp[i], fields)
+		}
+	}
+
+	// The caller's slice must not be modified.
+	if points[0] != original {
+		t.Fatal("expected input points to be left unmodified")
+	}
+}
+
+func TestDerivedField_Validate(t *testing.T) {
+	for _, f := range []coordinator.DerivedField{
+		{Field: "f", Expr: "a + b"},
+		{Measurement: "m", Expr: "a + b"},
+		{Measurement: "m", Field: "f", Expr: "a +"},
+	} {
+		if err := f.Validate(); err == nil {
+			t.Fatalf("expected error for %+v", f)
+		}
+	}
+}
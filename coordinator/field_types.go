@@ -0,0 +1,262 @@
+package coordinator
+
+import (
+	"errors"
+	"fmt"
+	"math"
+	"strconv"
+	"sync/atomic"
+
+	"github.com/influxdata/influxdb/models"
+)
+
+const (
+	// FieldTypeActionCoerce converts the values of a pinned field to its
+	// type, and drops the points whose values cannot be converted.
+	FieldTypeActionCoerce = "coerce"
+
+	// FieldTypeActionReject drops the points whose values of a pinned field
+	// are not of its type.
+	FieldTypeActionReject = "reject"
+)
+
+// FieldType is the configuration of the type pinned to a field, so points
+// written with another type are coerced or rejected predictably instead of
+// the first type written to a shard deciding it.
+type FieldType struct {
+	// Database limits the pinned type to writes to a database.
+	// An empty value applies it to all databases.
+	Database string `toml:"database"`
+
+	// Measurement is the name of the measurement of the field.
+	Measurement string `toml:"measurement"`
+
+	// Field is the name of the field.
+	Field string `toml:"field"`
+
+	// Type is either "float", "integer", "unsigned", "string" or "boolean".
+	Type string `toml:"type"`
+
+	// Action is either "coerce" to convert values of other types or "reject"
+	// to drop their points. Defaults to "coerce".
+	Action string `toml:"action"`
+}
+
+// Validate returns an error if the field type is invalid.
+func (f FieldType) Validate() error {
+	if f.Measurement == "" {
+		return errors.New("field type measurement must be specified")
+	} else if f.Field == "" {
+		return errors.New("field type field must be specified")
+	}
+
+	switch f.Type {
+	case "float", "integer", "unsigned", "string", "boolean":
+	default:
+		return fmt.Errorf("field type %q: unknown type %q", f.Field, f.Type)
+	}
+
+	switch f.Action {
+	case "", FieldTypeActionCoerce, FieldTypeActionReject:
+	default:
+		return fmt.Errorf("field type %q: unknown action %q", f.Field, f.Action)
+	}
+	return nil
+}
+
+// FieldTypeEnforcer coerces or rejects the values of pinned fields of other
+// types before points are written.
+type FieldTypeEnforcer struct {
+	types map[string][]FieldType // keyed by measurement
+
+	coercedN  int64
+	rejectedN int64
+}
+
+// NewFieldTypeEnforcer returns a FieldTypeEnforcer pinning the given types.
+func NewFieldTypeEnforcer(types []FieldType) (*FieldTypeEnforcer, error) {
+	e := &FieldTypeEnforcer{types: make(map[string][]FieldType)}
+	for _, f := range types {
+		if err := f.Validate(); err != nil {
+			return nil, err
+		}
+		for _, other := range e.types[f.Measurement] {
+			if other.Database == f.Database && other.Field == f.Field {
+				return nil, fmt.Errorf("field type %q: duplicate field", f.Field)
+			}
+		}
+		e.types[f.Measurement] = append(e.types[f.Measurement], f)
+	}
+	return e, nil
+}
+
+// CoercedN returns the number of points whose values were coerced.
+func (e *FieldTypeEnforcer) CoercedN() int64 {
+	if e == nil {
+		return 0
+	}
+	return atomic.LoadInt64(&e.coercedN)
+}
+
+// RejectedN returns the number of points dropped for a value of another type.
+func (e *FieldTypeEnforcer) RejectedN() int64 {
+	if e == nil {
+		return 0
+	}
+	return atomic.LoadInt64(&e.rejectedN)
+}
+
+// Enforce returns points with the pinned field types of database enforced.
+// The type pinned for database takes precedence over the type pinned for all
+// databases. Points with values that cannot be enforced are dropped, as
+// described by transformPoints.
+func (e *FieldTypeEnforcer) Enforce(database string, points []models.Point) ([]models.Point, error) {
+	if e == nil || len(e.types) == 0 {
+		return points, nil
+	}
+	return transformPoints(points, func(t *pointTransform) error { return e.enforce(database, t) }, nil)
+}
+
+// enforce enforces the pinned field types of database on the point of t.
+// It returns an error if the point must be dropped.
+func (e *FieldTypeEnforcer) enforce(database string, t *pointTransform) error {
+	if e == nil {
+		return nil
+	}
+	types := e.types[t.Name()]
+	if len(types) == 0 {
+		return nil
+	}
+
+	fields, err := t.Fields()
+	if err != nil {
+		return err
+	}
+
+	coerced := false
+	for _, f := range e.fieldTypes(database, types) {
+		v, ok := fields[f.Field]
+		if !ok || fieldValueType(v) == f.Type {
+			continue
+		}
+
+		var cv interface{}
+		if f.Action != FieldTypeActionReject {
+			cv = coerceFieldValue(v, f.Type)
+		}
+		if cv == nil {
+			atomic.AddInt64(&e.rejectedN, 1)
+			return fmt.Errorf("field %q on measurement %q is %s, pinned to %s", f.Field, t.Name(), fieldValueType(v), f.Type)
+		}
+
+		t.SetField(f.Field, cv)
+		fields, _ = t.Fields()
+		coerced = true
+	}
+	if coerced {
+		atomic.AddInt64(&e.coercedN, 1)
+	}
+	return nil
+}
+
+// fieldTypes returns the types pinned for database among types, where the
+// types pinned for database take precedence over those for all databases.
+func (e *FieldTypeEnforcer) fieldTypes(database string, types []FieldType) []FieldType {
+	var a []FieldType
+	for _, f := range types {
+		if f.Database == database {
+			a = append(a, f)
+		}
+	}
+	for _, f := range types {
+		if f.Database != "" {
+			continue
+		}
+		overridden := false
+		for _, other := range a {
+			if other.Field == f.Field {
+				overridden = true
+				break
+			}
+		}
+		if !overridden {
+			a = append(a, f)
+		}
+	}
+	return a
+}
+
+// fieldValueType returns the name of the type of a field value.
+func fieldValueType(v interface{}) string {
+	switch v.(type) {
+	case float64:
+		return "float"
+	case int64:
+		return "integer"
+	case uint64:
+		return "unsigned"
+	case string:
+		return "string"
+	case bool:
+		return "boolean"
+	default:
+		return fmt.Sprintf("%T", v)
+	}
+}
+
+// coerceFieldValue returns v converted to typ, or nil if it cannot be
+// converted without losing its value.
+func coerceFieldValue(v interface{}, typ string) interface{} {
+	switch typ {
+	case "float":
+		switch v := v.(type) {
+		case int64:
+			return float64(v)
+		case uint64:
+			return float64(v)
+		case string:
+			if f, err := strconv.ParseFloat(v, 64); err == nil && !math.IsNaN(f) && !math.IsInf(f, 0) {
+				return f
+			}
+		}
+	case "integer":
+		switch v := v.(type) {
+		case float64:
+			if v == math.Trunc(v) && v >= math.MinInt64 && v < math.MaxInt64 {
+				return int64(v)
+			}
+		case uint64:
+			if v <= math.MaxInt64 {
+				return int64(v)
+			}
+		case string:
+			if i, err := strconv.ParseInt(v, 10, 64); err == nil {
+				return i
+			}
+		}
+	case "unsigned":
+		switch v := v.(type) {
+		case float64:
+			if v == math.Trunc(v) && v >= 0 && v < math.MaxUint64 {
+				return uint64(v)
+			}
+		case int64:
+			if v >= 0 {
+				return uint64(v)
+			}
+		case string:
+			if u, err := strconv.ParseUint(v, 10, 64); err == nil {
+				return u
+			}
+		}
+	case "string":
+		return formatFieldValue(v)
+	case "boolean":
+		if s, ok := v.(string); ok {
+			if b, err := strconv.ParseBool(s); err == nil {
+				return b
+			}
+		}
+	}
+	return nil
+}
@@ -0,0 +1,77 @@
+package coordinator_test
+
+import (
+	"testing"
+	"time"
+
+	"github.com/influxdata/influxdb/coordinator"
+	"github.com/influxdata/influxdb/models"
+	"github.com/influxdata/influxdb/tsdb"
+)
+
+func TestFieldTypeEnforcer_Enforce(t *testing.T) {
+	e, err := coordinator.NewFieldTypeEnforcer([]coordinator.FieldType{
+		{Measurement: "http", Field: "status", Type: "string"},
+		{Measurement: "http", Field: "bytes", Type: "integer"},
+		{Database: "db0", Measurement: "http", Field: "bytes", Type: "float", Action: coordinator.FieldTypeActionReject},
+	})
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	points := []models.Point{
+		models.MustNewPoint("http", nil, models.Fields{"status": "200", "bytes": int64(10)}, time.Unix(0, 0)),
+		models.MustNewPoint("http", nil, models.Fields{"status": int64(404), "bytes": 12.0}, time.Unix(0, 0)),
+		models.MustNewPoint("http", nil, models.Fields{"status": "500", "bytes": 1.5}, time.Unix(0, 0)),
+	}
+
+	// Values are coerced to the types pinned for all databases.
+	got, err := e.Enforce("db1", points)
+	if pwe, ok := err.(tsdb.PartialWriteError); !ok || pwe.Dropped != 1 {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	exp := []string{`http bytes=10i,status="200" 0`, `http bytes=12i,status="404" 0`}
+	if len(got) != len(exp) {
+		t.Fatalf("unexpected points: %v", got)
+	}
+	for i, p := range got {
+		if p.String() != exp[i] {
+			t.Fatalf("unexpected point %d: exp %q, got %q", i, exp[i], p.String())
+		}
+	}
+	if got[0] != points[0] {
+		t.Fatal("point with pinned types was replaced")
+	}
+
+	// The type pinned for the database takes precedence and rejects integers.
+	got, err = e.Enforce("db0", points)
+	if pwe, ok := err.(tsdb.PartialWriteError); !ok || pwe.Dropped != 1 {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	exp = []string{`http bytes=12,status="404" 0`, `http bytes=1.5,status="500" 0`}
+	if len(got) != len(exp) {
+		t.Fatalf("unexpected points: %v", got)
+	}
+	for i, p := range got {
+		if p.String() != exp[i] {
+			t.Fatalf("unexpected point %d: exp %q, got %q", i, exp[i], p.String())
+		}
+	}
+
+	if n := e.RejectedN(); n != 2 {
+		t.Fatalf("unexpected rejected count: %d", n)
+	}
+}
+
+func TestFieldType_Validate(t *testing.T) {
+	for _, f := range []coordinator.FieldType{
+		{Field: "status", Type: "string"},
+		{Measurement: "http", Type: "string"},
+		{Measurement: "http", Field: "status", Type: "text"},
+		{Measurement: "http", Field: "status", Type: "string", Action: "drop"},
+	} {
+		if err := f.Validate(); err == nil {
+			t.Fatalf("expected error for %+v", f)
+		}
+	}
+}
@@ -0,0 +1,295 @@
+package coordinator
+
+import (
+	"math"
+	"sync"
+	"sync/atomic"
+	"time"
+
+	"github.com/influxdata/influxdb/models"
+	"go.uber.org/zap"
+)
+
+const (
+	// ingestBaselineWeight is the weight of the latest interval in the
+	// exponentially weighted baselines.
+	ingestBaselineWeight = 0.2
+
+	// ingestWarmupIntervals is the number of intervals observed before the
+	// baselines of a measurement are used to detect anomalies.
+	ingestWarmupIntervals = 3
+
+	// ingestIdleIntervals is the number of intervals without points after
+	// which the baselines of a measurement are forgotten.
+	ingestIdleIntervals = 10
+
+	// ingestMaxMeasurements is the maximum number of measurements whose
+	// baselines are kept. Measurements first written once it is reached are
+	// not monitored until others are forgotten.
+	ingestMaxMeasurements = 10000
+)
+
+// IngestAnomaly is a deviation of the points written to a measurement during
+// an interval from its baseline. Field is empty when the number of points
+// deviates, otherwise it names the numeric field whose mean deviates.
+type IngestAnomaly struct {
+	Database    string
+	Measurement string
+	Field       string
+	Value       float64
+	Baseline    float64
+}
+
+// ingestKey identifies a measurement of a database.
+type ingestKey struct {
+	database    string
+	measurement string
+}
+
+// ingestStats are the statistics of the points written to a measurement.
+type ingestStats struct {
+	// Statistics of the current interval.
+	pointN float64
+	sums   map[string]float64
+	counts map[string]float64
+
+	// Baselines of the previous intervals.
+	intervals int
+	idle      int
+	rate      float64
+	means     map[string]float64
+}
+
+// newIngestStats returns empty statistics.
+func newIngestStats() *ingestStats {
+	return &ingestStats{
+		sums:   make(map[string]float64),
+		counts: make(map[string]float64),
+		means:  make(map[string]float64),
+	}
+}
+
+// IngestMonitor compares the points written to each measurement during an
+// interval with a rolling baseline of the previous intervals, and logs the
+// measurements whose number of points or numeric field means deviate from it
+// by more than a factor. This catches agents that stopped reporting or
+// started writing values in another unit. The baselines adapt, so a lasting
+// change is only reported until it becomes the norm.
+type IngestMonitor struct {
+	mu        sync.Mutex
+	interval  time.Duration
+	threshold float64
+	stats     map[ingestKey]*ingestStats
+
+	closing  chan struct{}
+	wg       sync.WaitGroup
+	anomalyN int64
+
+	Logger *zap.Logger
+}
+
+// NewIngestMonitor returns an IngestMonitor comparing the points written
+// during each interval with their baseline, reporting deviations larger than
+// threshold times the baseline.
+func NewIngestMonitor(interval time.Duration, threshold float64) *IngestMonitor {
+	return &IngestMonitor{
+		interval:  interval,
+		threshold: threshold,
+		stats:     make(map[ingestKey]*ingestStats),
+		Logger:    zap.NewNop(),
+	}
+}
+
+// Open starts checking the points written at each interval.
+func (m *IngestMonitor) Open() {
+	m.closing = make(chan struct{})
+	m.wg.Add(1)
+	go func() {
+		defer m.wg.Done()
+		m.run()
+	}()
+}
+
+// Close stops checking the points written.
+func (m *IngestMonitor) Close() {
+	if m.closing == nil {
+		return
+	}
+	close(m.closing)
+	m.wg.Wait()
+	m.closing = nil
+}
+
+// AnomalyN returns the number of anomalies detected.
+func (m *IngestMonitor) AnomalyN() int64 {
+	if m == nil {
+		return 0
+	}
+	return atomic.LoadInt64(&m.anomalyN)
+}
+
+// Observe adds the points written to database to the statistics of the
+// current interval. The statistics of the points are gathered before they
+// are merged under the lock, so concurrent writes are only serialized to
+// merge them. Numeric fields are read with a field iterator rather than by
+// parsing all the fields of each point again.
+func (m *IngestMonitor) Observe(database string, points []models.Point) {
+	if m == nil || len(points) == 0 {
+		return
+	}
+
+	batch := make(map[string]*ingestStats)
+	for _, p := range points {
+		b := batch[string(p.Name())]
+		if b == nil {
+			b = newIngestStats()
+			batch[string(p.Name())] = b
+		}
+		b.pointN++
+
+		iter := p.FieldIterator()
+		for iter.Next() {
+			var f float64
+			switch iter.Type() {
+			case models.Float:
+				v, err := iter.FloatValue()
+				if err != nil {
+					continue
+				}
+				f = v
+			case models.Integer:
+				v, err := iter.IntegerValue()
+				if err != nil {
+					continue
+				}
+				f = float64(v)
+			case models.Unsigned:
+				v, err := iter.UnsignedValue()
+				if err != nil {
+					continue
+				}
+				f = float64(v)
+			default:
+				continue
+			}
+			k := string(iter.FieldKey())
+			b.sums[k] += f
+			b.counts[k]++
+		}
+	}
+
+	m.mu.Lock()
+	defer m.mu.Unlock()
+
+	for name, b := range batch {
+		key := ingestKey{database: database, measurement: name}
+		s := m.stats[key]
+		if s == nil {
+			if len(m.stats) >= ingestMaxMeasurements {
+				continue
+			}
+			s = newIngestStats()
+			m.stats[key] = s
+		}
+		s.pointN += b.pointN
+		for k, sum := range b.sums {
+			s.sums[k] += sum
+			s.counts[k] += b.counts[k]
+		}
+	}
+}
+
+// run checks the points written at each interval until the monitor is closed.
+func (m *IngestMonitor) run() {
+	ticker := time.NewTicker(m.interval)
+	defer ticker.Stop()
+
+	for {
+		select {
+		case <-m.closing:
+			return
+		case <-ticker.C:
+			for _, a := range m.check() {
+				m.Logger.Warn("Ingest anomaly detected",
+					zap.String("db", a.Database),
+					zap.String("measurement", a.Measurement),
+					zap.String("field", a.Field),
+					zap.Float64("value", a.Value),
+					zap.Float64("baseline", a.Baseline))
+			}
+		}
+	}
+}
+
+// check compares the statistics of the current interval with the baselines,
+// folds them into the baselines and starts a new interval.
+func (m *IngestMonitor) check() []IngestAnomaly {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+
+	var anomalies []IngestAnomaly
+	for key, s := range m.stats {
+		if s.intervals >= ingestWarmupIntervals {
+			if m.deviates(s.pointN, s.rate) {
+				anomalies = append(anomalies, IngestAnomaly{
+					Database:    key.database,
+					Measurement: key.measurement,
+					Value:       s.pointN,
+					Baseline:    s.rate,
+				})
+			}
+			for k, n := range s.counts {
+				if base, ok := s.means[k]; ok && m.deviates(s.sums[k]/n, base) {
+					anomalies = append(anomalies, IngestAnomaly{
+						Database:    key.database,
+						Measurement: key.measurement,
+						Field:       k,
+						Value:       s.sums[k] / n,
+						Baseline:    base,
+					})
+				}
+			}
+		}
+
+		if s.intervals == 0 {
+			s.rate = s.pointN
+		} else {
+			s.rate += ingestBaselineWeight * (s.pointN - s.rate)
+		}
+		for k, n := range s.counts {
+			if base, ok := s.means[k]; ok {
+				s.means[k] = base + ingestBaselineWeight*(s.sums[k]/n-base)
+			} else {
+				s.means[k] = s.sums[k] / n
+			}
+		}
+		s.intervals++
+
+		// Forget measurements that are no longer written to.
+		if s.pointN == 0 {
+			s.idle++
+		} else {
+			s.idle = 0
+		}
+		if s.idle >= ingestIdleIntervals || (s.pointN == 0 && s.rate < 1) {
+			delete(m.stats, key)
+			continue
+		}
+		s.pointN = 0
+		s.sums = make(map[string]float64)
+		s.counts = make(map[string]float64)
+	}
+
+	atomic.AddInt64(&m.anomalyN, int64(len(anomalies)))
+	return anomalies
+}
+
+// deviates returns true if the magnitude of v differs from the magnitude of
+// base by more than the threshold factor.
+func (m *IngestMonitor) deviates(v, base float64) bool {
+	v, base = math.Abs(v), math.Abs(base)
+	if base == 0 {
+		return false
+	}
+	return v > base*m.threshold || v < base/m.threshold
+}
@@ -0,0 +1,100 @@
+package coordinator
+
+import (
+	"fmt"
+	"sync"
+	"testing"
+	"time"
+
+	"github.com/influxdata/influxdb/models"
+)
+
+func TestIngestMonitor_Check(t *testing.T) {
+	m := NewIngestMonitor(time.Minute, 10)
+
+	write := func(n int, value float64) {
+		points := make([]models.Point, n)
+		for i := range points {
+			points[i] = models.MustNewPoint("cpu", models.NewTags(map[string]string{"host": "server01"}), models.Fields{"usage": value, "state": "ok"}, time.Unix(int64(i), 0))
+		}
+		m.Observe("db0", points)
+	}
+
+	// Establish the baseline.
+	for i := 0; i < ingestWarmupIntervals; i++ {
+		write(100, 0.5)
+		if a := m.check(); len(a) != 0 {
+			t.Fatalf("unexpected anomalies during warmup: %+v", a)
+		}
+	}
+
+	write(90, 0.6)
+	if a := m.check(); len(a) != 0 {
+		t.Fatalf("unexpected anomalies: %+v", a)
+	}
+
+	// The unit of the field changed.
+	write(100, 50)
+	if a := m.check(); len(a) != 1 {
+		t.Fatalf("unexpected anomalies: %+v", a)
+	} else if a[0].Database != "db0" || a[0].Measurement != "cpu" || a[0].Field != "usage" || a[0].Value != 50 {
+		t.Fatalf("unexpected anomaly: %+v", a[0])
+	}
+
+	// The agent stopped writing.
+	if a := m.check(); len(a) != 1 {
+		t.Fatalf("unexpected anomalies: %+v", a)
+	} else if a[0].Field != "" || a[0].Value != 0 {
+		t.Fatalf("unexpected anomaly: %+v", a[0])
+	}
+
+	if n := m.AnomalyN(); n != 2 {
+		t.Fatalf("unexpected anomaly count: %d", n)
+	}
+}
+
+func TestIngestMonitor_Observe_Concurrent(t *testing.T) {
+	m := NewIngestMonitor(time.Minute, 10)
+
+	var wg sync.WaitGroup
+	for i := 0; i < 8; i++ {
+		points := make([]models.Point, 100)
+		for j := range points {
+			points[j] = models.MustNewPoint("cpu", nil, models.Fields{"usage": 0.5}, time.Unix(int64(j), 0))
+		}
+
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			m.Observe("db0", points)
+		}()
+	}
+	wg.Wait()
+
+	s := m.stats[ingestKey{database: "db0", measurement: "cpu"}]
+	if s == nil || s.pointN != 800 || s.counts["usage"] != 800 || s.sums["usage"] != 400 {
+		t.Fatalf("unexpected statistics: %+v", s)
+	}
+}
+
+func TestIngestMonitor_Limits(t *testing.T) {
+	m := NewIngestMonitor(time.Minute, 10)
+
+	// Measurements beyond the maximum are not monitored.
+	points := make([]models.Point, ingestMaxMeasurements+1)
+	for i := range points {
+		points[i] = models.MustNewPoint(fmt.Sprintf("m%d", i), nil, models.Fields{"value": 1.0}, time.Unix(0, 0))
+	}
+	m.Observe("db0", points)
+	if n := len(m.stats); n != ingestMaxMeasurements {
+		t.Fatalf("unexpected number of measurements: %d", n)
+	}
+
+	// Measurements no longer written to are forgotten.
+	for i := 0; i < ingestIdleIntervals; i++ {
+		m.check()
+	}
+	if n := len(m.stats); n != 0 {
+		t.Fatalf("unexpected number of measurements: %d", n)
+	}
+}
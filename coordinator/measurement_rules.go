@@ -0,0 +1,132 @@
+package coordinator
+
+import (
+	"errors"
+	"fmt"
+	"regexp"
+	"strings"
+
+	"github.com/influxdata/influxdb/models"
+)
+
+// MeasurementRule is the configuration of a rule renaming the measurement of
+// the points written, so legacy naming schemes can be reorganized without
+// changing the clients writing them.
+type MeasurementRule struct {
+	// Database limits the rule to writes to a database.
+	// An empty value applies it to all databases.
+	Database string `toml:"database"`
+
+	// Measurement is the name of the measurements the rule applies to, or a
+	// regular expression enclosed in slashes such as "/^(?P<host>[^.]+)\.cpu$/".
+	Measurement string `toml:"measurement"`
+
+	// Rename is the new name of the measurement. With a regular expression
+	// it may refer to its capture groups, such as "$1" or "${host}".
+	Rename string `toml:"rename"`
+
+	// ExtractTags adds the named capture groups of the regular expression to
+	// the point as tags, unless the point already has them.
+	ExtractTags bool `toml:"extract-tags"`
+}
+
+// Validate returns an error if the measurement rule is invalid.
+func (r MeasurementRule) Validate() error {
+	if r.Measurement == "" {
+		return errors.New("measurement rule measurement must be specified")
+	} else if r.Rename == "" {
+		return fmt.Errorf("measurement rule %q: rename must be specified", r.Measurement)
+	}
+
+	re, err := r.regexp()
+	if err != nil {
+		return fmt.Errorf("measurement rule %q: %s", r.Measurement, err)
+	} else if re == nil && r.ExtractTags {
+		return fmt.Errorf("measurement rule %q: extract-tags requires a regular expression", r.Measurement)
+	}
+	return nil
+}
+
+// regexp returns the regular expression of the rule, or nil if the rule
+// matches a measurement name exactly.
+func (r MeasurementRule) regexp() (*regexp.Regexp, error) {
+	if len(r.Measurement) < 2 || !strings.HasPrefix(r.Measurement, "/") || !strings.HasSuffix(r.Measurement, "/") {
+		return nil, nil
+	}
+	return regexp.Compile(r.Measurement[1 : len(r.Measurement)-1])
+}
+
+// measurementRuleExpr is a parsed measurement rule.
+type measurementRuleExpr struct {
+	MeasurementRule
+	re *regexp.Regexp
+}
+
+// MeasurementRewriter renames the measurements of points before they are
+// written. The first rule matching a point applies.
+type MeasurementRewriter struct {
+	exact map[string][]measurementRuleExpr // keyed by measurement
+	regex []measurementRuleExpr
+}
+
+// NewMeasurementRewriter returns a MeasurementRewriter applying the given rules.
+func NewMeasurementRewriter(rules []MeasurementRule) (*MeasurementRewriter, error) {
+	m := &MeasurementRewriter{exact: make(map[string][]measurementRuleExpr)}
+	for _, r := range rules {
+		if err := r.Validate(); err != nil {
+			return nil, err
+		}
+		re, _ := r.regexp()
+		if re == nil {
+			m.exact[r.Measurement] = append(m.exact[r.Measurement], measurementRuleExpr{MeasurementRule: r})
+		} else {
+			m.regex = append(m.regex, measurementRuleExpr{MeasurementRule: r, re: re})
+		}
+	}
+	return m, nil
+}
+
+// Rewrite returns points with the rules of database applied. Rules matching
+// a measurement exactly take precedence over regular expressions.
+func (m *MeasurementRewriter) Rewrite(database string, points []models.Point) ([]models.Point, error) {
+	if m == nil {
+		return points, nil
+	}
+	return transformPoints(points, func(t *pointTransform) error { m.rewrite(database, t); return nil }, nil)
+}
+
+// rewrite applies the first rule of database matching the point of t.
+func (m *MeasurementRewriter) rewrite(database string, t *pointTransform) {
+	if m == nil {
+		return
+	}
+
+	name := t.Name()
+	for _, r := range m.exact[name] {
+		if r.Database == "" || r.Database == database {
+			t.SetName(r.Rename)
+			return
+		}
+	}
+
+	for _, r := range m.regex {
+		if r.Database != "" && r.Database != database {
+			continue
+		}
+		match := r.re.FindStringSubmatchIndex(name)
+		if match == nil {
+			continue
+		}
+
+		t.SetName(string(r.re.ExpandString(nil, r.Rename, name, match)))
+		if r.ExtractTags {
+			for i, group := range r.re.SubexpNames() {
+				if group == "" || match[2*i] < 0 || match[2*i] == match[2*i+1] || t.Tags().Get([]byte(group)) != nil {
+					continue
+				}
+				t.SetTag(group, name[match[2*i]:match[2*i+1]])
+			}
+		}
+		return
+	}
+}
@@ -0,0 +1,63 @@
+package coordinator_test
+
+import (
+	"testing"
+	"time"
+
+	"github.com/influxdata/influxdb/coordinator"
+	"github.com/influxdata/influxdb/models"
+)
+
+func TestMeasurementRewriter_Rewrite(t *testing.T) {
+	rw, err := coordinator.NewMeasurementRewriter([]coordinator.MeasurementRule{
+		{Measurement: `/^(?P<host>[^.]+)\.(?P<metric>[a-z]+)$/`, Rename: "${metric}", ExtractTags: true},
+		{Measurement: "server01.mem", Rename: "memory"},
+		{Database: "db0", Measurement: "legacy_cpu", Rename: "cpu"},
+	})
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	points := []models.Point{
+		models.MustNewPoint("server01.cpu", nil, models.Fields{"value": 1.0}, time.Unix(0, 0)),
+		models.MustNewPoint("server02.disk", models.NewTags(map[string]string{"host": "h0"}), models.Fields{"value": 2.0}, time.Unix(0, 0)),
+		models.MustNewPoint("server01.mem", nil, models.Fields{"value": 3.0}, time.Unix(0, 0)),
+		models.MustNewPoint("legacy_cpu", nil, models.Fields{"value": 4.0}, time.Unix(0, 0)),
+	}
+
+	for _, tt := range []struct {
+		database string
+		exp      []string
+	}{
+		{"db0", []string{"cpu,host=server01,metric=cpu value=1 0", "disk,host=h0,metric=disk value=2 0", "memory value=3 0", "cpu value=4 0"}},
+		{"db1", []string{"cpu,host=server01,metric=cpu value=1 0", "disk,host=h0,metric=disk value=2 0", "memory value=3 0", "legacy_cpu value=4 0"}},
+	} {
+		got, err := rw.Rewrite(tt.database, points)
+		if err != nil {
+			t.Fatal(err)
+		}
+		for i, p := range got {
+			if p.String() != tt.exp[i] {
+				t.Fatalf("%s: unexpected point %d: exp %q, got %q", tt.database, i, tt.exp[i], p.String())
+			}
+		}
+	}
+
+	// The points passed in are left untouched.
+	if s := points[0].String(); s != "server01.cpu value=1 0" {
+		t.Fatalf("unexpected point: %q", s)
+	}
+}
+
+func TestMeasurementRule_Validate(t *testing.T) {
+	for _, r := range []coordinator.MeasurementRule{
+		{Rename: "cpu"},
+		{Measurement: "legacy_cpu"},
+		{Measurement: "/(/", Rename: "cpu"},
+		{Measurement: "legacy_cpu", Rename: "cpu", ExtractTags: true},
+	} {
+		if err := r.Validate(); err == nil {
+			t.Fatalf("expected error for %+v", r)
+		}
+	}
+}
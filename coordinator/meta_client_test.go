@@ -27,6 +27,7 @@ type MetaClient struct {
 	DropSubscriptionFn                  func(database, rp, name string) error
 	DropShardFn                         func(id uint64) error
 	DropUserFn                          func(name string) error
+	MarkDatabaseDeletedFn               func(name string) error
 	MetaNodesFn                         func() ([]meta.NodeInfo, error)
 	RetentionPolicyFn                   func(database, name string) (rpi *meta.RetentionPolicyInfo, err error)
 	SetAdminPrivilegeFn                 func(username string, admin bool) error
@@ -68,6 +69,10 @@ func (c *MetaClient) CreateUser(name, password string, admin bool) (meta.User, e
 	return c.CreateUserFn(name, password, admin)
 }
 
+func (c *MetaClient) MarkDatabaseDeleted(name string) error {
+	return c.MarkDatabaseDeletedFn(name)
+}
+
 func (c *MetaClient) Database(name string) *meta.DatabaseInfo {
 	return c.DatabaseFn(name)
 }
@@ -0,0 +1,219 @@
+package coordinator
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/influxdata/influxdb/models"
+	"github.com/influxdata/influxdb/tsdb"
+)
+
+// maxReportedReasons is the number of distinct reasons for dropping points
+// described in the error returned for a write.
+const maxReportedReasons = 3
+
+// pointTransform is a point being transformed before it is written. Its
+// name, tags and fields are copied on the first change, so the point itself
+// is never modified, and its fields are parsed at most once.
+type pointTransform struct {
+	point  models.Point
+	name   string
+	tags   models.Tags
+	fields models.Fields
+
+	tagsCopied   bool
+	fieldsCopied bool
+	edited       bool
+
+	// err is the reason the point is dropped, if any.
+	err error
+}
+
+// reset makes t transform p.
+func (t *pointTransform) reset(p models.Point) {
+	*t = pointTransform{point: p, name: string(p.Name()), tags: p.Tags()}
+}
+
+// Name returns the measurement name of the point.
+func (t *pointTransform) Name() string { return t.name }
+
+// Tags returns the tags of the point. They must not be modified.
+func (t *pointTransform) Tags() models.Tags { return t.tags }
+
+// Fields returns the fields of the point. They must not be modified.
+func (t *pointTransform) Fields() (models.Fields, error) {
+	if t.fields == nil {
+		fields, err := t.point.Fields()
+		if err != nil {
+			return nil, fmt.Errorf("invalid fields on measurement %q: %s", t.name, err)
+		}
+		t.fields = fields
+	}
+	return t.fields, nil
+}
+
+// SetName renames the measurement of the point.
+func (t *pointTransform) SetName(name string) {
+	if name != t.name {
+		t.name, t.edited = name, true
+	}
+}
+
+// SetTags replaces the tags of the point.
+func (t *pointTransform) SetTags(tags models.Tags) {
+	t.tags, t.tagsCopied, t.edited = tags, true, true
+}
+
+// SetTag sets the value of a tag of the point.
+func (t *pointTransform) SetTag(key, value string) {
+	if !t.tagsCopied {
+		t.tags, t.tagsCopied = t.tags.Clone(), true
+	}
+	t.tags.SetString(key, value)
+	t.edited = true
+}
+
+// DeleteTag removes a tag of the point.
+func (t *pointTransform) DeleteTag(key string) {
+	if !t.tagsCopied {
+		t.tags, t.tagsCopied = t.tags.Clone(), true
+	}
+	t.tags.Delete([]byte(key))
+	t.edited = true
+}
+
+// SetFields replaces the fields of the point.
+func (t *pointTransform) SetFields(fields models.Fields) {
+	t.fields, t.fieldsCopied, t.edited = fields, true, true
+}
+
+// SetField sets the value of a field of the point. The fields must have
+// been parsed by Fields.
+func (t *pointTransform) SetField(key string, value interface{}) {
+	t.copyFields()
+	t.fields[key] = value
+}
+
+// DeleteField removes a field of the point. The fields must have been
+// parsed by Fields.
+func (t *pointTransform) DeleteField(key string) {
+	t.copyFields()
+	delete(t.fields, key)
+}
+
+// copyFields copies the fields of the point before their first change.
+func (t *pointTransform) copyFields() {
+	if !t.fieldsCopied {
+		t.fields, t.fieldsCopied = cloneFields(t.fields), true
+	}
+	t.edited = true
+}
+
+// build returns the transformed point, or the point itself if it is unchanged.
+func (t *pointTransform) build() (models.Point, error) {
+	if !t.edited {
+		return t.point, nil
+	}
+	fields, err := t.Fields()
+	if err != nil {
+		return nil, err
+	}
+	pt, err := models.NewPoint(t.name, t.tags, fields, t.point.Time())
+	if err != nil {
+		return nil, fmt.Errorf("invalid point on measurement %q: %s", t.name, err)
+	}
+	return pt, nil
+}
+
+// transformPoints applies fn to each of points in a single pass, then
+// finish, if not nil, to the points not dropped by fn. A point is dropped if
+// fn returns an error, if finish sets its err or if it cannot be rebuilt
+// once transformed, and a partial write error counting the dropped points
+// is returned with the others.
+//
+// The points slice is never modified: it is copied before any point in it is
+// replaced or removed, and returned as is if none are.
+func transformPoints(points []models.Point, fn func(t *pointTransform) error, finish func()) ([]models.Point, error) {
+	ts := make([]pointTransform, len(points))
+	for i, p := range points {
+		t := &ts[i]
+		t.reset(p)
+		t.err = fn(t)
+	}
+	if finish != nil {
+		finish()
+	}
+
+	var kept []models.Point
+	var dropped, unreported int
+	var reasons []string
+	for i := range ts {
+		t := &ts[i]
+		var pt models.Point
+		if t.err == nil {
+			pt, t.err = t.build()
+		}
+		if t.err != nil {
+			dropped++
+			switch reason := t.err.Error(); {
+			case containsString(reasons, reason):
+			case len(reasons) < maxReportedReasons:
+				reasons = append(reasons, reason)
+			default:
+				unreported++
+			}
+		}
+
+		if pt == points[i] && kept == nil {
+			continue
+		} else if kept == nil {
+			kept = append(make([]models.Point, 0, len(points)), points[:i]...)
+		}
+		if pt != nil {
+			kept = append(kept, pt)
+		}
+	}
+	if kept == nil {
+		return points, nil
+	} else if dropped == 0 {
+		return kept, nil
+	}
+
+	if unreported > 0 {
+		reasons = append(reasons, fmt.Sprintf("and %d more", unreported))
+	}
+	return kept, tsdb.PartialWriteError{Reason: strings.Join(reasons, "; "), Dropped: dropped}
+}
+
+// containsString returns true if a contains s.
+func containsString(a []string, s string) bool {
+	for _, other := range a {
+		if other == s {
+			return true
+		}
+	}
+	return false
+}
+
+// mergePartialWriteErrors returns a partial write error counting the points
+// dropped by both errors. Other errors are returned as is.
+func mergePartialWriteErrors(a, b error) error {
+	if a == nil {
+		return b
+	} else if b == nil {
+		return a
+	}
+
+	pa, ok := a.(tsdb.PartialWriteError)
+	if !ok {
+		return a
+	}
+	pb, ok := b.(tsdb.PartialWriteError)
+	if !ok {
+		return b
+	}
+	return tsdb.PartialWriteError{
+		Reason:  pa.Reason + "; " + pb.Reason,
+		Dropped: pa.Dropped + pb.Dropped,
+	}
+}
@@ -17,14 +17,23 @@ import (
 
 // The keys for statistics generated by the "write" module.
 const (
-	statWriteReq           = "req"
-	statPointWriteReq      = "pointReq"
-	statPointWriteReqLocal = "pointReqLocal"
-	statWriteOK            = "writeOk"
-	statWriteDrop          = "writeDrop"
-	statWriteTimeout       = "writeTimeout"
-	statWriteErr           = "writeError"
-	statSubWriteOK         = "subWriteOk"
+	statWriteReq            = "req"
+	statPointWriteReq       = "pointReq"
+	statPointWriteReqLocal  = "pointReqLocal"
+	statWriteOK             = "writeOk"
+	statWriteDrop           = "writeDrop"
+	statWriteTimeout        = "writeTimeout"
+	statWriteErr            = "writeError"
+	statSubWriteOK          = "subWriteOk"
+	statIngestAnomaly       = "ingestAnomaly"
+	statPointRejected       = "pointRejected"
+	statPointWarned         = "pointWarned"
+	statPointOutOfBounds    = "pointOutOfBounds"
+	statPointInvalidString  = "pointInvalidString"
+	statPointReplacedString = "pointReplacedString"
+	statPointTypeCoerced    = "pointTypeCoerced"
+	statPointTypeRejected   = "pointTypeRejected"
+	statWriteFrozen         = "writeFrozen"
 )
 
 var (
@@ -39,6 +48,20 @@ var (
 	ErrWriteFailed = errors.New("write failed")
 )
 
+// DatabaseDeletedError is returned when a write targets a database that is
+// soft-deleted and pending removal.
+type DatabaseDeletedError struct {
+	Database string
+}
+
+// Error returns a string representation of the error.
+func (e DatabaseDeletedError) Error() string {
+	return fmt.Sprintf("database is deleted: %q", e.Database)
+}
+
+// DatabaseDeleted returns true to indicate the write targeted a deleted database.
+func (e DatabaseDeletedError) DatabaseDeleted() bool { return true }
+
 // PointsWriter handles writes across multiple local and remote data nodes.
 type PointsWriter struct {
 	mu           sync.RWMutex
@@ -63,6 +86,42 @@ type PointsWriter struct {
 		Send(*WritePointsRequest)
 	}
 
+	// WriteFreezes rejects writes to frozen databases and measurements, if set.
+	WriteFreezes *WriteFreezes
+
+	// StringSanitizer checks and normalizes the strings of points before they are written.
+	StringSanitizer *StringSanitizer
+
+	// MeasurementRewriter renames the measurements of points before they are written.
+	MeasurementRewriter *MeasurementRewriter
+
+	// ColumnConverter converts tags to fields and fields to tags before points are written.
+	ColumnConverter *ColumnConverter
+
+	// TagInjector adds default tags to points before they are written.
+	TagInjector *TagInjector
+
+	// FieldDeriver adds derived fields to points before they are written.
+	FieldDeriver *FieldDeriver
+
+	// FieldTypeEnforcer coerces or rejects values of pinned fields of other types, if set.
+	FieldTypeEnforcer *FieldTypeEnforcer
+
+	// WriteTimeBounds drops points too far from the current time, if set.
+	WriteTimeBounds *WriteTimeBounds
+
+	// PointValidator checks points against validation rules before they are written.
+	PointValidator *PointValidator
+
+	// ResultCache is invalidated by the points written, if set.
+	ResultCache *ResultCache
+
+	// Alerter evaluates alert rules against the points written, if set.
+	Alerter *Alerter
+
+	// IngestMonitor compares the points written with their baseline, if set.
+	IngestMonitor *IngestMonitor
+
 	subPoints chan<- *WritePointsRequest
 
 	stats *WriteStatistics
@@ -124,6 +183,12 @@ func (s *ShardMapping) MapPoint(shardInfo *meta.ShardInfo, p models.Point) {
 // Open opens the communication channel with the point writer.
 func (w *PointsWriter) Open() error {
 	w.closing = make(chan struct{})
+	if w.Alerter != nil {
+		w.Alerter.Open()
+	}
+	if w.IngestMonitor != nil {
+		w.IngestMonitor.Open()
+	}
 	return nil
 }
 
@@ -132,12 +197,27 @@ func (w *PointsWriter) Close() error {
 	if w.closing != nil {
 		close(w.closing)
 	}
+	if w.Alerter != nil {
+		w.Alerter.Close()
+	}
+	if w.IngestMonitor != nil {
+		w.IngestMonitor.Close()
+	}
 	return nil
 }
 
 // WithLogger sets the Logger on w.
 func (w *PointsWriter) WithLogger(log *zap.Logger) {
 	w.Logger = log.With(zap.String("service", "write"))
+	if w.Alerter != nil {
+		w.Alerter.Logger = w.Logger
+	}
+	if w.PointValidator != nil {
+		w.PointValidator.Logger = w.Logger
+	}
+	if w.IngestMonitor != nil {
+		w.IngestMonitor.Logger = w.Logger
+	}
 }
 
 // WriteStatistics keeps statistics related to the PointsWriter.
@@ -158,14 +238,23 @@ func (w *PointsWriter) Statistics(tags map[string]string) []models.Statistic {
 		Name: "write",
 		Tags: tags,
 		Values: map[string]interface{}{
-			statWriteReq:           atomic.LoadInt64(&w.stats.WriteReq),
-			statPointWriteReq:      atomic.LoadInt64(&w.stats.PointWriteReq),
-			statPointWriteReqLocal: atomic.LoadInt64(&w.stats.PointWriteReqLocal),
-			statWriteOK:            atomic.LoadInt64(&w.stats.WriteOK),
-			statWriteDrop:          atomic.LoadInt64(&w.stats.WriteDropped),
-			statWriteTimeout:       atomic.LoadInt64(&w.stats.WriteTimeout),
-			statWriteErr:           atomic.LoadInt64(&w.stats.WriteErr),
-			statSubWriteOK:         atomic.LoadInt64(&w.stats.SubWriteOK),
+			statWriteReq:            atomic.LoadInt64(&w.stats.WriteReq),
+			statPointWriteReq:       atomic.LoadInt64(&w.stats.PointWriteReq),
+			statPointWriteReqLocal:  atomic.LoadInt64(&w.stats.PointWriteReqLocal),
+			statWriteOK:             atomic.LoadInt64(&w.stats.WriteOK),
+			statWriteDrop:           atomic.LoadInt64(&w.stats.WriteDropped),
+			statWriteTimeout:        atomic.LoadInt64(&w.stats.WriteTimeout),
+			statWriteErr:            atomic.LoadInt64(&w.stats.WriteErr),
+			statSubWriteOK:          atomic.LoadInt64(&w.stats.SubWriteOK),
+			statIngestAnomaly:       w.IngestMonitor.AnomalyN(),
+			statPointRejected:       w.PointValidator.RejectedN(),
+			statPointWarned:         w.PointValidator.WarnedN(),
+			statPointOutOfBounds:    w.WriteTimeBounds.DroppedN(),
+			statPointInvalidString:  w.StringSanitizer.RejectedN(),
+			statPointReplacedString: w.StringSanitizer.ReplacedN(),
+			statPointTypeCoerced:    w.FieldTypeEnforcer.CoercedN(),
+			statPointTypeRejected:   w.FieldTypeEnforcer.RejectedN(),
+			statWriteFrozen:         w.WriteFreezes.RejectedN(),
 		},
 	}}
 }
@@ -341,28 +430,62 @@ func (w *PointsWriter) WritePointsPrivileged(writeCtx tsdb.WriteContext, databas
 	atomic.AddInt64(&w.stats.WriteReq, 1)
 	atomic.AddInt64(&w.stats.PointWriteReq, int64(len(points)))
 
+	// Writes to a soft-deleted database are rejected whichever service they
+	// come from, as they would still land in its existing shard groups.
+	db := w.MetaClient.Database(database)
+	if db != nil && db.Deleted() {
+		return DatabaseDeletedError{Database: database}
+	}
+
 	if retentionPolicy == "" {
-		db := w.MetaClient.Database(database)
 		if db == nil {
 			return influxdb.ErrDatabaseNotFound(database)
 		}
 		retentionPolicy = db.DefaultRetentionPolicy
 	}
 
+	if err := w.WriteFreezes.Check(database, points); err != nil {
+		return err
+	}
+
+	// Points with invalid strings or field types, out of the write time bounds
+	// or failing validation are dropped and reported once the others have been written.
+	points, droppedErr := w.transformPoints(database, points)
+	if droppedErr != nil && len(points) == 0 {
+		return droppedErr
+	}
+
+	// Invalidate cached query results once the points are readable.
+	if w.ResultCache != nil && len(points) > 0 {
+		min, max := points[0].UnixNano(), points[0].UnixNano()
+		for _, p := range points[1:] {
+			if t := p.UnixNano(); t < min {
+				min = t
+			} else if t > max {
+				max = t
+			}
+		}
+		defer w.ResultCache.Invalidate(database, min, max)
+	}
+
 	shardMappings, err := w.MapShards(&WritePointsRequest{Database: database, RetentionPolicy: retentionPolicy, Points: points})
 	if err != nil {
 		return err
 	}
 
 	// Write each shard in it's own goroutine and return as soon as one fails.
-	ch := make(chan error, len(shardMappings.Points))
+	type shardWrite struct {
+		points []models.Point
+		err    error
+	}
+	ch := make(chan shardWrite, len(shardMappings.Points))
 	for shardID, points := range shardMappings.Points {
 		go func(writeCtx tsdb.WriteContext, shard *meta.ShardInfo, database, retentionPolicy string, points []models.Point) {
 			err := w.writeToShard(writeCtx, shard, database, retentionPolicy, points)
 			if err == tsdb.ErrShardDeletion {
-				err = tsdb.PartialWriteError{Reason: fmt.Sprintf("shard %d is pending deletion", shard.ID), Dropped: len(points)}
+				err = tsdb.PartialWriteError{Reason: fmt.Sprintf("shard %d is pending deletion", shard.ID), Dropped: len(points), DroppedPoints: points}
 			}
-			ch <- err
+			ch <- shardWrite{points: points, err: err}
 		}(writeCtx, shardMappings.Shards[shardID], database, retentionPolicy, points)
 	}
 
@@ -374,8 +497,11 @@ func (w *PointsWriter) WritePointsPrivileged(writeCtx tsdb.WriteContext, databas
 	w.Subscriber.Send(pts)
 	atomic.AddInt64(&w.stats.SubWriteOK, 1)
 
-	if err == nil && len(shardMappings.Dropped) > 0 {
-		err = tsdb.PartialWriteError{Reason: "points beyond retention policy", Dropped: len(shardMappings.Dropped)}
+	// Points not written are excluded from alerts and ingest monitoring.
+	var unwritten []models.Point
+	if len(shardMappings.Dropped) > 0 {
+		unwritten = append(unwritten, shardMappings.Dropped...)
+		droppedErr = mergePartialWriteErrors(droppedErr, tsdb.PartialWriteError{Reason: "points beyond retention policy", Dropped: len(shardMappings.Dropped)})
 	}
 
 	for range shardMappings.Points {
@@ -386,13 +512,70 @@ func (w *PointsWriter) WritePointsPrivileged(writeCtx tsdb.WriteContext, databas
 			atomic.AddInt64(&w.stats.WriteTimeout, 1)
 			// return timeout error to caller
 			return ErrTimeout
-		case err := <-ch:
-			if err != nil {
-				return err
+		case sw := <-ch:
+			if err, ok := sw.err.(tsdb.PartialWriteError); ok {
+				droppedErr = mergePartialWriteErrors(droppedErr, err)
+				// All the points of the shard are excluded when it does not
+				// report which it dropped.
+				if len(err.DroppedPoints) == 0 && err.Dropped > 0 {
+					unwritten = append(unwritten, sw.points...)
+				} else {
+					unwritten = append(unwritten, err.DroppedPoints...)
+				}
+			} else if sw.err != nil {
+				return sw.err
+			}
+		}
+	}
+
+	written := points
+	if len(unwritten) > 0 {
+		skip := make(map[models.Point]struct{}, len(unwritten))
+		for _, p := range unwritten {
+			skip[p] = struct{}{}
+		}
+		written = make([]models.Point, 0, len(points)-len(unwritten))
+		for _, p := range points {
+			if _, ok := skip[p]; !ok {
+				written = append(written, p)
 			}
 		}
 	}
-	return err
+	if len(written) > 0 {
+		w.Alerter.Evaluate(database, retentionPolicy, written)
+		w.IngestMonitor.Observe(database, written)
+	}
+	return droppedErr
+}
+
+// transformPoints applies the write rules of database to points in a single
+// pass over them.
+func (w *PointsWriter) transformPoints(database string, points []models.Point) ([]models.Point, error) {
+	if w.StringSanitizer == nil && w.MeasurementRewriter == nil && w.ColumnConverter == nil && w.TagInjector == nil &&
+		w.FieldDeriver == nil && w.FieldTypeEnforcer == nil && w.WriteTimeBounds == nil && w.PointValidator == nil {
+		return points, nil
+	}
+
+	window := w.WriteTimeBounds.window(database)
+	validation := w.PointValidator.validation(database)
+	return transformPoints(points, func(t *pointTransform) error {
+		if err := w.StringSanitizer.sanitize(t); err != nil {
+			return err
+		}
+		w.MeasurementRewriter.rewrite(database, t)
+		if err := w.ColumnConverter.convert(database, t); err != nil {
+			return err
+		}
+		w.TagInjector.inject(database, t)
+		if err := w.FieldDeriver.derive(database, t); err != nil {
+			return err
+		} else if err := w.FieldTypeEnforcer.enforce(database, t); err != nil {
+			return err
+		} else if err := window.check(t); err != nil {
+			return err
+		}
+		return validation.check(t)
+	}, validation.finish)
 }
 
 // writeToShards writes points to a shard.
@@ -1,8 +1,12 @@
 package coordinator_test
 
 import (
+	"encoding/json"
 	"fmt"
+	"net/http"
+	"net/http/httptest"
 	"reflect"
+	"strings"
 	"sync"
 	"sync/atomic"
 	"testing"
@@ -419,12 +423,213 @@ func TestPointsWriter_WritePoints_Dropped(t *testing.T) {
 	c.Subscriber = sub
 	c.Node = &influxdb.Node{ID: 1}
 
+	// The FieldTypeEnforcer drops a point of another type.
+	var err error
+	if c.FieldTypeEnforcer, err = coordinator.NewFieldTypeEnforcer([]coordinator.FieldType{
+		{Measurement: "cpu", Field: "value", Type: "float", Action: coordinator.FieldTypeActionReject},
+	}); err != nil {
+		t.Fatal(err)
+	}
+	pr.Points = append(pr.Points, models.MustNewPoint("cpu", nil, models.Fields{"value": "bad"}, time.Now()))
+
 	c.Open()
 	defer c.Close()
 
-	err := c.WritePointsPrivileged(tsdb.WriteContext{}, pr.Database, pr.RetentionPolicy, models.ConsistencyLevelOne, pr.Points)
-	if _, ok := err.(tsdb.PartialWriteError); !ok {
+	err = c.WritePointsPrivileged(tsdb.WriteContext{}, pr.Database, pr.RetentionPolicy, models.ConsistencyLevelOne, pr.Points)
+	if pwe, ok := err.(tsdb.PartialWriteError); !ok {
 		t.Errorf("PointsWriter.WritePoints(): got %v, exp %v", err, tsdb.PartialWriteError{})
+	} else if pwe.Dropped != 2 {
+		t.Errorf("PointsWriter.WritePoints(): unexpected dropped count: %d", pwe.Dropped)
+	} else if !strings.Contains(pwe.Reason, "pinned to float") || !strings.Contains(pwe.Reason, "points beyond retention policy") {
+		t.Errorf("PointsWriter.WritePoints(): unexpected reason: %s", pwe.Reason)
+	}
+}
+
+func TestPointsWriter_WritePoints_DatabaseDeleted(t *testing.T) {
+	ms := NewPointsWriterMetaClient()
+	ms.DatabaseFn = func(database string) *meta.DatabaseInfo {
+		return &meta.DatabaseInfo{Name: database, DefaultRetentionPolicy: "myrp", DeletedAt: time.Now()}
+	}
+	ms.NodeIDFn = func() uint64 { return 1 }
+
+	c := coordinator.NewPointsWriter()
+	c.MetaClient = ms
+	c.TSDBStore = &fakeStore{
+		WriteFn: func(_ tsdb.WriteContext, shardID uint64, points []models.Point) error {
+			t.Fatal("unexpected write to a deleted database")
+			return nil
+		},
+	}
+	c.Subscriber = Subscriber{SendFn: func(*coordinator.WritePointsRequest) {}}
+	c.Node = &influxdb.Node{ID: 1}
+
+	c.Open()
+	defer c.Close()
+
+	points := []models.Point{models.MustNewPoint("cpu", nil, models.Fields{"value": 1.0}, time.Now())}
+	for _, rp := range []string{"", "myrp"} {
+		err := c.WritePointsPrivileged(tsdb.WriteContext{}, "mydb", rp, models.ConsistencyLevelOne, points)
+		if !influxdb.IsDatabaseDeletedError(err) {
+			t.Fatalf("unexpected error with retention policy %q: %v", rp, err)
+		}
+	}
+}
+
+func TestPointsWriter_WritePoints_Transformed(t *testing.T) {
+	ms := NewPointsWriterMetaClient()
+	ms.DatabaseFn = func(database string) *meta.DatabaseInfo {
+		return nil
+	}
+	ms.NodeIDFn = func() uint64 { return 1 }
+
+	var mu sync.Mutex
+	var written []models.Point
+	store := &fakeStore{
+		WriteFn: func(_ tsdb.WriteContext, shardID uint64, points []models.Point) error {
+			mu.Lock()
+			defer mu.Unlock()
+			written = append(written, points...)
+			return nil
+		},
+	}
+
+	c := coordinator.NewPointsWriter()
+	c.MetaClient = ms
+	c.TSDBStore = store
+	c.Subscriber = Subscriber{SendFn: func(*coordinator.WritePointsRequest) {}}
+	c.Node = &influxdb.Node{ID: 1}
+
+	var err error
+	if c.MeasurementRewriter, err = coordinator.NewMeasurementRewriter([]coordinator.MeasurementRule{
+		{Measurement: `/^(?P<host>[^.]+)\.cpu$/`, Rename: "cpu", ExtractTags: true},
+		{Measurement: `/^(?P<name>x+)$/`, Rename: "${name}", ExtractTags: true},
+	}); err != nil {
+		t.Fatal(err)
+	}
+	if c.FieldDeriver, err = coordinator.NewFieldDeriver([]coordinator.DerivedField{
+		{Measurement: "cpu", Field: "usage_busy", Expr: "100 - usage_idle"},
+	}); err != nil {
+		t.Fatal(err)
+	}
+
+	// Alerts are evaluated on the points written despite the partial write.
+	events := make(chan coordinator.AlertEvent, 10)
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		var ev coordinator.AlertEvent
+		if err := json.NewDecoder(r.Body).Decode(&ev); err != nil {
+			t.Errorf("unexpected error: %s", err)
+		}
+		events <- ev
+	}))
+	defer srv.Close()
+	if c.Alerter, err = coordinator.NewAlerter([]coordinator.AlertRule{
+		{Name: "busy", Measurement: "cpu", Condition: "usage_busy > 20"},
+	}, srv.URL); err != nil {
+		t.Fatal(err)
+	}
+
+	c.Open()
+	defer c.Close()
+
+	// The second point cannot be rebuilt once its name is extracted as a tag,
+	// as its key would exceed the maximum length.
+	now := time.Now()
+	points := []models.Point{
+		models.MustNewPoint("server01.cpu", nil, models.Fields{"usage_idle": 75.0}, now),
+		models.MustNewPoint(strings.Repeat("x", models.MaxKeyLength/2), nil, models.Fields{"value": 1.0}, now),
+	}
+	err = c.WritePointsPrivileged(tsdb.WriteContext{}, "mydb", "myrp", models.ConsistencyLevelOne, points)
+	if pwe, ok := err.(tsdb.PartialWriteError); !ok || pwe.Dropped != 1 {
+		t.Fatalf("unexpected error: %v", err)
+	}
+
+	exp := fmt.Sprintf("cpu,host=server01 usage_busy=25,usage_idle=75 %d", now.UnixNano())
+	if len(written) != 1 || written[0].String() != exp {
+		t.Fatalf("unexpected points written: %v", written)
+	}
+
+	select {
+	case ev := <-events:
+		if ev.Rule != "busy" || ev.Tags["host"] != "server01" {
+			t.Fatalf("unexpected event: %+v", ev)
+		}
+	case <-time.After(10 * time.Second):
+		t.Fatal("timeout waiting for alert event")
+	}
+}
+
+// Ensure alerts are evaluated on the points of a shard not dropped by a
+// partial write to it.
+func TestPointsWriter_WritePoints_ShardPartialWrite(t *testing.T) {
+	ms := NewPointsWriterMetaClient()
+	ms.DatabaseFn = func(database string) *meta.DatabaseInfo {
+		return nil
+	}
+	ms.NodeIDFn = func() uint64 { return 1 }
+
+	store := &fakeStore{
+		WriteFn: func(_ tsdb.WriteContext, shardID uint64, points []models.Point) error {
+			for _, p := range points {
+				if string(p.Tags().Get([]byte("host"))) == "b" {
+					return tsdb.PartialWriteError{Reason: "field type conflict", Dropped: 1, DroppedPoints: []models.Point{p}}
+				}
+			}
+			return nil
+		},
+	}
+
+	c := coordinator.NewPointsWriter()
+	c.MetaClient = ms
+	c.TSDBStore = store
+	c.Subscriber = Subscriber{SendFn: func(*coordinator.WritePointsRequest) {}}
+	c.Node = &influxdb.Node{ID: 1}
+
+	events := make(chan coordinator.AlertEvent, 10)
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		var ev coordinator.AlertEvent
+		if err := json.NewDecoder(r.Body).Decode(&ev); err != nil {
+			t.Errorf("unexpected error: %s", err)
+		}
+		events <- ev
+	}))
+	defer srv.Close()
+	var err error
+	if c.Alerter, err = coordinator.NewAlerter([]coordinator.AlertRule{
+		{Name: "busy", Measurement: "cpu", Condition: "value > 0"},
+	}, srv.URL); err != nil {
+		t.Fatal(err)
+	}
+
+	c.Open()
+	defer c.Close()
+
+	now := time.Now()
+	points := []models.Point{
+		models.MustNewPoint("cpu", models.NewTags(map[string]string{"host": "a"}), models.Fields{"value": 1.0}, now),
+		models.MustNewPoint("cpu", models.NewTags(map[string]string{"host": "b"}), models.Fields{"value": 1.0}, now),
+		models.MustNewPoint("cpu", models.NewTags(map[string]string{"host": "c"}), models.Fields{"value": 1.0}, now),
+	}
+	err = c.WritePointsPrivileged(tsdb.WriteContext{}, "mydb", "myrp", models.ConsistencyLevelOne, points)
+	if pwe, ok := err.(tsdb.PartialWriteError); !ok || pwe.Dropped != 1 {
+		t.Fatalf("unexpected error: %v", err)
+	}
+
+	hosts := make(map[string]bool)
+	for len(hosts) < 2 {
+		select {
+		case ev := <-events:
+			hosts[ev.Tags["host"]] = true
+		case <-time.After(10 * time.Second):
+			t.Fatalf("timeout waiting for alert events, got %v", hosts)
+		}
+	}
+	select {
+	case ev := <-events:
+		hosts[ev.Tags["host"]] = true
+	case <-time.After(100 * time.Millisecond):
+	}
+	if exp := map[string]bool{"a": true, "c": true}; !reflect.DeepEqual(hosts, exp) {
+		t.Fatalf("unexpected alerted hosts: %v", hosts)
 	}
 }
 
@@ -0,0 +1,279 @@
+package coordinator
+
+import (
+	"container/list"
+	"math"
+	"sync"
+	"time"
+
+	"github.com/influxdata/influxdb/models"
+	"github.com/influxdata/influxdb/query"
+	"github.com/influxdata/influxql"
+)
+
+const (
+	// maxCachedResultValues is the number of values above which the results
+	// of a statement are not cached.
+	maxCachedResultValues = 100000
+
+	// maxResultCacheInvalidations is the number of recent invalidations kept
+	// to reject results of statements that ran concurrently with a write.
+	maxResultCacheInvalidations = 1024
+)
+
+// ResultCache caches the results of SELECT statements. Entries are
+// invalidated when points are written to their database within their time
+// range and expire after a TTL, which bounds how long data removed by
+// retention enforcement may still be returned.
+type ResultCache struct {
+	mu      sync.Mutex
+	size    int
+	ttl     time.Duration
+	entries map[string]map[string]*resultCacheEntry // keyed by database, then statement
+	lru     *list.List
+
+	// seq is incremented by each invalidation. Recent invalidations are
+	// kept so results computed across an invalidation are not cached.
+	// truncated is the sequence number of the newest dropped invalidation.
+	seq           uint64
+	truncated     uint64
+	invalidations []resultCacheInvalidation
+
+	now func() time.Time
+}
+
+// resultCacheEntry is the cached results of a statement.
+type resultCacheEntry struct {
+	key      string
+	database string
+	min, max int64
+	results  []*query.Result
+	expires  time.Time
+	elem     *list.Element
+}
+
+// resultCacheInvalidation records a write to a database within a time range.
+// An empty database invalidates all databases.
+type resultCacheInvalidation struct {
+	seq      uint64
+	database string
+	min, max int64
+}
+
+// NewResultCache returns a cache holding the results of up to size statements
+// for ttl.
+func NewResultCache(size int, ttl time.Duration) *ResultCache {
+	return &ResultCache{
+		size:    size,
+		ttl:     ttl,
+		entries: make(map[string]map[string]*resultCacheEntry),
+		lru:     list.New(),
+		now:     time.Now,
+	}
+}
+
+// Seq returns the current invalidation sequence number. It must be read
+// before a statement is executed and passed to Put with its results.
+func (c *ResultCache) Seq() uint64 {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	return c.seq
+}
+
+// Get returns the cached results for key in database.
+func (c *ResultCache) Get(database, key string) ([]*query.Result, bool) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	e := c.entries[database][key]
+	if e == nil {
+		return nil, false
+	} else if !c.now().Before(e.expires) {
+		c.remove(e)
+		return nil, false
+	}
+	c.lru.MoveToFront(e.elem)
+
+	results := make([]*query.Result, len(e.results))
+	for i, r := range e.results {
+		results[i] = cloneResult(r)
+	}
+	return results, true
+}
+
+// Put caches the results of key in database covering the time range min to
+// max. The results are discarded if an overlapping write was made since seq.
+func (c *ResultCache) Put(database, key string, min, max int64, seq uint64, results []*query.Result) {
+	var n int
+	for _, r := range results {
+		for _, row := range r.Series {
+			n += len(row.Values) * len(row.Columns)
+		}
+	}
+	if n > maxCachedResultValues {
+		return
+	}
+
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	// Reject the results if they may not reflect a write made while the
+	// statement was running, or if the invalidations since are unknown.
+	if seq != c.seq {
+		if seq < c.truncated {
+			return
+		}
+		for _, inv := range c.invalidations {
+			if inv.seq > seq && inv.overlaps(database, min, max) {
+				return
+			}
+		}
+	}
+
+	if e := c.entries[database][key]; e != nil {
+		c.remove(e)
+	}
+
+	e := &resultCacheEntry{
+		key:      key,
+		database: database,
+		min:      min,
+		max:      max,
+		results:  make([]*query.Result, len(results)),
+		expires:  c.now().Add(c.ttl),
+	}
+	for i, r := range results {
+		e.results[i] = cloneResult(r)
+	}
+	e.elem = c.lru.PushFront(e)
+
+	m := c.entries[database]
+	if m == nil {
+		m = make(map[string]*resultCacheEntry)
+		c.entries[database] = m
+	}
+	m[key] = e
+
+	for c.lru.Len() > c.size {
+		c.remove(c.lru.Back().Value.(*resultCacheEntry))
+	}
+}
+
+// Invalidate removes the cached results of database overlapping the time
+// range min to max. An empty database invalidates the results of all
+// databases.
+func (c *ResultCache) Invalidate(database string, min, max int64) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	c.seq++
+
+	// Widen the last invalidation when it is for the same database so a
+	// steady stream of writes does not overflow the log.
+	if n := len(c.invalidations); n > 0 && c.invalidations[n-1].database == database {
+		inv := &c.invalidations[n-1]
+		inv.seq = c.seq
+		if min < inv.min {
+			inv.min = min
+		}
+		if max > inv.max {
+			inv.max = max
+		}
+	} else {
+		if n == maxResultCacheInvalidations {
+			c.truncated = c.invalidations[0].seq
+			c.invalidations = append(c.invalidations[:0], c.invalidations[1:]...)
+		}
+		c.invalidations = append(c.invalidations, resultCacheInvalidation{seq: c.seq, database: database, min: min, max: max})
+	}
+
+	inv := resultCacheInvalidation{database: database, min: min, max: max}
+	for db, m := range c.entries {
+		if database != "" && db != database {
+			continue
+		}
+		for _, e := range m {
+			if inv.overlaps(e.database, e.min, e.max) {
+				c.remove(e)
+			}
+		}
+	}
+}
+
+// InvalidateAll removes all cached results.
+func (c *ResultCache) InvalidateAll() {
+	c.Invalidate("", math.MinInt64, math.MaxInt64)
+}
+
+// remove removes e from the cache.
+func (c *ResultCache) remove(e *resultCacheEntry) {
+	c.lru.Remove(e.elem)
+	delete(c.entries[e.database], e.key)
+	if len(c.entries[e.database]) == 0 {
+		delete(c.entries, e.database)
+	}
+}
+
+// overlaps returns true if the invalidation applies to results of database
+// covering the time range min to max.
+func (inv resultCacheInvalidation) overlaps(database string, min, max int64) bool {
+	if inv.database != "" && inv.database != database {
+		return false
+	}
+	return inv.min <= max && inv.max >= min
+}
+
+// cloneResult returns a copy of r that can be sent without modifying r when
+// the receiver merges the rows of consecutive results.
+func cloneResult(r *query.Result) *query.Result {
+	other := *r
+	other.Series = make(models.Rows, len(r.Series))
+	for i, row := range r.Series {
+		cp := *row
+		cp.Values = cp.Values[:len(cp.Values):len(cp.Values)]
+		other.Series[i] = &cp
+	}
+	return &other
+}
+
+// resultCacheScope returns the database and time range the results of stmt
+// depend on. It returns false if the results of stmt cannot be cached, such
+// as when stmt writes its results, depends on the current time or reads
+// system sources.
+func resultCacheScope(stmt *influxql.SelectStatement) (database string, min, max int64, ok bool) {
+	if stmt.Target != nil {
+		return "", 0, 0, false
+	}
+
+	ok = true
+	var hasSubquery bool
+	influxql.WalkFunc(stmt, func(n influxql.Node) {
+		switch n := n.(type) {
+		case *influxql.Call:
+			if n.Name == "now" {
+				ok = false
+			}
+		case *influxql.SubQuery:
+			hasSubquery = true
+		case *influxql.Measurement:
+			if n.SystemIterator != "" || (database != "" && n.Database != database) {
+				ok = false
+			}
+			database = n.Database
+		}
+	})
+	if !ok || database == "" {
+		return "", 0, 0, false
+	}
+
+	// Subqueries may each select their own time range.
+	if hasSubquery {
+		return database, math.MinInt64, math.MaxInt64, true
+	}
+
+	_, tr, err := influxql.ConditionExpr(stmt.Condition, &influxql.NowValuer{Now: time.Now()})
+	if err != nil {
+		return "", 0, 0, false
+	}
+	return database, tr.MinTimeNano(), tr.MaxTimeNano(), true
+}
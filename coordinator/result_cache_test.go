@@ -0,0 +1,57 @@
+package coordinator_test
+
+import (
+	"testing"
+	"time"
+
+	"github.com/influxdata/influxdb/coordinator"
+	"github.com/influxdata/influxdb/models"
+	"github.com/influxdata/influxdb/query"
+)
+
+func TestResultCache_Put(t *testing.T) {
+	results := []*query.Result{{Series: []*models.Row{{Name: "cpu", Columns: []string{"time", "value"}, Values: [][]interface{}{{int64(1), 1.0}}}}}}
+
+	c := coordinator.NewResultCache(2, time.Minute)
+
+	// Results computed across a write to another time range are cached.
+	seq := c.Seq()
+	c.Invalidate("db0", 20, 30)
+	c.Put("db0", "a", 100, 200, seq, results)
+	got, ok := c.Get("db0", "a")
+	if !ok || len(got) != 1 || got[0] == results[0] || got[0].Series[0] == results[0].Series[0] {
+		t.Fatalf("expected a copy of the cached results, got %v", got)
+	}
+
+	// Appending to returned rows must not modify the cached rows.
+	got[0].Series[0].Values = append(got[0].Series[0].Values, []interface{}{int64(2), 2.0})
+	if got, _ := c.Get("db0", "a"); len(got[0].Series[0].Values) != 1 {
+		t.Fatalf("unexpected cached values: %v", got[0].Series[0].Values)
+	}
+
+	// Results computed across an overlapping write are not cached.
+	seq = c.Seq()
+	c.Invalidate("db0", 0, 10)
+	c.Put("db0", "b", 5, 15, seq, results)
+	if _, ok := c.Get("db0", "b"); ok {
+		t.Fatal("expected results computed across a write not to be cached")
+	} else if _, ok := c.Get("db0", "a"); !ok {
+		t.Fatal("expected results outside of the written time range to be kept")
+	}
+
+	// The least recently used results are evicted.
+	seq = c.Seq()
+	c.Put("db0", "b", 5, 15, seq, results)
+	c.Get("db0", "a")
+	c.Put("db1", "c", 5, 15, seq, results)
+	if _, ok := c.Get("db0", "b"); ok {
+		t.Fatal("expected least recently used results to be evicted")
+	} else if _, ok := c.Get("db0", "a"); !ok {
+		t.Fatal("expected results to be cached")
+	}
+
+	c.InvalidateAll()
+	if _, ok := c.Get("db1", "c"); ok {
+		t.Fatal("expected results to be invalidated")
+	}
+}
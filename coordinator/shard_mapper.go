@@ -21,12 +21,16 @@ type IteratorCreator interface {
 // LocalShardMapper implements a ShardMapper for local shards.
 type LocalShardMapper struct {
 	MetaClient interface {
+		Database(name string) *meta.DatabaseInfo
 		ShardGroupsByTimeRange(database, policy string, min, max time.Time) (a []meta.ShardGroupInfo, err error)
 	}
 
 	TSDBStore interface {
 		ShardGroup(ids []uint64) tsdb.ShardGroup
 	}
+
+	// QueryDeletedDatabases allows queries of soft-deleted databases.
+	QueryDeletedDatabases bool
 }
 
 // MapShards maps the sources to the appropriate shards into an IteratorCreator.
@@ -56,6 +60,12 @@ func (e *LocalShardMapper) mapShards(a *LocalShardMapping, sources influxql.Sour
 			// shards is always the same regardless of which measurement we are
 			// using.
 			if _, ok := a.ShardMap[source]; !ok {
+				if !e.QueryDeletedDatabases {
+					if di := e.MetaClient.Database(s.Database); di != nil && di.Deleted() {
+						return meta.ErrDatabaseDeleted
+					}
+				}
+
 				groups, err := e.MetaClient.ShardGroupsByTimeRange(s.Database, s.RetentionPolicy, tmin, tmax)
 				if err != nil {
 					return err
@@ -16,6 +16,9 @@ import (
 
 func TestLocalShardMapper(t *testing.T) {
 	var metaClient MetaClient
+	metaClient.DatabaseFn = func(name string) *meta.DatabaseInfo {
+		return &meta.DatabaseInfo{Name: name}
+	}
 	metaClient.ShardGroupsByTimeRangeFn = func(database, policy string, min, max time.Time) ([]meta.ShardGroupInfo, error) {
 		if database != "db0" {
 			t.Errorf("unexpected database: %s", database)
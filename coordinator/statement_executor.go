@@ -58,6 +58,14 @@ type StatementExecutor struct {
 	MaxSelectPointN   int
 	MaxSelectSeriesN  int
 	MaxSelectBucketsN int
+
+	// ResultCache caches the results of SELECT statements, if set.
+	ResultCache *ResultCache
+
+	// SoftDeleteDatabases marks dropped databases as deleted instead of
+	// removing them, so they can be restored until the retention service
+	// removes them after the database delete grace period.
+	SoftDeleteDatabases bool
 }
 
 // ExecuteStatement executes the given statement with the given execution context.
@@ -67,6 +75,15 @@ func (e *StatementExecutor) ExecuteStatement(ctx *query.ExecutionContext, stmt i
 		return e.executeSelectStatement(ctx, stmt)
 	}
 
+	// Cached results may include data removed by the statement.
+	if e.ResultCache != nil {
+		switch stmt.(type) {
+		case *influxql.DeleteSeriesStatement, *influxql.DropDatabaseStatement, *influxql.DropMeasurementStatement,
+			*influxql.DropSeriesStatement, *influxql.DropRetentionPolicyStatement, *influxql.DropShardStatement:
+			defer e.ResultCache.InvalidateAll()
+		}
+	}
+
 	var rows models.Rows
 	var messages []*query.Message
 	var err error
@@ -337,10 +354,14 @@ func (e *StatementExecutor) executeDropContinuousQueryStatement(q *influxql.Drop
 
 // executeDropDatabaseStatement drops a database from the cluster.
 // It does not return an error if the database was not found on any of
-// the nodes, or in the Meta store.
+// the nodes, or in the Meta store. When soft deletes are enabled, the
+// database is only marked deleted unless it already was.
 func (e *StatementExecutor) executeDropDatabaseStatement(stmt *influxql.DropDatabaseStatement) error {
-	if e.MetaClient.Database(stmt.Name) == nil {
+	di := e.MetaClient.Database(stmt.Name)
+	if di == nil {
 		return nil
+	} else if e.SoftDeleteDatabases && !di.Deleted() {
+		return e.MetaClient.MarkDatabaseDeleted(stmt.Name)
 	}
 
 	// Locally delete the datababse.
@@ -451,7 +472,8 @@ func (e *StatementExecutor) executeExplainAnalyzeStatement(ectx *query.Execution
 	ctx = query.NewContextWithIterators(ctx, &aux)
 	start := time.Now()
 
-	cur, err := e.createIterators(ctx, stmt, ectx.ExecutionOptions)
+	sm := &explainShardMapper{ShardMapper: e.ShardMapper, MetaClient: e.MetaClient}
+	cur, err := e.createIterators(ctx, stmt, sm, ectx.ExecutionOptions)
 	if err != nil {
 		return nil, err
 	}
@@ -496,6 +518,9 @@ CLEANUP:
 		fields.Duration("total_time", totalTime),
 		fields.Duration("planning_time", iterTime),
 		fields.Duration("execution_time", totalTime-iterTime),
+		fields.Int64("shards_mapped", int64(sm.mappedN)),
+		fields.Int64("shards_pruned", int64(sm.prunedN)),
+		fields.Int64("rows_returned", writeN),
 	)
 	span.Finish()
 
@@ -509,6 +534,62 @@ CLEANUP:
 	return models.Rows{row}, nil
 }
 
+// explainShardMapper counts the shards mapped for a statement, as returned by
+// the underlying mapper, and the shards of the retention policies it reads
+// stored on this node that are pruned by its time range.
+type explainShardMapper struct {
+	query.ShardMapper
+	MetaClient MetaClient
+
+	mappedN, prunedN int
+}
+
+// MapShards maps the sources with the underlying mapper and counts their shards.
+func (m *explainShardMapper) MapShards(sources influxql.Sources, t influxql.TimeRange, opt query.SelectOptions) (query.ShardGroup, error) {
+	sg, err := m.ShardMapper.MapShards(sources, t, opt)
+	if err != nil {
+		return nil, err
+	}
+
+	mapping, ok := sg.(*LocalShardMapping)
+	if !ok {
+		return sg, nil
+	}
+	local, _ := m.ShardMapper.(*LocalShardMapper)
+	for source, g := range mapping.ShardMap {
+		mappedN := shardGroupLen(g)
+		m.mappedN += mappedN
+		if local == nil {
+			continue
+		}
+
+		rpi, err := m.MetaClient.RetentionPolicy(source.Database, source.RetentionPolicy)
+		if err != nil || rpi == nil {
+			continue
+		}
+		var shardIDs []uint64
+		for i := range rpi.ShardGroups {
+			if g := &rpi.ShardGroups[i]; !g.Deleted() {
+				for _, si := range g.Shards {
+					shardIDs = append(shardIDs, si.ID)
+				}
+			}
+		}
+		if n := shardGroupLen(local.TSDBStore.ShardGroup(shardIDs)) - mappedN; n > 0 {
+			m.prunedN += n
+		}
+	}
+	return sg, nil
+}
+
+// shardGroupLen returns the number of shards in g, or 0 if it cannot tell.
+func shardGroupLen(g tsdb.ShardGroup) int {
+	if g, ok := g.(interface{ Len() int }); ok {
+		return g.Len()
+	}
+	return 0
+}
+
 func (e *StatementExecutor) executeGrantStatement(stmt *influxql.GrantStatement) error {
 	return e.MetaClient.SetPrivilege(stmt.User, stmt.On, stmt.Privilege)
 }
@@ -542,7 +623,31 @@ func (e *StatementExecutor) executeSetPasswordUserStatement(q *influxql.SetPassw
 }
 
 func (e *StatementExecutor) executeSelectStatement(ctx *query.ExecutionContext, stmt *influxql.SelectStatement) error {
-	cur, err := e.createIterators(ctx, stmt, ctx.ExecutionOptions)
+	// Serve the results from the cache if possible, or collect them to cache.
+	var (
+		cacheable          bool
+		cacheKey, cacheDB  string
+		cacheMin, cacheMax int64
+		cacheSeq           uint64
+		cached             []*query.Result
+	)
+	if e.ResultCache != nil && query.AuthorizerIsOpen(ctx.ExecutionOptions.Authorizer) {
+		cacheDB, cacheMin, cacheMax, cacheable = resultCacheScope(stmt)
+	}
+	if cacheable {
+		cacheKey = strconv.Itoa(ctx.ChunkSize) + " " + stmt.String()
+		if results, ok := e.ResultCache.Get(cacheDB, cacheKey); ok {
+			for _, result := range results {
+				if err := ctx.Send(result); err != nil {
+					return err
+				}
+			}
+			return nil
+		}
+		cacheSeq = e.ResultCache.Seq()
+	}
+
+	cur, err := e.createIterators(ctx, stmt, e.ShardMapper, ctx.ExecutionOptions)
 	if err != nil {
 		return err
 	}
@@ -550,6 +655,7 @@ func (e *StatementExecutor) executeSelectStatement(ctx *query.ExecutionContext,
 	// Generate a row emitter from the iterator set.
 	em := query.NewEmitter(cur, ctx.ChunkSize)
 	defer em.Close()
+	defer func() { ctx.AddIteratorStats(cur.Stats()) }()
 
 	// Emit rows to the results channel.
 	var writeN int64
@@ -588,6 +694,9 @@ func (e *StatementExecutor) executeSelectStatement(ctx *query.ExecutionContext,
 			Series:  []*models.Row{row},
 			Partial: partial,
 		}
+		if cacheable {
+			cached = append(cached, cloneResult(result))
+		}
 
 		// Send results or exit if closing.
 		if err := ctx.Send(result); err != nil {
@@ -620,15 +729,24 @@ func (e *StatementExecutor) executeSelectStatement(ctx *query.ExecutionContext,
 
 	// Always emit at least one result.
 	if !emitted {
-		return ctx.Send(&query.Result{
+		result := &query.Result{
 			Series: make([]*models.Row, 0),
-		})
+		}
+		if cacheable {
+			cached = append(cached, cloneResult(result))
+		}
+		if err := ctx.Send(result); err != nil {
+			return err
+		}
 	}
 
+	if cacheable {
+		e.ResultCache.Put(cacheDB, cacheKey, cacheMin, cacheMax, cacheSeq, cached)
+	}
 	return nil
 }
 
-func (e *StatementExecutor) createIterators(ctx context.Context, stmt *influxql.SelectStatement, opt query.ExecutionOptions) (query.Cursor, error) {
+func (e *StatementExecutor) createIterators(ctx context.Context, stmt *influxql.SelectStatement, shardMapper query.ShardMapper, opt query.ExecutionOptions) (query.Cursor, error) {
 	sopt := query.SelectOptions{
 		NodeID:      opt.NodeID,
 		MaxSeriesN:  e.MaxSelectSeriesN,
@@ -638,7 +756,7 @@ func (e *StatementExecutor) createIterators(ctx context.Context, stmt *influxql.
 	}
 
 	// Create a set of iterators from a selection.
-	cur, err := query.Select(ctx, stmt, e.ShardMapper, sopt)
+	cur, err := query.Select(ctx, stmt, shardMapper, sopt)
 	if err != nil {
 		return nil, err
 	}
@@ -669,6 +787,9 @@ func (e *StatementExecutor) executeShowDatabasesStatement(ctx *query.ExecutionCo
 
 	row := &models.Row{Name: "databases", Columns: []string{"name"}}
 	for _, di := range dis {
+		if di.Deleted() {
+			continue
+		}
 		// Only include databases that the user is authorized to read or write.
 		if a.AuthorizeDatabase(influxql.ReadPrivilege, di.Name) || a.AuthorizeDatabase(influxql.WritePrivilege, di.Name) {
 			row.Values = append(row.Values, []interface{}{di.Name})
@@ -9,6 +9,7 @@ import (
 	"os"
 	"reflect"
 	"regexp"
+	"strings"
 	"testing"
 	"time"
 
@@ -85,6 +86,136 @@ func TestQueryExecutor_ExecuteQuery_SelectStatement(t *testing.T) {
 	}
 }
 
+// Ensure EXPLAIN ANALYZE reports the shards pruned by the time range of a query.
+func TestQueryExecutor_ExecuteQuery_ExplainAnalyze(t *testing.T) {
+	e := DefaultQueryExecutor()
+
+	// Shard 102 is not stored on this node, as no point was written to it.
+	day := time.Date(2000, 1, 1, 0, 0, 0, 0, time.UTC)
+	groups := []meta.ShardGroupInfo{
+		{ID: 1, StartTime: day.Add(-24 * time.Hour), EndTime: day, Shards: []meta.ShardInfo{
+			{ID: 100, Owners: []meta.ShardOwner{{NodeID: 0}}},
+		}},
+		{ID: 2, StartTime: day, EndTime: day.Add(24 * time.Hour), Shards: []meta.ShardInfo{
+			{ID: 101, Owners: []meta.ShardOwner{{NodeID: 0}}},
+			{ID: 102, Owners: []meta.ShardOwner{{NodeID: 0}}},
+		}},
+	}
+	e.MetaClient.RetentionPolicyFn = func(database, name string) (*meta.RetentionPolicyInfo, error) {
+		return &meta.RetentionPolicyInfo{Name: name, ShardGroups: groups}, nil
+	}
+	e.MetaClient.ShardGroupsByTimeRangeFn = func(database, policy string, min, max time.Time) (a []meta.ShardGroupInfo, err error) {
+		return groups[1:], nil
+	}
+	e.TSDBStore.ShardGroupFn = func(ids []uint64) tsdb.ShardGroup {
+		var sh MockShard
+		for _, id := range ids {
+			if id != 102 {
+				sh.ShardN++
+			}
+		}
+		sh.CreateIteratorFn = func(_ context.Context, _ *influxql.Measurement, _ query.IteratorOptions) (query.Iterator, error) {
+			return &FloatIterator{Points: []query.FloatPoint{
+				{Name: "cpu", Time: day.UnixNano(), Aux: []interface{}{float64(100)}},
+			}}, nil
+		}
+		sh.FieldDimensionsFn = func(measurements []string) (fields map[string]influxql.DataType, dimensions map[string]struct{}, err error) {
+			return map[string]influxql.DataType{"value": influxql.Float}, nil, nil
+		}
+		return &sh
+	}
+
+	results := ReadAllResults(e.ExecuteQuery(`EXPLAIN ANALYZE SELECT * FROM cpu WHERE time >= '2000-01-01T00:00:00Z' AND time < '2000-01-01T12:00:00Z'`, "db0", 0))
+	if len(results) != 1 || results[0].Err != nil || len(results[0].Series) != 1 {
+		t.Fatalf("unexpected results: %s", spew.Sdump(results))
+	}
+
+	lines := make(map[string]bool)
+	for _, v := range results[0].Series[0].Values {
+		lines[strings.TrimLeft(v[0].(string), "├└│─ ")] = true
+	}
+	for _, exp := range []string{"shards_mapped: 1", "shards_pruned: 1", "rows_returned: 1"} {
+		if !lines[exp] {
+			t.Fatalf("missing %q: %s", exp, spew.Sdump(results[0].Series[0].Values))
+		}
+	}
+}
+
+// Ensure query executor serves repeated statements from the result cache until invalidated.
+func TestQueryExecutor_ExecuteQuery_ResultCache(t *testing.T) {
+	e := DefaultQueryExecutor()
+	e.StatementExecutor.ResultCache = coordinator.NewResultCache(10, time.Minute)
+
+	e.MetaClient.ShardGroupsByTimeRangeFn = func(database, policy string, min, max time.Time) (a []meta.ShardGroupInfo, err error) {
+		return []meta.ShardGroupInfo{
+			{ID: 1, Shards: []meta.ShardInfo{
+				{ID: 100, Owners: []meta.ShardOwner{{NodeID: 0}}},
+			}},
+		}, nil
+	}
+
+	var iterators int
+	e.TSDBStore.ShardGroupFn = func(ids []uint64) tsdb.ShardGroup {
+		var sh MockShard
+		sh.CreateIteratorFn = func(_ context.Context, _ *influxql.Measurement, _ query.IteratorOptions) (query.Iterator, error) {
+			iterators++
+			return &FloatIterator{Points: []query.FloatPoint{
+				{Name: "cpu", Time: int64(1 * time.Second), Aux: []interface{}{float64(100)}},
+			}}, nil
+		}
+		sh.FieldDimensionsFn = func(measurements []string) (fields map[string]influxql.DataType, dimensions map[string]struct{}, err error) {
+			return map[string]influxql.DataType{"value": influxql.Float}, nil, nil
+		}
+		return &sh
+	}
+
+	exp := []*query.Result{{
+		StatementID: 0,
+		Series: []*models.Row{{
+			Name:    "cpu",
+			Columns: []string{"time", "value"},
+			Values:  [][]interface{}{{time.Unix(1, 0).UTC(), float64(100)}},
+		}},
+	}}
+
+	for i, tt := range []struct {
+		q          string
+		invalidate func()
+		iterators  int
+	}{
+		{q: `SELECT * FROM cpu WHERE time < '2000-01-01T00:00:00Z'`, iterators: 1},
+		{q: `SELECT * FROM cpu WHERE time < '2000-01-01T00:00:00Z'`, iterators: 1},
+		// Writes outside of the time range or to another database keep the results.
+		{
+			q:          `SELECT * FROM cpu WHERE time < '2000-01-01T00:00:00Z'`,
+			invalidate: func() { e.StatementExecutor.ResultCache.Invalidate("db0", ts("2020-01-01T00:00:00Z").UnixNano(), ts("2020-01-01T00:00:00Z").UnixNano()) },
+			iterators:  1,
+		},
+		{
+			q:          `SELECT * FROM cpu WHERE time < '2000-01-01T00:00:00Z'`,
+			invalidate: func() { e.StatementExecutor.ResultCache.Invalidate("db1", 0, 0) },
+			iterators:  1,
+		},
+		{
+			q:          `SELECT * FROM cpu WHERE time < '2000-01-01T00:00:00Z'`,
+			invalidate: func() { e.StatementExecutor.ResultCache.Invalidate("db0", 0, 0) },
+			iterators:  2,
+		},
+		// Statements relative to the current time are not cached.
+		{q: `SELECT * FROM cpu WHERE time < now()`, iterators: 3},
+		{q: `SELECT * FROM cpu WHERE time < now()`, iterators: 4},
+	} {
+		if tt.invalidate != nil {
+			tt.invalidate()
+		}
+		if a := ReadAllResults(e.ExecuteQuery(tt.q, "db0", 0)); !reflect.DeepEqual(a, exp) {
+			t.Fatalf("%d. unexpected results: %s", i, spew.Sdump(a))
+		} else if iterators != tt.iterators {
+			t.Fatalf("%d. unexpected iterators: exp %d, got %d", i, tt.iterators, iterators)
+		}
+	}
+}
+
 // Ensure query executor can enforce a maximum bucket selection count.
 func TestQueryExecutor_ExecuteQuery_MaxSelectBucketsN(t *testing.T) {
 	e := DefaultQueryExecutor()
@@ -580,6 +711,7 @@ func (e *QueryExecutor) ExecuteQuery(q, database string, chunkSize int) <-chan *
 
 type MockShard struct {
 	Measurements             []string
+	ShardN                   int
 	FieldDimensionsFn        func(measurements []string) (fields map[string]influxql.DataType, dimensions map[string]struct{}, err error)
 	FieldKeysByMeasurementFn func(name []byte) []string
 	CreateIteratorFn         func(ctx context.Context, m *influxql.Measurement, opt query.IteratorOptions) (query.Iterator, error)
@@ -587,6 +719,11 @@ type MockShard struct {
 	ExpandSourcesFn          func(sources influxql.Sources) (influxql.Sources, error)
 }
 
+// Len returns the number of shards the mock stands for.
+func (sh *MockShard) Len() int {
+	return sh.ShardN
+}
+
 func (sh *MockShard) MeasurementsByRegex(re *regexp.Regexp) []string {
 	names := make([]string, 0, len(sh.Measurements))
 	for _, name := range sh.Measurements {
@@ -0,0 +1,170 @@
+package coordinator
+
+import (
+	"fmt"
+	"strings"
+	"sync/atomic"
+	"unicode/utf8"
+
+	"github.com/influxdata/influxdb/models"
+	"golang.org/x/text/unicode/norm"
+)
+
+// Policies for strings of the points written that are not valid UTF-8.
+const (
+	// InvalidStringsAccept writes invalid strings as is.
+	InvalidStringsAccept = "accept"
+
+	// InvalidStringsReplace replaces the invalid bytes of strings with the
+	// Unicode replacement character.
+	InvalidStringsReplace = "replace"
+
+	// InvalidStringsReject drops the points with invalid strings.
+	InvalidStringsReject = "reject"
+)
+
+// StringSanitizer checks that the measurement names, tags, field keys and
+// string field values of points are valid UTF-8 before they are written, and
+// optionally normalizes them to Unicode normalization form C (NFC) so equal
+// strings are always stored with the same bytes.
+type StringSanitizer struct {
+	invalid   string
+	normalize bool
+
+	rejectedN int64
+	replacedN int64
+}
+
+// NewStringSanitizer returns a StringSanitizer applying the invalid strings
+// policy and normalizing strings if normalize is set. An empty policy accepts
+// invalid strings.
+func NewStringSanitizer(invalid string, normalize bool) (*StringSanitizer, error) {
+	switch invalid {
+	case "":
+		invalid = InvalidStringsAccept
+	case InvalidStringsAccept, InvalidStringsReplace, InvalidStringsReject:
+	default:
+		return nil, fmt.Errorf("invalid strings policy %q: must be %q, %q or %q", invalid, InvalidStringsAccept, InvalidStringsReplace, InvalidStringsReject)
+	}
+	return &StringSanitizer{invalid: invalid, normalize: normalize}, nil
+}
+
+// RejectedN returns the number of points dropped for invalid strings.
+func (s *StringSanitizer) RejectedN() int64 {
+	if s == nil {
+		return 0
+	}
+	return atomic.LoadInt64(&s.rejectedN)
+}
+
+// ReplacedN returns the number of points whose invalid strings were replaced.
+func (s *StringSanitizer) ReplacedN() int64 {
+	if s == nil {
+		return 0
+	}
+	return atomic.LoadInt64(&s.replacedN)
+}
+
+// Sanitize returns points with their strings sanitized. Points with invalid
+// strings are dropped under the reject policy, as described by transformPoints.
+func (s *StringSanitizer) Sanitize(points []models.Point) ([]models.Point, error) {
+	if s == nil {
+		return points, nil
+	}
+	return transformPoints(points, s.sanitize, nil)
+}
+
+// sanitize sanitizes the strings of the point of t. It returns an error if
+// the point must be dropped.
+func (s *StringSanitizer) sanitize(t *pointTransform) error {
+	if s == nil {
+		return nil
+	}
+
+	fields, err := t.Fields()
+	if err != nil {
+		return err
+	}
+
+	var replaced bool
+	str := func(v string) (string, bool, error) {
+		changed := false
+		if !utf8.ValidString(v) {
+			switch s.invalid {
+			case InvalidStringsReject:
+				atomic.AddInt64(&s.rejectedN, 1)
+				return "", false, fmt.Errorf("invalid UTF-8 string %q on measurement %q", v, t.Name())
+			case InvalidStringsReplace:
+				v = strings.ToValidUTF8(v, string(utf8.RuneError))
+				changed, replaced = true, true
+			}
+		}
+		if s.normalize && !norm.NFC.IsNormalString(v) {
+			v = norm.NFC.String(v)
+			changed = true
+		}
+		return v, changed, nil
+	}
+
+	name, changed, err := str(t.Name())
+	if err != nil {
+		return err
+	} else if changed {
+		t.SetName(name)
+	}
+
+	var tags map[string]string
+	for i, tag := range t.Tags() {
+		k, kChanged, err := str(string(tag.Key))
+		if err != nil {
+			return err
+		}
+		v, vChanged, err := str(string(tag.Value))
+		if err != nil {
+			return err
+		}
+		if (kChanged || vChanged) && tags == nil {
+			tags = make(map[string]string, len(t.Tags()))
+			for _, prev := range t.Tags()[:i] {
+				tags[string(prev.Key)] = string(prev.Value)
+			}
+		}
+		if tags != nil {
+			tags[k] = v
+		}
+	}
+	if tags != nil {
+		t.SetTags(models.NewTags(tags))
+	}
+
+	var other models.Fields
+	for k, v := range fields {
+		sk, kChanged, err := str(k)
+		if err != nil {
+			return err
+		}
+		vChanged := false
+		if sv, ok := v.(string); ok {
+			if sv, vChanged, err = str(sv); err != nil {
+				return err
+			} else if vChanged {
+				v = sv
+			}
+		}
+		if (kChanged || vChanged) && other == nil {
+			other = cloneFields(fields)
+		}
+		if other != nil {
+			delete(other, k)
+			other[sk] = v
+		}
+	}
+	if other != nil {
+		t.SetFields(other)
+	}
+
+	if replaced {
+		atomic.AddInt64(&s.replacedN, 1)
+	}
+	return nil
+}
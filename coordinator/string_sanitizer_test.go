@@ -0,0 +1,96 @@
+package coordinator_test
+
+import (
+	"testing"
+	"time"
+
+	"github.com/influxdata/influxdb/coordinator"
+	"github.com/influxdata/influxdb/models"
+	"github.com/influxdata/influxdb/tsdb"
+)
+
+func TestStringSanitizer_Sanitize(t *testing.T) {
+	newPoints := func() []models.Point {
+		return []models.Point{
+			models.MustNewPoint("cpu", models.NewTags(map[string]string{"host": "server01"}), models.Fields{"value": 1.0}, time.Unix(0, 0)),
+			// An encoded surrogate half is not valid UTF-8.
+			models.MustNewPoint("cpu", models.NewTags(map[string]string{"host": "server\xed\xa0\x80"}), models.Fields{"value": 2.0}, time.Unix(0, 0)),
+			models.MustNewPoint("log", nil, models.Fields{"message": "bad \xff byte"}, time.Unix(0, 0)),
+			// "e" followed by a combining acute accent is "é" in NFC form.
+			models.MustNewPoint("log", models.NewTags(map[string]string{"city": "Cafe\u0301"}), models.Fields{"message": "ok"}, time.Unix(0, 0)),
+		}
+	}
+
+	t.Run("Accept", func(t *testing.T) {
+		s, err := coordinator.NewStringSanitizer(coordinator.InvalidStringsAccept, false)
+		if err != nil {
+			t.Fatal(err)
+		}
+		points := newPoints()
+		got, err := s.Sanitize(points)
+		if err != nil {
+			t.Fatal(err)
+		}
+		for i, p := range points {
+			if got[i] != p {
+				t.Fatalf("point %d was replaced", i)
+			}
+		}
+	})
+
+	t.Run("Replace", func(t *testing.T) {
+		s, err := coordinator.NewStringSanitizer(coordinator.InvalidStringsReplace, true)
+		if err != nil {
+			t.Fatal(err)
+		}
+		points := newPoints()
+		got, err := s.Sanitize(points)
+		if err != nil {
+			t.Fatal(err)
+		}
+
+		exp := []string{
+			"cpu,host=server01 value=1 0",
+			"cpu,host=server\ufffd value=2 0",
+			"log message=\"bad \ufffd byte\" 0",
+			"log,city=Caf\u00e9 message=\"ok\" 0",
+		}
+		for i, p := range got {
+			if p.String() != exp[i] {
+				t.Fatalf("unexpected point %d: exp %q, got %q", i, exp[i], p.String())
+			}
+		}
+		if got[0] != points[0] {
+			t.Fatal("valid point was replaced")
+		} else if n := s.ReplacedN(); n != 2 {
+			t.Fatalf("unexpected replaced count: %d", n)
+		}
+	})
+
+	t.Run("Reject", func(t *testing.T) {
+		s, err := coordinator.NewStringSanitizer(coordinator.InvalidStringsReject, false)
+		if err != nil {
+			t.Fatal(err)
+		}
+		points := newPoints()
+		got, err := s.Sanitize(points)
+		if pwe, ok := err.(tsdb.PartialWriteError); !ok || pwe.Dropped != 2 {
+			t.Fatalf("unexpected error: %v", err)
+		} else if len(got) != 2 || got[0] != points[0] || got[1] != points[3] {
+			t.Fatalf("unexpected points: %v", got)
+		} else if n := s.RejectedN(); n != 2 {
+			t.Fatalf("unexpected rejected count: %d", n)
+		}
+
+		// The points passed in are left untouched.
+		if len(points) != 4 || points[1].String() != "cpu,host=server\xed\xa0\x80 value=2 0" {
+			t.Fatalf("unexpected points: %v", points)
+		}
+	})
+}
+
+func TestNewStringSanitizer_InvalidPolicy(t *testing.T) {
+	if _, err := coordinator.NewStringSanitizer("drop", false); err == nil {
+		t.Fatal("expected error")
+	}
+}
@@ -0,0 +1,323 @@
+package coordinator
+
+import (
+	"errors"
+	"fmt"
+	"sort"
+	"strings"
+	"sync/atomic"
+
+	"github.com/influxdata/influxdb/models"
+	"github.com/influxdata/influxdb/query"
+	"github.com/influxdata/influxql"
+	"go.uber.org/zap"
+)
+
+const (
+	// ValidationActionReject drops the points violating a validation rule.
+	ValidationActionReject = "reject"
+
+	// ValidationActionWarn writes the points violating a validation rule and
+	// logs their number once per write, with a sample violation.
+	ValidationActionWarn = "warn"
+)
+
+// ValidationRule is the configuration of constraints checked against the
+// points of a write before they are written.
+type ValidationRule struct {
+	// Name identifies the rule in the violations it reports.
+	Name string `toml:"name"`
+
+	// Database limits the rule to writes to a database.
+	// An empty value applies it to all databases.
+	Database string `toml:"database"`
+
+	// Measurement is the name of the measurement the rule applies to.
+	Measurement string `toml:"measurement"`
+
+	// Condition is an InfluxQL expression over the tags and fields of the
+	// point that must be true, such as "usage_idle >= 0 AND usage_idle <= 100".
+	Condition string `toml:"condition"`
+
+	// RequiredFields are the fields every point must have.
+	RequiredFields []string `toml:"required-fields"`
+
+	// MonotonicFields are counters that must not decrease over time within
+	// the points of a series in a write.
+	MonotonicFields []string `toml:"monotonic-fields"`
+
+	// Action is either "reject" to drop the violating points or "warn" to
+	// log them. Defaults to "reject".
+	Action string `toml:"action"`
+}
+
+// Validate returns an error if the validation rule is invalid.
+func (r ValidationRule) Validate() error {
+	if r.Name == "" {
+		return errors.New("validation rule name must be specified")
+	} else if r.Measurement == "" {
+		return fmt.Errorf("validation rule %q: measurement must be specified", r.Name)
+	} else if r.Condition == "" && len(r.RequiredFields) == 0 && len(r.MonotonicFields) == 0 {
+		return fmt.Errorf("validation rule %q: condition, required-fields or monotonic-fields must be specified", r.Name)
+	}
+
+	switch r.Action {
+	case "", ValidationActionReject, ValidationActionWarn:
+	default:
+		return fmt.Errorf("validation rule %q: unknown action %q", r.Name, r.Action)
+	}
+
+	if r.Condition != "" {
+		if _, err := influxql.ParseExpr(r.Condition); err != nil {
+			return fmt.Errorf("validation rule %q: %s", r.Name, err)
+		}
+	}
+	return nil
+}
+
+// ValidationViolation describes a point violating a validation rule.
+type ValidationViolation struct {
+	Rule   string
+	Point  string
+	Reason string
+}
+
+func (v ValidationViolation) String() string {
+	return fmt.Sprintf("rule %q: %s: %s", v.Rule, v.Point, v.Reason)
+}
+
+// Error returns a string representation of the violation of a rejecting rule.
+func (v ValidationViolation) Error() string {
+	return "point failed validation: " + v.String()
+}
+
+// validationRuleExpr is a parsed validation rule.
+type validationRuleExpr struct {
+	ValidationRule
+	cond influxql.Expr
+}
+
+// PointValidator checks the points of a write against validation rules
+// before they are written.
+type PointValidator struct {
+	rules map[string][]validationRuleExpr // keyed by measurement
+
+	rejectedN int64
+	warnedN   int64
+
+	Logger *zap.Logger
+}
+
+// NewPointValidator returns a PointValidator checking the given rules.
+func NewPointValidator(rules []ValidationRule) (*PointValidator, error) {
+	v := &PointValidator{
+		rules:  make(map[string][]validationRuleExpr),
+		Logger: zap.NewNop(),
+	}
+	for _, r := range rules {
+		if err := r.Validate(); err != nil {
+			return nil, err
+		}
+		e := validationRuleExpr{ValidationRule: r}
+		if r.Condition != "" {
+			e.cond, _ = influxql.ParseExpr(r.Condition)
+		}
+		v.rules[r.Measurement] = append(v.rules[r.Measurement], e)
+	}
+	return v, nil
+}
+
+// RejectedN returns the number of points dropped for violating a rule.
+func (v *PointValidator) RejectedN() int64 {
+	if v == nil {
+		return 0
+	}
+	return atomic.LoadInt64(&v.rejectedN)
+}
+
+// WarnedN returns the number of points written despite violating a rule.
+func (v *PointValidator) WarnedN() int64 {
+	if v == nil {
+		return 0
+	}
+	return atomic.LoadInt64(&v.warnedN)
+}
+
+// Validate returns the points written to database without those violating a
+// rejecting rule, as described by transformPoints.
+func (v *PointValidator) Validate(database string, points []models.Point) ([]models.Point, error) {
+	pv := v.validation(database)
+	if pv == nil {
+		return points, nil
+	}
+	return transformPoints(points, pv.check, pv.finish)
+}
+
+// monotonicValue is a value of a monotonic field of a series.
+type monotonicValue struct {
+	t     *pointTransform
+	time  int64
+	value float64
+}
+
+// pointValidation is the validation of the points of a write. Monotonic
+// fields are checked across the points once they have all been checked.
+type pointValidation struct {
+	v        *PointValidator
+	database string
+
+	// Values of monotonic fields keyed by rule, series and field.
+	monotonic      map[string][]monotonicValue
+	monotonicRules map[string]validationRuleExpr
+
+	// Violations of warning rules, logged once for the write.
+	warnedN int
+	warning ValidationViolation
+}
+
+// validation returns the validation of the points written to database, or
+// nil if there are no rules.
+func (v *PointValidator) validation(database string) *pointValidation {
+	if v == nil || len(v.rules) == 0 {
+		return nil
+	}
+	return &pointValidation{
+		v:              v,
+		database:       database,
+		monotonic:      make(map[string][]monotonicValue),
+		monotonicRules: make(map[string]validationRuleExpr),
+	}
+}
+
+// check checks the point of t against the rules of its measurement. It
+// returns the first violation of a rejecting rule.
+func (pv *pointValidation) check(t *pointTransform) error {
+	if pv == nil {
+		return nil
+	}
+	rules := pv.v.rules[t.Name()]
+	if len(rules) == 0 {
+		return nil
+	}
+
+	fields, err := t.Fields()
+	if err != nil {
+		return err
+	}
+
+	var key string
+	var violation error
+	report := func(r validationRuleExpr, reason string) {
+		if key == "" {
+			key = string(models.MakeKey([]byte(t.Name()), t.Tags()))
+		}
+		vi := ValidationViolation{Rule: r.Name, Point: key, Reason: reason}
+		if r.Action == ValidationActionWarn {
+			pv.warn(vi)
+		} else if violation == nil {
+			violation = vi
+		}
+	}
+
+	for _, r := range rules {
+		if r.Database != "" && r.Database != pv.database {
+			continue
+		}
+
+		for _, name := range r.RequiredFields {
+			if _, ok := fields[name]; !ok {
+				report(r, fmt.Sprintf("missing field %q", name))
+			}
+		}
+
+		if r.cond != nil {
+			m := make(map[string]interface{}, len(fields)+len(t.Tags()))
+			for _, tag := range t.Tags() {
+				m[string(tag.Key)] = string(tag.Value)
+			}
+			for k, fv := range fields {
+				m[k] = fv
+			}
+			valuer := influxql.ValuerEval{
+				Valuer: influxql.MultiValuer(
+					query.MathValuer{},
+					influxql.MapValuer(m),
+				),
+				IntegerFloatDivision: true,
+			}
+			if !valuer.EvalBool(r.cond) {
+				report(r, fmt.Sprintf("condition %s is false", r.cond))
+			}
+		}
+
+		for _, name := range r.MonotonicFields {
+			var f float64
+			switch fv := fields[name].(type) {
+			case float64:
+				f = fv
+			case int64:
+				f = float64(fv)
+			case uint64:
+				f = float64(fv)
+			default:
+				continue
+			}
+			if key == "" {
+				key = string(models.MakeKey([]byte(t.Name()), t.Tags()))
+			}
+			mkey := r.Name + "\x00" + key + "\x00" + name
+			pv.monotonic[mkey] = append(pv.monotonic[mkey], monotonicValue{t: t, time: t.point.UnixNano(), value: f})
+			pv.monotonicRules[mkey] = r
+		}
+	}
+
+	if violation != nil {
+		atomic.AddInt64(&pv.v.rejectedN, 1)
+	}
+	return violation
+}
+
+// finish drops the points whose monotonic fields decreased, and logs the
+// violations of warning rules.
+func (pv *pointValidation) finish() {
+	if pv == nil {
+		return
+	}
+
+	for key, values := range pv.monotonic {
+		sort.SliceStable(values, func(i, j int) bool { return values[i].time < values[j].time })
+		r := pv.monotonicRules[key]
+		series, name := key[len(r.Name)+1:strings.LastIndexByte(key, 0)], key[strings.LastIndexByte(key, 0)+1:]
+		for i := 1; i < len(values); i++ {
+			if values[i].value >= values[i-1].value {
+				continue
+			}
+			vi := ValidationViolation{Rule: r.Name, Point: series, Reason: fmt.Sprintf("field %q decreased from %v to %v", name, values[i-1].value, values[i].value)}
+			if r.Action == ValidationActionWarn {
+				pv.warn(vi)
+			} else if t := values[i].t; t.err == nil {
+				t.err = vi
+				atomic.AddInt64(&pv.v.rejectedN, 1)
+			}
+		}
+	}
+
+	if pv.warnedN > 0 {
+		pv.v.Logger.Warn("Points violate validation rules",
+			zap.String("db", pv.database),
+			zap.Int("violations", pv.warnedN),
+			zap.String("rule", pv.warning.Rule),
+			zap.String("point", pv.warning.Point),
+			zap.String("reason", pv.warning.Reason))
+		atomic.AddInt64(&pv.v.warnedN, int64(pv.warnedN))
+	}
+}
+
+// warn records a violation of a warning rule. The first one is logged as a
+// sample of the violations of the write.
+func (pv *pointValidation) warn(vi ValidationViolation) {
+	if pv.warnedN == 0 {
+		pv.warning = vi
+	}
+	pv.warnedN++
+}
@@ -0,0 +1,81 @@
+package coordinator_test
+
+import (
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/influxdata/influxdb/coordinator"
+	"github.com/influxdata/influxdb/models"
+	"github.com/influxdata/influxdb/tsdb"
+	"go.uber.org/zap"
+	"go.uber.org/zap/zaptest/observer"
+)
+
+func TestPointValidator_Validate(t *testing.T) {
+	v, err := coordinator.NewPointValidator([]coordinator.ValidationRule{
+		{Name: "idle_range", Measurement: "cpu", Condition: "usage_idle >= 0 AND usage_idle <= 100", RequiredFields: []string{"usage_idle"}},
+		{Name: "bytes_counter", Measurement: "net", MonotonicFields: []string{"bytes_sent"}},
+		{Name: "warn_only", Measurement: "cpu", Condition: "usage_idle > 10", Action: coordinator.ValidationActionWarn},
+		{Name: "other_db", Database: "other", Measurement: "cpu", RequiredFields: []string{"missing"}},
+	})
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	tags := models.NewTags(map[string]string{"host": "server01"})
+	points := []models.Point{
+		models.MustNewPoint("cpu", tags, models.Fields{"usage_idle": 50.0}, time.Unix(0, 0)),
+		models.MustNewPoint("cpu", tags, models.Fields{"usage_idle": 120.0}, time.Unix(1, 0)),
+		models.MustNewPoint("cpu", tags, models.Fields{"usage_user": 5.0}, time.Unix(2, 0)),
+		models.MustNewPoint("cpu", tags, models.Fields{"usage_idle": 5.0}, time.Unix(3, 0)),
+		models.MustNewPoint("net", tags, models.Fields{"bytes_sent": int64(20)}, time.Unix(2, 0)),
+		models.MustNewPoint("net", tags, models.Fields{"bytes_sent": int64(10)}, time.Unix(1, 0)),
+		models.MustNewPoint("net", tags, models.Fields{"bytes_sent": int64(5)}, time.Unix(3, 0)),
+	}
+
+	core, logs := observer.New(zap.WarnLevel)
+	v.Logger = zap.New(core)
+
+	kept, err := v.Validate("db0", points)
+	if perr, ok := err.(tsdb.PartialWriteError); !ok {
+		t.Fatalf("unexpected error: %v", err)
+	} else if perr.Dropped != 3 {
+		t.Fatalf("unexpected dropped count: %d", perr.Dropped)
+	} else if !strings.Contains(perr.Reason, `rule "idle_range"`) || !strings.Contains(perr.Reason, `field "bytes_sent" decreased from 20 to 5`) {
+		t.Fatalf("unexpected reason: %s", perr.Reason)
+	}
+
+	if len(kept) != 4 {
+		t.Fatalf("unexpected points: %v", kept)
+	} else if kept[0] != points[0] || kept[1] != points[3] || kept[2] != points[4] || kept[3] != points[5] {
+		t.Fatalf("unexpected points: %v", kept)
+	}
+
+	if n := v.RejectedN(); n != 3 {
+		t.Fatalf("unexpected rejected count: %d", n)
+	} else if n := v.WarnedN(); n != 2 {
+		t.Fatalf("unexpected warned count: %d", n)
+	}
+
+	// The violations of warning rules are logged once for the write.
+	if entries := logs.All(); len(entries) != 1 {
+		t.Fatalf("unexpected log entries: %v", entries)
+	} else if n := entries[0].ContextMap()["violations"]; n != int64(2) {
+		t.Fatalf("unexpected logged violations: %v", n)
+	}
+}
+
+func TestValidationRule_Validate(t *testing.T) {
+	for _, r := range []coordinator.ValidationRule{
+		{Measurement: "m", Condition: "a > 1"},
+		{Name: "r", Condition: "a > 1"},
+		{Name: "r", Measurement: "m"},
+		{Name: "r", Measurement: "m", Condition: "a >"},
+		{Name: "r", Measurement: "m", Condition: "a > 1", Action: "drop"},
+	} {
+		if err := r.Validate(); err == nil {
+			t.Fatalf("expected error for %+v", r)
+		}
+	}
+}
@@ -0,0 +1,117 @@
+package coordinator
+
+import (
+	"errors"
+	"fmt"
+	"sync/atomic"
+
+	"github.com/influxdata/influxdb/models"
+)
+
+// WriteFreeze is the configuration of a database or measurement frozen for
+// writes, such as during a migration or once a measurement name is deprecated.
+// Queries of a frozen database or measurement are not affected.
+type WriteFreeze struct {
+	// Database is the name of the frozen database.
+	Database string `toml:"database"`
+
+	// Measurement limits the freeze to a measurement of the database.
+	// An empty value freezes the whole database.
+	Measurement string `toml:"measurement"`
+
+	// Reason is included in the error returned for the writes rejected.
+	Reason string `toml:"reason"`
+}
+
+// Validate returns an error if the write freeze is invalid.
+func (f WriteFreeze) Validate() error {
+	if f.Database == "" {
+		return errors.New("write freeze database must be specified")
+	}
+	return nil
+}
+
+// WriteFrozenError is returned when a write targets a frozen database or measurement.
+type WriteFrozenError struct {
+	Database    string
+	Measurement string
+	Reason      string
+}
+
+// Error returns a string representation of the error.
+func (e WriteFrozenError) Error() string {
+	s := fmt.Sprintf("database %q is frozen for writes", e.Database)
+	if e.Measurement != "" {
+		s = fmt.Sprintf("measurement %q of database %q is frozen for writes", e.Measurement, e.Database)
+	}
+	if e.Reason != "" {
+		s += ": " + e.Reason
+	}
+	return s
+}
+
+// WriteFrozen returns true to indicate the write was rejected by a freeze.
+func (e WriteFrozenError) WriteFrozen() bool { return true }
+
+// WriteFreezes rejects the writes to frozen databases and measurements.
+type WriteFreezes struct {
+	freezes   map[string]map[string]WriteFreeze // keyed by database and measurement
+	rejectedN int64
+}
+
+// NewWriteFreezes returns WriteFreezes enforcing the given freezes.
+func NewWriteFreezes(freezes []WriteFreeze) (*WriteFreezes, error) {
+	w := &WriteFreezes{freezes: make(map[string]map[string]WriteFreeze)}
+	for _, f := range freezes {
+		if err := f.Validate(); err != nil {
+			return nil, err
+		}
+
+		m := w.freezes[f.Database]
+		if m == nil {
+			m = make(map[string]WriteFreeze)
+			w.freezes[f.Database] = m
+		} else if _, ok := m[f.Measurement]; ok {
+			return nil, fmt.Errorf("write freeze %q: duplicate measurement %q", f.Database, f.Measurement)
+		}
+		m[f.Measurement] = f
+	}
+	return w, nil
+}
+
+// RejectedN returns the number of writes rejected by a freeze.
+func (w *WriteFreezes) RejectedN() int64 {
+	if w == nil {
+		return 0
+	}
+	return atomic.LoadInt64(&w.rejectedN)
+}
+
+// Check returns a WriteFrozenError if database or the measurement of any of
+// points is frozen. The write is rejected as a whole so that no point of it
+// is written.
+func (w *WriteFreezes) Check(database string, points []models.Point) error {
+	if w == nil {
+		return nil
+	}
+
+	m := w.freezes[database]
+	if len(m) == 0 {
+		return nil
+	}
+
+	f, ok := m[""]
+	if !ok {
+		for _, p := range points {
+			if f, ok = m[string(p.Name())]; ok {
+				break
+			}
+		}
+	}
+	if !ok {
+		return nil
+	}
+
+	atomic.AddInt64(&w.rejectedN, 1)
+	return WriteFrozenError{Database: f.Database, Measurement: f.Measurement, Reason: f.Reason}
+}
@@ -0,0 +1,59 @@
+package coordinator_test
+
+import (
+	"testing"
+	"time"
+
+	"github.com/influxdata/influxdb"
+	"github.com/influxdata/influxdb/coordinator"
+	"github.com/influxdata/influxdb/models"
+)
+
+func TestWriteFreezes_Check(t *testing.T) {
+	w, err := coordinator.NewWriteFreezes([]coordinator.WriteFreeze{
+		{Database: "db0", Reason: "migrating"},
+		{Database: "db1", Measurement: "cpu_old", Reason: "renamed to cpu"},
+	})
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	cpu := models.MustNewPoint("cpu", nil, models.Fields{"value": 1.0}, time.Unix(0, 0))
+	cpuOld := models.MustNewPoint("cpu_old", nil, models.Fields{"value": 1.0}, time.Unix(0, 0))
+
+	// The whole database is frozen.
+	err = w.Check("db0", []models.Point{cpu})
+	if !influxdb.IsWriteFrozenError(err) {
+		t.Fatalf("unexpected error: %v", err)
+	} else if exp := `database "db0" is frozen for writes: migrating`; err.Error() != exp {
+		t.Fatalf("unexpected error: exp %q, got %q", exp, err.Error())
+	}
+
+	// Only the frozen measurement is rejected, along with the rest of its write.
+	if err := w.Check("db1", []models.Point{cpu}); err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	err = w.Check("db1", []models.Point{cpu, cpuOld})
+	if !influxdb.IsWriteFrozenError(err) {
+		t.Fatalf("unexpected error: %v", err)
+	} else if exp := `measurement "cpu_old" of database "db1" is frozen for writes: renamed to cpu`; err.Error() != exp {
+		t.Fatalf("unexpected error: exp %q, got %q", exp, err.Error())
+	}
+
+	if err := w.Check("db2", []models.Point{cpuOld}); err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	} else if n := w.RejectedN(); n != 2 {
+		t.Fatalf("unexpected rejected count: %d", n)
+	}
+}
+
+func TestNewWriteFreezes_Invalid(t *testing.T) {
+	for _, freezes := range [][]coordinator.WriteFreeze{
+		{{Measurement: "cpu"}},
+		{{Database: "db0", Measurement: "cpu"}, {Database: "db0", Measurement: "cpu"}},
+	} {
+		if _, err := coordinator.NewWriteFreezes(freezes); err == nil {
+			t.Fatalf("expected error for %+v", freezes)
+		}
+	}
+}
@@ -0,0 +1,124 @@
+package coordinator
+
+import (
+	"fmt"
+	"sync/atomic"
+	"time"
+
+	"github.com/influxdata/influxdb/models"
+	"github.com/influxdata/influxdb/toml"
+)
+
+// WriteTimeBound is the configuration of how far from the current time the
+// timestamps of the points written to a database may be.
+type WriteTimeBound struct {
+	// Database is the database the bound applies to. An empty value applies
+	// it to the databases without a bound of their own.
+	Database string `toml:"database"`
+
+	// Future is how far ahead of the current time a point may be.
+	// A value of 0 does not limit it.
+	Future toml.Duration `toml:"future"`
+
+	// Past is how far behind the current time a point may be.
+	// A value of 0 does not limit it.
+	Past toml.Duration `toml:"past"`
+}
+
+// Validate returns an error if the write time bound is invalid.
+func (b WriteTimeBound) Validate() error {
+	if b.Future < 0 {
+		return fmt.Errorf("write time bound %q: future must be non-negative", b.Database)
+	} else if b.Past < 0 {
+		return fmt.Errorf("write time bound %q: past must be non-negative", b.Database)
+	}
+	return nil
+}
+
+// WriteTimeBounds drops the points written with timestamps too far from the
+// current time, such as those of agents with a skewed clock.
+type WriteTimeBounds struct {
+	bounds   map[string]WriteTimeBound // keyed by database
+	droppedN int64
+
+	now func() time.Time
+}
+
+// NewWriteTimeBounds returns WriteTimeBounds enforcing the given bounds.
+func NewWriteTimeBounds(bounds []WriteTimeBound) (*WriteTimeBounds, error) {
+	b := &WriteTimeBounds{
+		bounds: make(map[string]WriteTimeBound),
+		now:    time.Now,
+	}
+	for _, bound := range bounds {
+		if err := bound.Validate(); err != nil {
+			return nil, err
+		} else if _, ok := b.bounds[bound.Database]; ok {
+			return nil, fmt.Errorf("write time bound %q: duplicate database", bound.Database)
+		}
+		b.bounds[bound.Database] = bound
+	}
+	return b, nil
+}
+
+// DroppedN returns the number of points dropped for being out of bounds.
+func (b *WriteTimeBounds) DroppedN() int64 {
+	if b == nil {
+		return 0
+	}
+	return atomic.LoadInt64(&b.droppedN)
+}
+
+// Filter returns the points written to database within its bounds. Points
+// out of bounds are dropped, as described by transformPoints.
+func (b *WriteTimeBounds) Filter(database string, points []models.Point) ([]models.Point, error) {
+	w := b.window(database)
+	if w.b == nil {
+		return points, nil
+	}
+	return transformPoints(points, w.check, nil)
+}
+
+// writeTimeWindow is the range of timestamps of the points written to a
+// database, computed once per write.
+type writeTimeWindow struct {
+	b        *WriteTimeBounds
+	min, max int64
+}
+
+// window returns the range of timestamps of the points written to database
+// now. The window checks nothing if database has no bound.
+func (b *WriteTimeBounds) window(database string) writeTimeWindow {
+	if b == nil {
+		return writeTimeWindow{}
+	}
+
+	bound, ok := b.bounds[database]
+	if !ok {
+		if bound, ok = b.bounds[""]; !ok {
+			return writeTimeWindow{}
+		}
+	}
+
+	now := b.now()
+	w := writeTimeWindow{b: b, min: models.MinNanoTime, max: models.MaxNanoTime}
+	if bound.Past > 0 {
+		w.min = now.Add(-time.Duration(bound.Past)).UnixNano()
+	}
+	if bound.Future > 0 {
+		w.max = now.Add(time.Duration(bound.Future)).UnixNano()
+	}
+	return w
+}
+
+// check returns an error if the point of t is out of the window.
+func (w writeTimeWindow) check(t *pointTransform) error {
+	if w.b == nil {
+		return nil
+	}
+	if ts := t.point.UnixNano(); ts >= w.min && ts <= w.max {
+		return nil
+	}
+	atomic.AddInt64(&w.b.droppedN, 1)
+	return fmt.Errorf("points outside the write time bounds of %s to %s", time.Unix(0, w.min).UTC().Format(time.RFC3339), time.Unix(0, w.max).UTC().Format(time.RFC3339))
+}
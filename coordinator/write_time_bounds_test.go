@@ -0,0 +1,60 @@
+package coordinator_test
+
+import (
+	"testing"
+	"time"
+
+	"github.com/influxdata/influxdb/coordinator"
+	"github.com/influxdata/influxdb/models"
+	"github.com/influxdata/influxdb/toml"
+	"github.com/influxdata/influxdb/tsdb"
+)
+
+func TestWriteTimeBounds_Filter(t *testing.T) {
+	b, err := coordinator.NewWriteTimeBounds([]coordinator.WriteTimeBound{
+		{Future: toml.Duration(time.Hour), Past: toml.Duration(30 * 24 * time.Hour)},
+		{Database: "unbounded"},
+	})
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	now := time.Now()
+	points := []models.Point{
+		models.MustNewPoint("cpu", nil, models.Fields{"value": 1.0}, now),
+		models.MustNewPoint("cpu", nil, models.Fields{"value": 2.0}, now.Add(2*time.Hour)),
+		models.MustNewPoint("cpu", nil, models.Fields{"value": 3.0}, now.Add(-60*24*time.Hour)),
+		models.MustNewPoint("cpu", nil, models.Fields{"value": 4.0}, now.Add(-time.Hour)),
+	}
+
+	kept, err := b.Filter("db0", points)
+	if perr, ok := err.(tsdb.PartialWriteError); !ok {
+		t.Fatalf("unexpected error: %v", err)
+	} else if perr.Dropped != 2 {
+		t.Fatalf("unexpected dropped count: %d", perr.Dropped)
+	} else if len(kept) != 2 || kept[0] != points[0] || kept[1] != points[3] {
+		t.Fatalf("unexpected points: %v", kept)
+	}
+
+	if kept, err := b.Filter("unbounded", points); err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	} else if len(kept) != len(points) {
+		t.Fatalf("unexpected points: %v", kept)
+	}
+
+	if n := b.DroppedN(); n != 2 {
+		t.Fatalf("unexpected dropped count: %d", n)
+	}
+}
+
+func TestNewWriteTimeBounds_Invalid(t *testing.T) {
+	for _, bounds := range [][]coordinator.WriteTimeBound{
+		{{Database: "db0", Future: toml.Duration(-time.Hour)}},
+		{{Database: "db0", Past: toml.Duration(-time.Hour)}},
+		{{Database: "db0"}, {Database: "db0"}},
+	} {
+		if _, err := coordinator.NewWriteTimeBounds(bounds); err == nil {
+			t.Fatalf("expected error for %+v", bounds)
+		}
+	}
+}
@@ -28,6 +28,24 @@ func IsAuthorizationError(err error) bool {
 	return ok && e.AuthorizationFailed()
 }
 
+// IsWriteFrozenError indicates whether an error is due to a write to a
+// database or measurement frozen for writes.
+func IsWriteFrozenError(err error) bool {
+	e, ok := err.(interface {
+		WriteFrozen() bool
+	})
+	return ok && e.WriteFrozen()
+}
+
+// IsDatabaseDeletedError indicates whether an error is due to a write to a
+// soft-deleted database.
+func IsDatabaseDeletedError(err error) bool {
+	e, ok := err.(interface {
+		DatabaseDeleted() bool
+	})
+	return ok && e.DatabaseDeleted()
+}
+
 // IsClientError indicates whether an error is a known client error.
 func IsClientError(err error) bool {
 	if err == nil {
@@ -30,11 +30,14 @@ type MetaClientMock struct {
 	DropShardFn           func(id uint64) error
 	DropUserFn            func(name string) error
 
+	MarkDatabaseDeletedFn func(name string) error
+
 	OpenFn func() error
 
 	PrecreateShardGroupsFn func(from, to time.Time) error
 	PruneShardGroupsFn     func() error
 
+	RestoreDatabaseFn func(name string) error
 	RetentionPolicyFn func(database, name string) (rpi *meta.RetentionPolicyInfo, err error)
 
 	AuthenticateFn           func(username, password string) (ui meta.User, err error)
@@ -121,6 +124,14 @@ func (c *MetaClientMock) DropUser(name string) error {
 	return c.DropUserFn(name)
 }
 
+func (c *MetaClientMock) MarkDatabaseDeleted(name string) error {
+	return c.MarkDatabaseDeletedFn(name)
+}
+
+func (c *MetaClientMock) RestoreDatabase(name string) error {
+	return c.RestoreDatabaseFn(name)
+}
+
 func (c *MetaClientMock) RetentionPolicy(database, name string) (rpi *meta.RetentionPolicyInfo, err error) {
 	return c.RetentionPolicyFn(database, name)
 }
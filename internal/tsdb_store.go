@@ -22,6 +22,7 @@ type TSDBStoreMock struct {
 	CreateShardSnapshotFn       func(id uint64) (string, error)
 	DatabasesFn                 func() []string
 	DeleteDatabaseFn            func(name string) error
+	DeleteFieldRangeFn          func(database, name, field string, min, max int64) error
 	DeleteMeasurementFn         func(database, name string) error
 	DeleteRetentionPolicyFn     func(database, name string) error
 	DeleteSeriesFn              func(database string, sources []influxql.Source, condition influxql.Expr) error
@@ -74,6 +75,9 @@ func (s *TSDBStoreMock) Databases() []string {
 func (s *TSDBStoreMock) DeleteDatabase(name string) error {
 	return s.DeleteDatabaseFn(name)
 }
+func (s *TSDBStoreMock) DeleteFieldRange(database, name, field string, min, max int64) error {
+	return s.DeleteFieldRangeFn(database, name, field, min, max)
+}
 func (s *TSDBStoreMock) DeleteMeasurement(database string, name string) error {
 	return s.DeleteMeasurementFn(database, name)
 }
@@ -11,9 +11,12 @@ import (
 	"strconv"
 	"strings"
 	"testing"
+	"testing/quick"
 	"time"
 
 	"github.com/influxdata/influxdb/models"
+	"github.com/influxdata/influxdb/pkg/testing/quickpoint"
+	"github.com/influxdata/influxdb/pkg/testing/workload"
 )
 
 var (
@@ -238,6 +241,103 @@ func BenchmarkNewPointFromBinary(b *testing.B) {
 	}
 }
 
+func TestPoint_RoundTrip_Quick(t *testing.T) {
+	equal := func(a, b models.Point) error {
+		if !bytes.Equal(a.Key(), b.Key()) {
+			return fmt.Errorf("key mismatch: %q != %q", a.Key(), b.Key())
+		} else if a.UnixNano() != b.UnixNano() {
+			return fmt.Errorf("time mismatch: %d != %d", a.UnixNano(), b.UnixNano())
+		}
+
+		af, err := a.Fields()
+		if err != nil {
+			return err
+		}
+		bf, err := b.Fields()
+		if err != nil {
+			return err
+		} else if !reflect.DeepEqual(af, bf) {
+			return fmt.Errorf("fields mismatch: %v != %v", af, bf)
+		}
+		return nil
+	}
+
+	if err := quick.Check(func(qp quickpoint.Point) bool {
+		pt, err := qp.Point()
+		if err != nil {
+			t.Errorf("unexpected error creating point %+v: %s", qp, err)
+			return false
+		}
+
+		// Line protocol.
+		pts, err := models.ParsePointsString(pt.String())
+		if err != nil {
+			t.Errorf("unexpected error parsing %q: %s", pt.String(), err)
+			return false
+		} else if len(pts) != 1 {
+			t.Errorf("unexpected point count parsing %q: %d", pt.String(), len(pts))
+			return false
+		} else if err := equal(pt, pts[0]); err != nil {
+			t.Errorf("line protocol round trip of %q: %s", pt.String(), err)
+			return false
+		}
+
+		// Binary encoding.
+		buf, err := pt.MarshalBinary()
+		if err != nil {
+			t.Errorf("unexpected error marshaling %q: %s", pt.String(), err)
+			return false
+		}
+		other, err := models.NewPointFromBytes(buf)
+		if err != nil {
+			t.Errorf("unexpected error unmarshaling %q: %s", pt.String(), err)
+			return false
+		} else if err := equal(pt, other); err != nil {
+			t.Errorf("binary round trip of %q: %s", pt.String(), err)
+			return false
+		}
+		return true
+	}, &quick.Config{MaxCount: 1000}); err != nil {
+		t.Fatal(err)
+	}
+}
+
+func BenchmarkParsePoints_Workloads(b *testing.B) {
+	for _, w := range workload.All(5000) {
+		b.Run(w.Name, func(b *testing.B) {
+			b.SetBytes(int64(len(w.Data)))
+			for i := 0; i < b.N; i++ {
+				if _, err := models.ParsePoints(w.Data); err != nil {
+					b.Fatal(err)
+				}
+			}
+		})
+	}
+}
+
+func BenchmarkPoint_MarshalBinary_Workloads(b *testing.B) {
+	for _, w := range workload.All(5000) {
+		pts, err := models.ParsePoints(w.Data)
+		if err != nil {
+			b.Fatal(err)
+		}
+
+		b.Run(w.Name, func(b *testing.B) {
+			for i := 0; i < b.N; i++ {
+				for _, p := range pts {
+					buf, err := p.MarshalBinary()
+					if err != nil {
+						b.Fatal(err)
+					}
+					if _, err := models.NewPointFromBytes(buf); err != nil {
+						b.Fatal(err)
+					}
+				}
+			}
+		})
+	}
+}
+
 func BenchmarkParsePointNoTags5000(b *testing.B) {
 	var batch [5000]string
 	for i := 0; i < len(batch); i++ {
@@ -0,0 +1,119 @@
+/*
+Package quickpoint generates random points for property-based tests using
+testing/quick, so the parsers and converters of the write path can share the
+same generative checks for round trips and panic safety.
+
+Names and tag values include the characters line protocol must escape and
+string fields include quotes and backslashes.
+*/
+package quickpoint
+
+import (
+	"math"
+	"math/rand"
+	"reflect"
+	"time"
+
+	"github.com/influxdata/influxdb/models"
+)
+
+const (
+	// nameChars are the characters of tag keys, tag values and field keys
+	// after their first letter.
+	nameChars = measurementChars + "="
+
+	// measurementChars are the characters of measurement names after their
+	// first letter. An equal sign is not escaped in measurement names.
+	measurementChars = "abcdefxyzABCXYZ0189_-.:/ ,é"
+
+	// stringChars are the characters of string field values.
+	stringChars = nameChars + `"\'`
+
+	maxNameLen   = 12
+	maxTagN      = 5
+	maxFieldN    = 5
+	maxStringLen = 32
+)
+
+// Point is a random point. It implements quick.Generator.
+type Point struct {
+	Name   string
+	Tags   map[string]string
+	Fields map[string]interface{}
+	Time   time.Time
+}
+
+// Generate returns a random Point.
+func (Point) Generate(r *rand.Rand, size int) reflect.Value {
+	ns := r.Int63n(models.MaxNanoTime)
+	if r.Intn(2) == 0 {
+		ns = -ns
+	}
+
+	p := Point{
+		Name:   name(r, measurementChars),
+		Tags:   make(map[string]string),
+		Fields: make(map[string]interface{}),
+		Time:   time.Unix(0, ns).UTC(),
+	}
+	for i, n := 0, r.Intn(maxTagN+1); i < n; i++ {
+		p.Tags[name(r, nameChars)] = name(r, nameChars)
+	}
+	for i, n := 0, 1+r.Intn(maxFieldN); i < n; i++ {
+		k := name(r, nameChars)
+		if _, ok := p.Tags[k]; ok {
+			continue
+		}
+		p.Fields[k] = value(r)
+	}
+	if len(p.Fields) == 0 {
+		p.Fields["value"] = value(r)
+	}
+	return reflect.ValueOf(p)
+}
+
+// Point returns p as a models.Point.
+func (p Point) Point() (models.Point, error) {
+	return models.NewPoint(p.Name, models.NewTags(p.Tags), p.Fields, p.Time)
+}
+
+// name returns a random name of chars starting with a letter. It is never
+// "time", which is not allowed as a tag or field key.
+func name(r *rand.Rand, chars string) string {
+	for {
+		b := []rune{rune('a' + r.Intn(26))}
+		runes := []rune(chars)
+		for i, n := 0, r.Intn(maxNameLen); i < n; i++ {
+			b = append(b, runes[r.Intn(len(runes))])
+		}
+		if s := string(b); s != "time" {
+			return s
+		}
+	}
+}
+
+// value returns a random field value of a random type.
+func value(r *rand.Rand) interface{} {
+	switch r.Intn(4) {
+	case 0:
+		switch r.Intn(4) {
+		case 0:
+			return 0.0
+		case 1:
+			return math.MaxFloat64 * (r.Float64()*2 - 1)
+		default:
+			return r.NormFloat64() * 1000
+		}
+	case 1:
+		return r.Int63() - r.Int63()
+	case 2:
+		return r.Intn(2) == 1
+	default:
+		chars := []rune(stringChars)
+		b := make([]rune, r.Intn(maxStringLen))
+		for i := range b {
+			b[i] = chars[r.Intn(len(chars))]
+		}
+		return string(b)
+	}
+}
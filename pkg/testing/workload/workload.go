@@ -0,0 +1,95 @@
+/*
+Package workload generates batches of line protocol representative of write
+workloads, so benchmarks of the write path measure the same shapes of data.
+
+The batches are deterministic: a workload of a given size is always the same.
+*/
+package workload
+
+import (
+	"bytes"
+	"fmt"
+	"math/rand"
+	"strconv"
+)
+
+// Workload is a named batch of line protocol.
+type Workload struct {
+	Name string
+	Data []byte
+
+	// N is the number of points in Data.
+	N int
+}
+
+// All returns each workload with n points.
+func All(n int) []Workload {
+	return []Workload{
+		Typical(n),
+		WideSparse(n),
+		HighCardinality(n),
+		HeavyEscaping(n),
+	}
+}
+
+// baseTime is the timestamp of the first point of a workload.
+const baseTime = int64(1600000000000000000)
+
+// Typical returns points of a few series with a handful of fields, where tag
+// values repeat from a small set as with a fleet of hosts.
+func Typical(n int) Workload {
+	r := rand.New(rand.NewSource(1))
+	var buf bytes.Buffer
+	for i := 0; i < n; i++ {
+		fmt.Fprintf(&buf, "cpu,host=server%02d,region=region%d usage_user=%s,usage_system=%s,usage_idle=%s,cpu_count=%di %d\n",
+			i%50, i%4, formatFloat(r.Float64()*100), formatFloat(r.Float64()*100), formatFloat(r.Float64()*100), 1+i%64, baseTime+int64(i))
+	}
+	return Workload{Name: "typical", Data: buf.Bytes(), N: n}
+}
+
+// WideSparse returns points of a measurement with hundreds of fields of
+// which each point only has a few.
+func WideSparse(n int) Workload {
+	const fieldN, fieldsPerPoint = 500, 5
+
+	r := rand.New(rand.NewSource(2))
+	var buf bytes.Buffer
+	for i := 0; i < n; i++ {
+		fmt.Fprintf(&buf, "sparse,host=server%02d ", i%50)
+		for j := 0; j < fieldsPerPoint; j++ {
+			if j > 0 {
+				buf.WriteByte(',')
+			}
+			fmt.Fprintf(&buf, "field%03d=%s", r.Intn(fieldN), formatFloat(r.Float64()))
+		}
+		fmt.Fprintf(&buf, " %d\n", baseTime+int64(i))
+	}
+	return Workload{Name: "wide_sparse", Data: buf.Bytes(), N: n}
+}
+
+// HighCardinality returns points which each belong to a new series, as when
+// a tag holds request or trace identifiers.
+func HighCardinality(n int) Workload {
+	r := rand.New(rand.NewSource(3))
+	var buf bytes.Buffer
+	for i := 0; i < n; i++ {
+		fmt.Fprintf(&buf, "requests,service=api%d,trace_id=%016x,span_id=%08x duration=%di,status=%di %d\n",
+			i%8, r.Uint64(), r.Uint32(), r.Intn(10000), 200+100*(i%4), baseTime+int64(i))
+	}
+	return Workload{Name: "high_cardinality", Data: buf.Bytes(), N: n}
+}
+
+// HeavyEscaping returns points whose names, tags and string fields contain
+// the characters line protocol must escape.
+func HeavyEscaping(n int) Workload {
+	var buf bytes.Buffer
+	for i := 0; i < n; i++ {
+		fmt.Fprintf(&buf, `http\ requests\,total,path=/api/v1/query\=x\,y,user\ agent=curl/7.%d\ (x86_64) message="GET \"/api?q=%d\" \\ done",bytes\ sent=%di %d`+"\n",
+			i%100, i, i*10, baseTime+int64(i))
+	}
+	return Workload{Name: "heavy_escaping", Data: buf.Bytes(), N: n}
+}
+
+func formatFloat(v float64) string {
+	return strconv.FormatFloat(v, 'f', 3, 64)
+}
@@ -0,0 +1,23 @@
+package workload_test
+
+import (
+	"bytes"
+	"testing"
+
+	"github.com/influxdata/influxdb/models"
+	"github.com/influxdata/influxdb/pkg/testing/workload"
+)
+
+func TestAll(t *testing.T) {
+	again := workload.All(100)
+	for i, w := range workload.All(100) {
+		pts, err := models.ParsePoints(w.Data)
+		if err != nil {
+			t.Fatalf("%s: unexpected error: %s", w.Name, err)
+		} else if len(pts) != w.N {
+			t.Fatalf("%s: unexpected point count: got %d, exp %d", w.Name, len(pts), w.N)
+		} else if !bytes.Equal(w.Data, again[i].Data) {
+			t.Fatalf("%s: workload is not deterministic", w.Name)
+		}
+	}
+}
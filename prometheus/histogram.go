@@ -0,0 +1,122 @@
+package prometheus
+
+import (
+	"fmt"
+	"math"
+	"strconv"
+	"time"
+
+	"github.com/influxdata/influxdb/models"
+	dto "github.com/prometheus/client_model/go"
+)
+
+const (
+	// bucketTagKey is the tag key holding the upper bound of a histogram bucket
+	bucketTagKey = "le"
+
+	// bucketFieldName is the field holding the cumulative count of a histogram bucket
+	bucketFieldName = "bucket_count"
+)
+
+// Histogram is a cumulative histogram with explicit bucket bounds, such as a
+// Prometheus histogram or an OpenTelemetry explicit bucket histogram.
+type Histogram struct {
+	Sum   float64
+	Count uint64
+
+	// Buckets are sorted by upper bound and their counts are cumulative.
+	Buckets []HistogramBucket
+}
+
+// HistogramBucket is a bucket of a Histogram.
+type HistogramBucket struct {
+	UpperBound float64
+	Count      uint64
+}
+
+// HistogramFromMetric returns the histogram of a Prometheus histogram metric.
+func HistogramFromMetric(m *dto.Metric) Histogram {
+	h := Histogram{
+		Sum:     m.GetHistogram().GetSampleSum(),
+		Count:   m.GetHistogram().GetSampleCount(),
+		Buckets: make([]HistogramBucket, 0, len(m.GetHistogram().Bucket)),
+	}
+	for _, b := range m.GetHistogram().Bucket {
+		h.Buckets = append(h.Buckets, HistogramBucket{UpperBound: b.GetUpperBound(), Count: b.GetCumulativeCount()})
+	}
+	return h
+}
+
+// Validate returns an error if the buckets of the histogram are not sorted,
+// their counts are not cumulative or exceed the count of the histogram.
+func (h Histogram) Validate() error {
+	if math.IsNaN(h.Sum) || math.IsInf(h.Sum, 0) {
+		return fmt.Errorf("histogram sum is %v", h.Sum)
+	}
+
+	for i, b := range h.Buckets {
+		if math.IsNaN(b.UpperBound) {
+			return fmt.Errorf("histogram bucket %d has a NaN upper bound", i)
+		} else if b.Count > h.Count {
+			return fmt.Errorf("histogram bucket le=%v count %d exceeds the histogram count %d", b.UpperBound, b.Count, h.Count)
+		} else if i == 0 {
+			continue
+		}
+
+		prev := h.Buckets[i-1]
+		if b.UpperBound <= prev.UpperBound {
+			return fmt.Errorf("histogram bucket le=%v follows bucket le=%v", b.UpperBound, prev.UpperBound)
+		} else if b.Count < prev.Count {
+			return fmt.Errorf("histogram bucket le=%v count %d is less than the count %d of bucket le=%v", b.UpperBound, b.Count, prev.Count, prev.UpperBound)
+		}
+	}
+
+	if n := len(h.Buckets); n > 0 && math.IsInf(h.Buckets[n-1].UpperBound, 1) && h.Buckets[n-1].Count != h.Count {
+		return fmt.Errorf("histogram bucket le=+Inf count %d differs from the histogram count %d", h.Buckets[n-1].Count, h.Count)
+	}
+	return nil
+}
+
+// HistogramToPoints converts a histogram into the points written to
+// measurement: a point with the "sum" and "count" fields and a point per
+// bucket with its upper bound in the "le" tag and its cumulative count in the
+// "bucket_count" field. A +Inf bucket is added if it is missing.
+func HistogramToPoints(measurement string, tags models.Tags, h Histogram, t time.Time) ([]models.Point, error) {
+	if err := h.Validate(); err != nil {
+		return nil, err
+	}
+
+	buckets := h.Buckets
+	if n := len(buckets); n == 0 || !math.IsInf(buckets[n-1].UpperBound, 1) {
+		buckets = append(buckets[:n:n], HistogramBucket{UpperBound: math.Inf(1), Count: h.Count})
+	}
+
+	points := make([]models.Point, 0, len(buckets)+1)
+	p, err := models.NewPoint(measurement, tags, models.Fields{"sum": h.Sum, "count": float64(h.Count)}, t)
+	if err != nil {
+		return nil, err
+	}
+	points = append(points, p)
+
+	for _, b := range buckets {
+		bucketTags := tags.Clone()
+		bucketTags.SetString(bucketTagKey, formatBound(b.UpperBound))
+
+		p, err := models.NewPoint(measurement, bucketTags, models.Fields{bucketFieldName: float64(b.Count)}, t)
+		if err != nil {
+			return nil, err
+		}
+		points = append(points, p)
+	}
+	return points, nil
+}
+
+// formatBound formats the upper bound of a bucket as Prometheus does.
+func formatBound(v float64) string {
+	if math.IsInf(v, 1) {
+		return "+Inf"
+	} else if math.IsInf(v, -1) {
+		return "-Inf"
+	}
+	return strconv.FormatFloat(v, 'g', -1, 64)
+}
@@ -0,0 +1,51 @@
+package prometheus
+
+import (
+	"math"
+	"testing"
+	"time"
+
+	"github.com/influxdata/influxdb/models"
+	"github.com/stretchr/testify/require"
+)
+
+func TestHistogramToPoints(t *testing.T) {
+	h := Histogram{
+		Sum:   12.5,
+		Count: 10,
+		Buckets: []HistogramBucket{
+			{UpperBound: 0.5, Count: 4},
+			{UpperBound: 1, Count: 9},
+		},
+	}
+	tags := models.NewTags(map[string]string{"handler": "query"})
+
+	points, err := HistogramToPoints("request_duration", tags, h, time.Unix(0, 0))
+	require.NoError(t, err)
+
+	var lines []string
+	for _, p := range points {
+		lines = append(lines, p.String())
+	}
+	require.Equal(t, []string{
+		"request_duration,handler=query count=10,sum=12.5 0",
+		"request_duration,handler=query,le=0.5 bucket_count=4 0",
+		"request_duration,handler=query,le=1 bucket_count=9 0",
+		"request_duration,handler=query,le=+Inf bucket_count=10 0",
+	}, lines)
+
+	// The buckets of the histogram are left untouched.
+	require.Len(t, h.Buckets, 2)
+}
+
+func TestHistogram_Validate(t *testing.T) {
+	for _, h := range []Histogram{
+		{Sum: math.NaN(), Count: 1},
+		{Count: 1, Buckets: []HistogramBucket{{UpperBound: 1, Count: 2}}},
+		{Count: 2, Buckets: []HistogramBucket{{UpperBound: 1, Count: 1}, {UpperBound: 1, Count: 2}}},
+		{Count: 2, Buckets: []HistogramBucket{{UpperBound: 1, Count: 2}, {UpperBound: 2, Count: 1}}},
+		{Count: 2, Buckets: []HistogramBucket{{UpperBound: 1, Count: 1}, {UpperBound: math.Inf(1), Count: 1}}},
+	} {
+		require.Error(t, h.Validate(), "%+v", h)
+	}
+}
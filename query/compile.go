@@ -280,6 +280,8 @@ func (c *compiledField) compileExpr(expr influxql.Expr) error {
 		switch expr.Name {
 		case "percentile":
 			return c.compilePercentile(expr.Args)
+		case "approx_percentile":
+			return c.compileApproxPercentile(expr.Args)
 		case "sample":
 			return c.compileSample(expr.Args)
 		case "distinct":
@@ -431,6 +433,29 @@ func (c *compiledField) compilePercentile(args []influxql.Expr) error {
 	return c.compileSymbol("percentile", args[0])
 }
 
+func (c *compiledField) compileApproxPercentile(args []influxql.Expr) error {
+	if exp, got := 2, len(args); got != exp {
+		return fmt.Errorf("invalid number of arguments for approx_percentile, expected %d, got %d", exp, got)
+	}
+
+	var percentile float64
+	switch arg1 := args[1].(type) {
+	case *influxql.IntegerLiteral:
+		percentile = float64(arg1.Val)
+	case *influxql.NumberLiteral:
+		percentile = arg1.Val
+	default:
+		return fmt.Errorf("expected float argument in approx_percentile()")
+	}
+	if percentile < 0 || percentile > 100 {
+		return fmt.Errorf("approx_percentile() percentile must be between 0 and 100, got %v", percentile)
+	}
+
+	// An estimated percentile is not the value of one of the points.
+	c.global.OnlySelectors = false
+	return c.compileSymbol("approx_percentile", args[0])
+}
+
 func (c *compiledField) compileSample(args []influxql.Expr) error {
 	if exp, got := 2, len(args); got != exp {
 		return fmt.Errorf("invalid number of arguments for sample, expected %d, got %d", exp, got)
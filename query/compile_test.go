@@ -65,6 +65,8 @@ func TestCompile_Success(t *testing.T) {
 		`SELECT max(bottom) FROM (SELECT bottom(value, host, 1) FROM cpu) GROUP BY region`,
 		`SELECT percentile(value, 75) FROM cpu`,
 		`SELECT percentile(value, 75.0) FROM cpu`,
+		`SELECT approx_percentile(value, 99) FROM cpu`,
+		`SELECT approx_percentile(value, 99.9) FROM cpu WHERE time >= now() - 1h GROUP BY time(1m)`,
 		`SELECT sample(value, 2) FROM cpu`,
 		`SELECT sample(*, 2) FROM cpu`,
 		`SELECT sample(/val/, 2) FROM cpu`,
@@ -223,6 +225,10 @@ func TestCompile_Failures(t *testing.T) {
 		{s: `SELECT percentile(field1) FROM myseries`, err: `invalid number of arguments for percentile, expected 2, got 1`},
 		{s: `SELECT percentile(field1, foo) FROM myseries`, err: `expected float argument in percentile()`},
 		{s: `SELECT percentile(max(field1), 75) FROM myseries`, err: `expected field argument in percentile()`},
+		{s: `SELECT approx_percentile(field1) FROM myseries`, err: `invalid number of arguments for approx_percentile, expected 2, got 1`},
+		{s: `SELECT approx_percentile(field1, foo) FROM myseries`, err: `expected float argument in approx_percentile()`},
+		{s: `SELECT approx_percentile(field1, 101) FROM myseries`, err: `approx_percentile() percentile must be between 0 and 100, got 101`},
+		{s: `SELECT approx_percentile(field1, 99), host FROM myseries`, err: `mixing aggregate and non-aggregate queries is not supported`},
 		{s: `SELECT field1 FROM foo group by time(1s)`, err: `GROUP BY requires at least one aggregate function`},
 		{s: `SELECT field1 FROM foo fill(none)`, err: `fill(none) must be used with a function`},
 		{s: `SELECT field1 FROM foo fill(linear)`, err: `fill(linear) must be used with a function`},
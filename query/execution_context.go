@@ -3,6 +3,7 @@ package query
 import (
 	"context"
 	"sync"
+	"sync/atomic"
 )
 
 // ExecutionContext contains state that the query is currently executing with.
@@ -103,6 +104,11 @@ func (ctx *ExecutionContext) send(result *Result) error {
 // been interrupted or aborted.
 func (ctx *ExecutionContext) Send(result *Result) error {
 	result.StatementID = ctx.statementID
+	if ctx.task != nil {
+		for _, row := range result.Series {
+			atomic.AddInt64(&ctx.task.rowN, int64(len(row.Values)))
+		}
+	}
 	select {
 	case <-ctx.Done():
 		return ctx.Err()
@@ -110,3 +116,13 @@ func (ctx *ExecutionContext) Send(result *Result) error {
 	}
 	return nil
 }
+
+// AddIteratorStats adds the series and points read by a statement to the
+// statistics of the query.
+func (ctx *ExecutionContext) AddIteratorStats(stats IteratorStats) {
+	if ctx.task == nil {
+		return
+	}
+	atomic.AddInt64(&ctx.task.seriesN, int64(stats.SeriesN))
+	atomic.AddInt64(&ctx.task.pointN, int64(stats.PointN))
+}
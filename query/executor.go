@@ -49,6 +49,14 @@ const (
 	statQueryExecutionDuration = "queryDurationNs" // Total (wall) time spent executing queries.
 	statRecoveredPanics        = "recoveredPanics" // Number of panics recovered by Query Executor.
 
+	// Statistics of the queries of each database.
+	statUsageQueries  = "queries"    // Number of queries completed.
+	statUsageErrors   = "errors"     // Number of queries completed with an error.
+	statUsageDuration = "durationNs" // Total (wall) time spent executing queries.
+	statUsageRows     = "rows"       // Number of rows returned.
+	statUsageSeries   = "series"     // Number of series read.
+	statUsagePoints   = "points"     // Number of points read.
+
 	// PanicCrashEnv is the environment variable that, when set, will prevent
 	// the handler from recovering any panics.
 	PanicCrashEnv = "INFLUXDB_PANIC_CRASH"
@@ -201,6 +209,10 @@ type ExecutionOptions struct {
 
 	// AbortCh is a channel that signals when results are no longer desired by the caller.
 	AbortCh <-chan struct{}
+
+	// Timeout limits the time the query may run. It only shortens the
+	// query timeout of the executor.
+	Timeout time.Duration
 }
 
 type (
@@ -264,7 +276,7 @@ type Statistics struct {
 
 // Statistics returns statistics for periodic monitoring.
 func (e *Executor) Statistics(tags map[string]string) []models.Statistic {
-	return []models.Statistic{{
+	statistics := []models.Statistic{{
 		Name: "queryExecutor",
 		Tags: tags,
 		Values: map[string]interface{}{
@@ -275,6 +287,23 @@ func (e *Executor) Statistics(tags map[string]string) []models.Statistic {
 			statRecoveredPanics:        atomic.LoadInt64(&e.stats.RecoveredPanics),
 		},
 	}}
+
+	// Attribute the resources used by queries to their database.
+	for db, u := range e.TaskManager.Usage() {
+		statistics = append(statistics, models.Statistic{
+			Name: "queryUsage",
+			Tags: models.StatisticTags{"database": db}.Merge(tags),
+			Values: map[string]interface{}{
+				statUsageQueries:  u.QueryN,
+				statUsageErrors:   u.ErrorN,
+				statUsageDuration: u.Duration.Nanoseconds(),
+				statUsageRows:     u.RowN,
+				statUsageSeries:   u.SeriesN,
+				statUsagePoints:   u.PointN,
+			},
+		})
+	}
+	return statistics
 }
 
 // Close kills all running queries and prevents new queries from being attached.
@@ -476,6 +505,12 @@ type Task struct {
 	monitorCh chan error
 	err       error
 	mu        sync.Mutex
+
+	// The number of rows returned, and series and points read, by the
+	// statements of the query.
+	rowN    int64
+	seriesN int64
+	pointN  int64
 }
 
 // Monitor starts a new goroutine that will monitor a query. The function
@@ -7,6 +7,7 @@ import (
 	"testing"
 	"time"
 
+	"github.com/influxdata/influxdb/models"
 	"github.com/influxdata/influxdb/query"
 	"github.com/influxdata/influxql"
 )
@@ -348,6 +349,74 @@ func TestQueryExecutor_ShowQueries(t *testing.T) {
 	}
 }
 
+func TestQueryExecutor_QueryHistory(t *testing.T) {
+	e := NewQueryExecutor()
+	e.TaskManager.QueryHistorySize = 2
+	e.StatementExecutor = &StatementExecutor{
+		ExecuteStatementFn: func(stmt influxql.Statement, ctx *query.ExecutionContext) error {
+			ctx.AddIteratorStats(query.IteratorStats{SeriesN: 1, PointN: 3})
+			return ctx.Send(&query.Result{
+				Series: models.Rows{{Name: "cpu", Columns: []string{"time", "value"}, Values: [][]interface{}{{0, 1.0}, {1, 2.0}}}},
+			})
+		},
+	}
+
+	for _, s := range []string{`SELECT value FROM cpu`, `SELECT value FROM mem`, `SELECT value FROM disk`} {
+		q, err := influxql.ParseQuery(s)
+		if err != nil {
+			t.Fatal(err)
+		}
+		discardOutput(e.ExecuteQuery(q, query.ExecutionOptions{Database: "db0"}, nil))
+	}
+
+	// Only the most recent queries are kept.
+	history := e.TaskManager.QueryHistory()
+	if len(history) != 2 {
+		t.Fatalf("expected %d queries, got %d", 2, len(history))
+	}
+	for i, exp := range []string{`SELECT value FROM mem`, `SELECT value FROM disk`} {
+		if got := history[i].Query; got != exp {
+			t.Errorf("unexpected query: got %q, exp %q", got, exp)
+		} else if history[i].Database != "db0" || history[i].RowN != 2 || history[i].SeriesN != 1 || history[i].PointN != 3 {
+			t.Errorf("unexpected query statistics: %+v", history[i])
+		}
+	}
+}
+
+func TestQueryExecutor_Usage(t *testing.T) {
+	e := NewQueryExecutor()
+	e.StatementExecutor = &StatementExecutor{
+		ExecuteStatementFn: func(stmt influxql.Statement, ctx *query.ExecutionContext) error {
+			ctx.AddIteratorStats(query.IteratorStats{SeriesN: 1, PointN: 3})
+			return ctx.Send(&query.Result{
+				Series: models.Rows{{Name: "cpu", Columns: []string{"time", "value"}, Values: [][]interface{}{{0, 1.0}, {1, 2.0}}}},
+			})
+		},
+	}
+
+	q, err := influxql.ParseQuery(`SELECT value FROM cpu`)
+	if err != nil {
+		t.Fatal(err)
+	}
+	for _, db := range []string{"db0", "db0", "db1"} {
+		discardOutput(e.ExecuteQuery(q, query.ExecutionOptions{Database: db}, nil))
+	}
+
+	usage := make(map[string]map[string]interface{})
+	for _, s := range e.Statistics(nil) {
+		if s.Name == "queryUsage" {
+			usage[s.Tags["database"]] = s.Values
+		}
+	}
+	for db, exp := range map[string]int64{"db0": 2, "db1": 1} {
+		if v := usage[db]; v == nil {
+			t.Fatalf("missing usage of %s", db)
+		} else if v["queries"] != exp || v["rows"] != 2*exp || v["series"] != exp || v["points"] != 3*exp || v["errors"] != int64(0) {
+			t.Fatalf("unexpected usage of %s: %v", db, v)
+		}
+	}
+}
+
 func TestQueryExecutor_Limit_Timeout(t *testing.T) {
 	q, err := influxql.ParseQuery(`SELECT count(value) FROM cpu`)
 	if err != nil {
@@ -375,6 +444,33 @@ func TestQueryExecutor_Limit_Timeout(t *testing.T) {
 	}
 }
 
+func TestQueryExecutor_Limit_Timeout_PerQuery(t *testing.T) {
+	q, err := influxql.ParseQuery(`SELECT count(value) FROM cpu`)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	e := NewQueryExecutor()
+	e.StatementExecutor = &StatementExecutor{
+		ExecuteStatementFn: func(stmt influxql.Statement, ctx *query.ExecutionContext) error {
+			select {
+			case <-ctx.Done():
+				return ctx.Err()
+			case <-time.After(time.Second):
+				t.Errorf("timeout has not killed the query")
+				return errUnexpected
+			}
+		},
+	}
+	e.TaskManager.QueryTimeout = time.Hour
+
+	results := e.ExecuteQuery(q, query.ExecutionOptions{Timeout: time.Nanosecond}, nil)
+	result := <-results
+	if result.Err == nil || !strings.Contains(result.Err.Error(), "query-timeout") {
+		t.Errorf("unexpected error: %s", result.Err)
+	}
+}
+
 func TestQueryExecutor_Limit_ConcurrentQueries(t *testing.T) {
 	q, err := influxql.ParseQuery(`SELECT count(value) FROM cpu`)
 	if err != nil {
@@ -414,6 +510,51 @@ func TestQueryExecutor_Limit_ConcurrentQueries(t *testing.T) {
 	}
 }
 
+func TestQueryExecutor_Drain(t *testing.T) {
+	q, err := influxql.ParseQuery(`SELECT count(value) FROM cpu`)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	started := make(chan struct{})
+	release := make(chan struct{})
+
+	e := NewQueryExecutor()
+	e.StatementExecutor = &StatementExecutor{
+		ExecuteStatementFn: func(stmt influxql.Statement, ctx *query.ExecutionContext) error {
+			close(started)
+			<-release
+			return ctx.Send(&query.Result{Series: models.Rows{{Name: "cpu"}}})
+		},
+	}
+
+	results := e.ExecuteQuery(q, query.ExecutionOptions{}, nil)
+	<-started
+
+	drained := make(chan int)
+	go func() { drained <- e.TaskManager.Drain(10 * time.Second) }()
+
+	// Queries are rejected once draining starts.
+	time.Sleep(20 * time.Millisecond)
+	if result := <-e.ExecuteQuery(q, query.ExecutionOptions{}, nil); result.Err != query.ErrQueryEngineShutdown {
+		t.Fatalf("unexpected error: %v", result.Err)
+	}
+
+	// The running query is allowed to finish.
+	close(release)
+	if result := <-results; result.Err != nil || len(result.Series) != 1 {
+		t.Fatalf("unexpected result: %v", result)
+	}
+	select {
+	case n := <-drained:
+		if n != 0 {
+			t.Fatalf("unexpected running queries: %d", n)
+		}
+	case <-time.After(5 * time.Second):
+		t.Fatal("timeout waiting for drain")
+	}
+}
+
 func TestQueryExecutor_Close(t *testing.T) {
 	q, err := influxql.ParseQuery(`SELECT count(value) FROM cpu`)
 	if err != nil {
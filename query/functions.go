@@ -12,6 +12,7 @@ import (
 	"github.com/influxdata/influxdb/query/internal/gota"
 	"github.com/influxdata/influxdb/query/neldermead"
 	"github.com/influxdata/influxql"
+	"github.com/influxdata/tdigest"
 )
 
 var hllPrefix = []byte("HLL_")
@@ -79,7 +80,7 @@ func (m FunctionTypeMapper) CallType(name string, args []influxql.DataType) (inf
 
 	// Handle functions implemented by the query engine.
 	switch name {
-	case "median", "integral", "stddev",
+	case "median", "integral", "stddev", "approx_percentile",
 		"derivative", "non_negative_derivative",
 		"moving_average",
 		"exponential_moving_average",
@@ -131,6 +132,59 @@ func (r *FloatMeanReducer) Emit() []FloatPoint {
 	}}
 }
 
+// ApproxPercentileCompression is the compression of the t-digests used by
+// approx_percentile(). Higher values are more accurate and use more memory.
+const ApproxPercentileCompression = 1000
+
+// ApproxPercentileReducer estimates a percentile of the aggregated points with
+// a t-digest, so the points do not need to be held in memory and sorted.
+type ApproxPercentileReducer struct {
+	percentile float64
+	digest     *tdigest.TDigest
+	count      int
+}
+
+// NewApproxPercentileReducer creates a new ApproxPercentileReducer estimating
+// the given percentile, between 0 and 100.
+func NewApproxPercentileReducer(percentile float64) *ApproxPercentileReducer {
+	return &ApproxPercentileReducer{
+		percentile: percentile,
+		digest:     tdigest.NewWithCompression(ApproxPercentileCompression),
+	}
+}
+
+// AggregateFloat aggregates a point into the reducer.
+func (r *ApproxPercentileReducer) AggregateFloat(p *FloatPoint) {
+	if math.IsNaN(p.Value) {
+		return
+	}
+	r.digest.Add(p.Value, 1)
+	r.count++
+}
+
+// AggregateInteger aggregates a point into the reducer.
+func (r *ApproxPercentileReducer) AggregateInteger(p *IntegerPoint) {
+	r.digest.Add(float64(p.Value), 1)
+	r.count++
+}
+
+// AggregateUnsigned aggregates a point into the reducer.
+func (r *ApproxPercentileReducer) AggregateUnsigned(p *UnsignedPoint) {
+	r.digest.Add(float64(p.Value), 1)
+	r.count++
+}
+
+// Emit emits the estimated percentile of the aggregated points as a single point.
+func (r *ApproxPercentileReducer) Emit() []FloatPoint {
+	if r.count == 0 {
+		return nil
+	}
+	return []FloatPoint{{
+		Time:  ZeroTime,
+		Value: r.digest.Quantile(r.percentile / 100),
+	}}
+}
+
 // IntegerMeanReducer calculates the mean of the aggregated points.
 type IntegerMeanReducer struct {
 	sum   int64
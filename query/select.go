@@ -555,6 +555,19 @@ func (b *exprIteratorBuilder) buildCallIterator(ctx context.Context, expr *influ
 				percentile = float64(arg.Val)
 			}
 			return newPercentileIterator(input, opt, percentile)
+		case "approx_percentile":
+			input, err := buildExprIterator(ctx, expr.Args[0].(*influxql.VarRef), b.ic, b.sources, opt, false, false)
+			if err != nil {
+				return nil, err
+			}
+			var percentile float64
+			switch arg := expr.Args[1].(type) {
+			case *influxql.NumberLiteral:
+				percentile = arg.Val
+			case *influxql.IntegerLiteral:
+				percentile = float64(arg.Val)
+			}
+			return newApproxPercentileIterator(input, opt, percentile)
 		default:
 			return nil, fmt.Errorf("unsupported call: %s", expr.Name)
 		}
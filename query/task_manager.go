@@ -6,6 +6,7 @@ import (
 	"encoding/json"
 	"fmt"
 	"sync"
+	"sync/atomic"
 	"time"
 
 	"github.com/influxdata/influxdb/models"
@@ -78,6 +79,10 @@ type TaskManager struct {
 	// Maximum number of concurrent queries.
 	MaxConcurrentQueries int
 
+	// Number of completed queries kept in the query history.
+	// If zero, no query history is kept.
+	QueryHistorySize int
+
 	// Logger to use for all logging.
 	// Defaults to discarding all log output.
 	Logger *zap.Logger
@@ -87,6 +92,13 @@ type TaskManager struct {
 	nextID   uint64
 	mu       sync.RWMutex
 	shutdown bool
+
+	// Ring buffer of recently completed queries.
+	history     []QueryHistoryEntry
+	historyNext int
+
+	// Resources used by the completed queries of each database.
+	usage map[string]*QueryUsage
 }
 
 // NewTaskManager creates a new TaskManager.
@@ -215,7 +227,11 @@ func (t *TaskManager) AttachQuery(q *influxql.Query, opt ExecutionOptions, inter
 	}
 	t.queries[qid] = query
 
-	go t.waitForQuery(qid, query.closing, interrupt, query.monitorCh)
+	timeout := t.QueryTimeout
+	if opt.Timeout > 0 && (timeout == 0 || opt.Timeout < timeout) {
+		timeout = opt.Timeout
+	}
+	go t.waitForQuery(qid, timeout, query.closing, interrupt, query.monitorCh)
 	if t.LogQueriesAfter != 0 {
 		go query.monitor(func(closing <-chan struct{}) error {
 			timer := time.NewTimer(t.LogQueriesAfter)
@@ -260,18 +276,126 @@ func (t *TaskManager) KillQuery(qid uint64) error {
 // killed state, this will also close the related channel.
 func (t *TaskManager) DetachQuery(qid uint64) error {
 	t.mu.Lock()
-	defer t.mu.Unlock()
-
 	query := t.queries[qid]
 	if query == nil {
+		t.mu.Unlock()
 		return fmt.Errorf("no such query id: %d", qid)
 	}
 
+	entry := QueryHistoryEntry{
+		ID:       qid,
+		Query:    query.query,
+		Database: query.database,
+		Start:    query.startTime,
+		Duration: time.Since(query.startTime),
+		RowN:     atomic.LoadInt64(&query.rowN),
+		SeriesN:  atomic.LoadInt64(&query.seriesN),
+		PointN:   atomic.LoadInt64(&query.pointN),
+	}
+	if err := query.Error(); err != nil {
+		entry.Error = err.Error()
+	}
+
 	query.close()
 	delete(t.queries, qid)
+	t.recordQuery(entry)
+	t.recordUsage(entry)
+	t.mu.Unlock()
+
+	if t.LogQueriesAfter != 0 && entry.Duration >= t.LogQueriesAfter {
+		t.Logger.Warn("Slow query completed",
+			zap.Uint64("qid", qid),
+			zap.String("query", entry.Query),
+			zap.String("database", entry.Database),
+			zap.String("duration", prettyTime(entry.Duration).String()),
+			zap.Int64("rows", entry.RowN),
+			zap.Int64("series", entry.SeriesN),
+			zap.Int64("points", entry.PointN))
+	}
 	return nil
 }
 
+// QueryHistoryEntry represents a completed query in the query history.
+type QueryHistoryEntry struct {
+	ID       uint64        `json:"id"`
+	Query    string        `json:"query"`
+	Database string        `json:"database"`
+	Start    time.Time     `json:"start"`
+	Duration time.Duration `json:"duration"`
+	Error    string        `json:"error,omitempty"`
+	RowN     int64         `json:"rows"`
+	SeriesN  int64         `json:"series"`
+	PointN   int64         `json:"points"`
+}
+
+// recordQuery adds a completed query to the query history.
+// It must be called with the lock held.
+func (t *TaskManager) recordQuery(entry QueryHistoryEntry) {
+	if t.QueryHistorySize <= 0 {
+		return
+	}
+	if len(t.history) < t.QueryHistorySize {
+		t.history = append(t.history, entry)
+	} else {
+		t.history[t.historyNext] = entry
+	}
+	t.historyNext = (t.historyNext + 1) % t.QueryHistorySize
+}
+
+// QueryHistory returns the most recently completed queries, oldest first.
+func (t *TaskManager) QueryHistory() []QueryHistoryEntry {
+	t.mu.RLock()
+	defer t.mu.RUnlock()
+
+	history := make([]QueryHistoryEntry, 0, len(t.history))
+	history = append(history, t.history[t.historyNext:]...)
+	return append(history, t.history[:t.historyNext]...)
+}
+
+// QueryUsage is the total of the resources used by completed queries.
+type QueryUsage struct {
+	QueryN   int64
+	ErrorN   int64
+	Duration time.Duration
+	RowN     int64
+	SeriesN  int64
+	PointN   int64
+}
+
+// recordUsage adds a completed query to the usage of its database.
+// It must be called with the lock held.
+func (t *TaskManager) recordUsage(entry QueryHistoryEntry) {
+	if t.usage == nil {
+		t.usage = make(map[string]*QueryUsage)
+	}
+	u := t.usage[entry.Database]
+	if u == nil {
+		u = &QueryUsage{}
+		t.usage[entry.Database] = u
+	}
+
+	u.QueryN++
+	if entry.Error != "" {
+		u.ErrorN++
+	}
+	u.Duration += entry.Duration
+	u.RowN += entry.RowN
+	u.SeriesN += entry.SeriesN
+	u.PointN += entry.PointN
+}
+
+// Usage returns the resources used by the completed queries of each database.
+func (t *TaskManager) Usage() map[string]QueryUsage {
+	t.mu.RLock()
+	defer t.mu.RUnlock()
+
+	usage := make(map[string]QueryUsage, len(t.usage))
+	for db, u := range t.usage {
+		usage[db] = *u
+	}
+	return usage
+}
+
 // QueryInfo represents the information for a query.
 type QueryInfo struct {
 	ID       uint64        `json:"id"`
@@ -300,10 +424,10 @@ func (t *TaskManager) Queries() []QueryInfo {
 	return queries
 }
 
-func (t *TaskManager) waitForQuery(qid uint64, interrupt <-chan struct{}, closing <-chan struct{}, monitorCh <-chan error) {
+func (t *TaskManager) waitForQuery(qid uint64, timeout time.Duration, interrupt <-chan struct{}, closing <-chan struct{}, monitorCh <-chan error) {
 	var timerCh <-chan time.Time
-	if t.QueryTimeout != 0 {
-		timer := time.NewTimer(t.QueryTimeout)
+	if timeout != 0 {
+		timer := time.NewTimer(timeout)
 		timerCh = timer.C
 		defer timer.Stop()
 	}
@@ -323,7 +447,7 @@ func (t *TaskManager) waitForQuery(qid uint64, interrupt <-chan struct{}, closin
 				"query killed for exceeding timeout limit",
 				zap.String("query", t.queries[qid].query),
 				zap.String("database", t.queries[qid].database),
-				zap.String("timeout", prettyTime(t.QueryTimeout).String()),
+				zap.String("timeout", prettyTime(timeout).String()),
 			)
 		}
 		t.queryError(qid, ErrQueryTimeoutLimitExceeded)
@@ -334,6 +458,25 @@ func (t *TaskManager) waitForQuery(qid uint64, interrupt <-chan struct{}, closin
 	t.KillQuery(qid)
 }
 
+// Drain prevents new queries from being attached and waits up to timeout for
+// the running queries to finish. It returns the number of queries still running.
+func (t *TaskManager) Drain(timeout time.Duration) int {
+	t.mu.Lock()
+	t.shutdown = true
+	t.mu.Unlock()
+
+	deadline := time.Now().Add(timeout)
+	for {
+		t.mu.RLock()
+		n := len(t.queries)
+		t.mu.RUnlock()
+		if n == 0 || !time.Now().Before(deadline) {
+			return n
+		}
+		time.Sleep(10 * time.Millisecond)
+	}
+}
+
 // Close kills all running queries and prevents new queries from being attached.
 func (t *TaskManager) Close() error {
 	t.mu.Lock()
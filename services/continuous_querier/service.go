@@ -256,6 +256,10 @@ func (s *Service) runContinuousQueries(req *RunRequest) {
 	dbs := s.MetaClient.Databases()
 	// Loop through all databases executing CQs.
 	for _, db := range dbs {
+		// Soft-deleted databases do not accept the results of their CQs.
+		if db.Deleted() {
+			continue
+		}
 		// TODO: distribute across nodes
 		for _, cq := range db.ContinuousQueries {
 			if !req.matches(&cq) {
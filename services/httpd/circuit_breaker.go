@@ -0,0 +1,89 @@
+package httpd
+
+import (
+	"sync"
+	"time"
+)
+
+// circuitBreakers sheds the requests to a database after consecutive
+// server-side failures, such as failed writes or queries exceeding the query
+// timeout. Once the cooldown has passed, a single probe request is let through.
+// Its success closes the breaker and its failure opens it again.
+type circuitBreakers struct {
+	mu        sync.Mutex
+	threshold int
+	cooldown  time.Duration
+	breakers  map[string]*circuitBreaker // keyed by database
+	openN     uint64
+
+	now func() time.Time
+}
+
+// circuitBreaker is the state of the breaker of a database.
+type circuitBreaker struct {
+	failures   int
+	openedAt   time.Time // zero while the breaker is closed
+	probing    bool
+	generation uint64 // changes each time the breaker opens
+}
+
+// newCircuitBreakers returns breakers opening after threshold consecutive
+// failures and rejecting requests for cooldown.
+func newCircuitBreakers(threshold int, cooldown time.Duration) *circuitBreakers {
+	return &circuitBreakers{
+		threshold: threshold,
+		cooldown:  cooldown,
+		breakers:  make(map[string]*circuitBreaker),
+		now:       time.Now,
+	}
+}
+
+// Allow returns false if a request to database must be rejected. The outcome
+// of an allowed request must be reported with Done, along with the
+// generation of the breaker returned.
+func (c *circuitBreakers) Allow(database string) (uint64, bool) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	b := c.breakers[database]
+	if b == nil {
+		return 0, true
+	} else if b.openedAt.IsZero() {
+		return b.generation, true
+	} else if b.probing || c.now().Sub(b.openedAt) < c.cooldown {
+		return 0, false
+	}
+	b.probing = true
+	return b.generation, true
+}
+
+// Done records whether an allowed request to database failed. The outcome
+// of a request allowed before the breaker last opened is ignored, so it
+// cannot close or count against the breaker of a later generation.
+func (c *circuitBreakers) Done(database string, generation uint64, failed bool) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	b := c.breakers[database]
+	if b == nil && generation != 0 {
+		return
+	} else if b != nil && b.generation != generation {
+		return
+	}
+
+	if !failed {
+		delete(c.breakers, database)
+		return
+	} else if b == nil {
+		b = &circuitBreaker{}
+		c.breakers[database] = b
+	}
+
+	b.failures++
+	if b.probing || b.failures >= c.threshold {
+		c.openN++
+		b.openedAt = c.now()
+		b.probing = false
+		b.generation = c.openN
+	}
+}
@@ -0,0 +1,39 @@
+package httpd
+
+import (
+	"testing"
+	"time"
+)
+
+func TestCircuitBreakers_StaleDone(t *testing.T) {
+	now := time.Unix(0, 0)
+	c := newCircuitBreakers(1, time.Minute)
+	c.now = func() time.Time { return now }
+
+	// A request is allowed while the breaker is closed, then another one
+	// fails and opens it.
+	stale, ok := c.Allow("db0")
+	if !ok {
+		t.Fatal("expected request to be allowed")
+	}
+	generation, _ := c.Allow("db0")
+	c.Done("db0", generation, true)
+
+	// The success of the request allowed before the breaker opened does not
+	// close it.
+	c.Done("db0", stale, false)
+	if _, ok := c.Allow("db0"); ok {
+		t.Fatal("expected request to be shed")
+	}
+
+	// Once the cooldown has passed, the success of the probe closes it.
+	now = now.Add(time.Minute)
+	probe, ok := c.Allow("db0")
+	if !ok {
+		t.Fatal("expected probe to be allowed")
+	}
+	c.Done("db0", probe, false)
+	if _, ok := c.Allow("db0"); !ok {
+		t.Fatal("expected request to be allowed")
+	}
+}
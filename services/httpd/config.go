@@ -28,6 +28,22 @@ const (
 
 	// DefaultEnqueuedWriteTimeout is the maximum time a write request can wait to be processed.
 	DefaultEnqueuedWriteTimeout = 30 * time.Second
+
+	// DefaultCircuitBreakerCooldown is the default time requests to a database
+	// are rejected for once its circuit breaker opens.
+	DefaultCircuitBreakerCooldown = 30 * time.Second
+
+	// DefaultWriteIdempotencyMaxKeys is the default maximum number of write
+	// idempotency keys remembered at once.
+	DefaultWriteIdempotencyMaxKeys = 100000
+
+	// DefaultRejectedWritesMaxBodySize is the default maximum size of the body
+	// of a write recorded in the rejected writes log, in bytes.
+	DefaultRejectedWritesMaxBodySize = 64 * 1024
+
+	// DefaultRejectedWritesMaxSize is the default size of the rejected writes
+	// log it is rotated at, in bytes.
+	DefaultRejectedWritesMaxSize = 100 * 1024 * 1024
 )
 
 // Config represents a configuration for a HTTP service.
@@ -38,6 +54,7 @@ type Config struct {
 	LogEnabled              bool              `toml:"log-enabled"`
 	SuppressWriteLog        bool              `toml:"suppress-write-log"`
 	WriteTracing            bool              `toml:"write-tracing"`
+	WriteTimingHeader       bool              `toml:"write-timing-header"`
 	FluxEnabled             bool              `toml:"flux-enabled"`
 	FluxLogEnabled          bool              `toml:"flux-log-enabled"`
 	FluxTesting             bool              `toml:"flux-testing"`
@@ -51,6 +68,8 @@ type Config struct {
 	HTTPSCertificate        string            `toml:"https-certificate"`
 	HTTPSPrivateKey         string            `toml:"https-private-key"`
 	MaxRowLimit             int               `toml:"max-row-limit"`
+	MaxChunkSize            int               `toml:"max-chunk-size"`
+	QueryKeepAliveInterval  toml.Duration     `toml:"query-keep-alive-interval"`
 	MaxConnectionLimit      int               `toml:"max-connection-limit"`
 	SharedSecret            string            `toml:"shared-secret"`
 	Realm                   string            `toml:"realm"`
@@ -59,37 +78,50 @@ type Config struct {
 	UnixSocketPermissions   toml.FileMode     `toml:"unix-socket-permissions"`
 	BindSocket              string            `toml:"bind-socket"`
 	MaxBodySize             int               `toml:"max-body-size"`
+	SoftMaxBodySize         int               `toml:"soft-max-body-size"`
+	SoftMaxColumnsPerPoint  int               `toml:"soft-max-columns-per-point"`
 	AccessLogPath           string            `toml:"access-log-path"`
 	AccessLogStatusFilters  []StatusFilter    `toml:"access-log-status-filters"`
 	MaxConcurrentWriteLimit int               `toml:"max-concurrent-write-limit"`
 	MaxEnqueuedWriteLimit   int               `toml:"max-enqueued-write-limit"`
 	EnqueuedWriteTimeout    time.Duration     `toml:"enqueued-write-timeout"`
+	WriteIdempotencyWindow  toml.Duration     `toml:"write-idempotency-window"`
+	WriteIdempotencyMaxKeys int               `toml:"write-idempotency-max-keys"`
+	CircuitBreakerFailures  int               `toml:"circuit-breaker-failures"`
+	CircuitBreakerCooldown  toml.Duration     `toml:"circuit-breaker-cooldown"`
+	RejectedWritesPath      string            `toml:"rejected-writes-path"`
+	RejectedWritesMaxBody   int               `toml:"rejected-writes-max-body-size"`
+	RejectedWritesMaxSize   int64             `toml:"rejected-writes-max-size"`
 	TLS                     *tls.Config       `toml:"-"`
 }
 
 // NewConfig returns a new Config with default settings.
 func NewConfig() Config {
 	return Config{
-		Enabled:               true,
-		FluxEnabled:           false,
-		FluxLogEnabled:        false,
-		FluxTesting:           false,
-		BindAddress:           DefaultBindAddress,
-		LogEnabled:            true,
-		PprofEnabled:          true,
-		PprofAuthEnabled:      false,
-		DebugPprofEnabled:     false,
-		PingAuthEnabled:       false,
-		PromReadAuthEnabled:   false,
-		HTTPSEnabled:          false,
-		HTTPSCertificate:      "/etc/ssl/influxdb.pem",
-		MaxRowLimit:           0,
-		Realm:                 DefaultRealm,
-		UnixSocketEnabled:     false,
-		UnixSocketPermissions: 0777,
-		BindSocket:            DefaultBindSocket,
-		MaxBodySize:           DefaultMaxBodySize,
-		EnqueuedWriteTimeout:  DefaultEnqueuedWriteTimeout,
+		Enabled:                 true,
+		FluxEnabled:             false,
+		FluxLogEnabled:          false,
+		FluxTesting:             false,
+		BindAddress:             DefaultBindAddress,
+		LogEnabled:              true,
+		PprofEnabled:            true,
+		PprofAuthEnabled:        false,
+		DebugPprofEnabled:       false,
+		PingAuthEnabled:         false,
+		PromReadAuthEnabled:     false,
+		HTTPSEnabled:            false,
+		HTTPSCertificate:        "/etc/ssl/influxdb.pem",
+		MaxRowLimit:             0,
+		Realm:                   DefaultRealm,
+		UnixSocketEnabled:       false,
+		UnixSocketPermissions:   0777,
+		BindSocket:              DefaultBindSocket,
+		MaxBodySize:             DefaultMaxBodySize,
+		EnqueuedWriteTimeout:    DefaultEnqueuedWriteTimeout,
+		CircuitBreakerCooldown:  toml.Duration(DefaultCircuitBreakerCooldown),
+		WriteIdempotencyMaxKeys: DefaultWriteIdempotencyMaxKeys,
+		RejectedWritesMaxBody:   DefaultRejectedWritesMaxBodySize,
+		RejectedWritesMaxSize:   DefaultRejectedWritesMaxSize,
 	}
 }
 
@@ -106,9 +138,20 @@ func (c Config) Diagnostics() (*diagnostics.Diagnostics, error) {
 		"bind-address":         c.BindAddress,
 		"https-enabled":        c.HTTPSEnabled,
 		"max-row-limit":        c.MaxRowLimit,
+		"max-chunk-size":       c.MaxChunkSize,
 		"max-connection-limit": c.MaxConnectionLimit,
 		"access-log-path":      c.AccessLogPath,
 		"flux-enabled":         c.FluxEnabled,
+
+		"query-keep-alive-interval":  c.QueryKeepAliveInterval,
+		"write-idempotency-window":   c.WriteIdempotencyWindow,
+		"write-idempotency-max-keys": c.WriteIdempotencyMaxKeys,
+		"rejected-writes-path":       c.RejectedWritesPath,
+		"circuit-breaker-failures":   c.CircuitBreakerFailures,
+		"circuit-breaker-cooldown":   c.CircuitBreakerCooldown,
+		"write-timing-header":        c.WriteTimingHeader,
+		"soft-max-body-size":         c.SoftMaxBodySize,
+		"soft-max-columns-per-point": c.SoftMaxColumnsPerPoint,
 	}), nil
 }
 
@@ -4,10 +4,13 @@ import (
 	"bytes"
 	"compress/gzip"
 	"context"
+	"crypto/md5"
+	"encoding/base64"
 	"encoding/json"
 	"errors"
 	"expvar"
 	"fmt"
+	"hash"
 	"io"
 	"log"
 	"math"
@@ -56,6 +59,14 @@ const (
 	DefaultDebugRequestsInterval = 10 * time.Second
 
 	MaxDebugRequestsInterval = 6 * time.Hour
+
+	// WriteTimingHeader is the response header reporting the time spent in
+	// each stage of a write request when write-timing-header is enabled.
+	WriteTimingHeader = "X-Influxdb-Write-Timing"
+
+	// WriteWarningHeader is the response header carrying a warning for each
+	// soft limit exceeded by a write request.
+	WriteWarningHeader = "X-Influxdb-Warning"
 )
 
 // AuthenticationMethod defines the type of authentication used.
@@ -116,6 +127,7 @@ type Handler struct {
 		DropRetentionPolicy(database, name string) error
 		CreateRetentionPolicy(database string, spec *meta.RetentionPolicySpec, makeDefault bool) (*meta.RetentionPolicyInfo, error)
 		UpdateRetentionPolicy(database, name string, rpu *meta.RetentionPolicyUpdate, makeDefault bool) error
+		RestoreDatabase(name string) error
 	}
 
 	QueryAuthorizer QueryAuthorizer
@@ -150,6 +162,10 @@ type Handler struct {
 
 	requestTracker *RequestTracker
 	writeThrottler *Throttler
+	writeKeys      *writeKeyCache
+	rejectedWrites *rejectedWriteLog
+	queryBreakers  *circuitBreakers
+	writeBreakers  *circuitBreakers
 }
 
 // NewHandler returns a new instance of handler with routes.
@@ -167,6 +183,18 @@ func NewHandler(c Config) *Handler {
 	h.writeThrottler = NewThrottler(c.MaxConcurrentWriteLimit, c.MaxEnqueuedWriteLimit)
 	h.writeThrottler.EnqueueTimeout = c.EnqueuedWriteTimeout
 
+	// Remember idempotency keys of acknowledged writes if enabled.
+	if c.WriteIdempotencyWindow > 0 {
+		h.writeKeys = newWriteKeyCache(time.Duration(c.WriteIdempotencyWindow), c.WriteIdempotencyMaxKeys)
+	}
+
+	// Shed requests to databases that keep failing if enabled. Queries and
+	// writes have their own breakers so failures of one do not shed the other.
+	if c.CircuitBreakerFailures > 0 {
+		h.queryBreakers = newCircuitBreakers(c.CircuitBreakerFailures, time.Duration(c.CircuitBreakerCooldown))
+		h.writeBreakers = newCircuitBreakers(c.CircuitBreakerFailures, time.Duration(c.CircuitBreakerCooldown))
+	}
+
 	// Disable the write log if they have been suppressed.
 	writeLogEnabled := c.LogEnabled
 	if c.SuppressWriteLog {
@@ -231,6 +259,18 @@ func NewHandler(c Config) *Handler {
 			"update-bucket",
 			"PATCH", "/api/v2/buckets/:dbrp", false, true, h.serveUpdateBucketV2,
 		},
+		Route{
+			"list-orgs",
+			"GET", "/api/v2/orgs", false, true, h.serveListOrgsV2,
+		},
+		Route{
+			"retrieve-org",
+			"GET", "/api/v2/orgs/:orgID", false, true, h.serveRetrieveOrgV2,
+		},
+		Route{
+			"restore-database",
+			"POST", "/api/v1/databases/:db/restore", false, true, h.serveRestoreDatabase,
+		},
 		Route{
 			"bucket-labels",
 			"GET", "/api/v2/buckets/:dbrp/labels", false, true, h.serveLabelsNotAllowedV2,
@@ -311,6 +351,14 @@ func NewHandler(c Config) *Handler {
 			"prometheus-metrics",
 			"GET", "/metrics", false, true, authWrapper(promhttp.Handler().ServeHTTP),
 		},
+		Route{ // Completed queries of all users, for admins only.
+			"debug-queries",
+			"GET", "/debug/queries", true, true, h.adminOnly(h.serveDebugQueries),
+		},
+		Route{
+			"debug-memory",
+			"GET", "/debug/memory", true, true, h.adminOnly(h.serveDebugMemory),
+		},
 	}...)
 
 	// When PprofAuthEnabled is enabled, create debug/pprof endpoints with the
@@ -375,6 +423,16 @@ func NewHandler(c Config) *Handler {
 }
 
 func (h *Handler) Open() {
+	if h.Config.RejectedWritesPath != "" {
+		l, err := openRejectedWriteLog(h.Config.RejectedWritesPath, h.Config.RejectedWritesMaxBody, h.Config.RejectedWritesMaxSize)
+		if err != nil {
+			h.Logger.Error("unable to open rejected writes log", zap.Error(err), zap.String("path", h.Config.RejectedWritesPath))
+		} else {
+			h.rejectedWrites = l
+			h.Logger.Info("opened rejected writes log", zap.String("path", h.Config.RejectedWritesPath))
+		}
+	}
+
 	if h.Config.LogEnabled {
 		path := "stderr"
 
@@ -407,6 +465,11 @@ func (h *Handler) Close() {
 		h.accessLog = nil
 		h.accessLogFilters = nil
 	}
+
+	if h.rejectedWrites != nil {
+		h.rejectedWrites.Close()
+		h.rejectedWrites = nil
+	}
 }
 
 // Statistics maintains statistics for the httpd service.
@@ -435,6 +498,12 @@ type Statistics struct {
 	PromReadRequests             int64
 	FluxQueryRequests            int64
 	FluxQueryRequestDuration     int64
+	WriteRequestsDeduplicated    int64
+	RequestsShed                 int64
+	WriteRequestReadDuration     int64
+	WriteRequestParseDuration    int64
+	WriteRequestWriteDuration    int64
+	WriteRequestsSoftLimited     int64
 }
 
 // Statistics returns statistics for periodic monitoring.
@@ -466,6 +535,12 @@ func (h *Handler) Statistics(tags map[string]string) []models.Statistic {
 			statPromReadRequest:              atomic.LoadInt64(&h.stats.PromReadRequests),
 			statFluxQueryRequests:            atomic.LoadInt64(&h.stats.FluxQueryRequests),
 			statFluxQueryRequestDuration:     atomic.LoadInt64(&h.stats.FluxQueryRequestDuration),
+			statWriteRequestsDeduplicated:    atomic.LoadInt64(&h.stats.WriteRequestsDeduplicated),
+			statRequestsShed:                 atomic.LoadInt64(&h.stats.RequestsShed),
+			statWriteRequestReadDuration:     atomic.LoadInt64(&h.stats.WriteRequestReadDuration),
+			statWriteRequestParseDuration:    atomic.LoadInt64(&h.stats.WriteRequestParseDuration),
+			statWriteRequestWriteDuration:    atomic.LoadInt64(&h.stats.WriteRequestWriteDuration),
+			statWriteRequestsSoftLimited:     atomic.LoadInt64(&h.stats.WriteRequestsSoftLimited),
 		},
 	}}
 }
@@ -663,11 +738,25 @@ func (h *Handler) serveQuery(w http.ResponseWriter, r *http.Request, user meta.U
 		if n, err := strconv.ParseInt(r.FormValue("chunk_size"), 10, 64); err == nil && int(n) > 0 {
 			chunkSize = int(n)
 		}
+		if h.Config.MaxChunkSize > 0 && chunkSize > h.Config.MaxChunkSize {
+			chunkSize = h.Config.MaxChunkSize
+		}
 	}
 
 	// Parse whether this is an async command.
 	async := r.FormValue("async") == "true"
 
+	// Parse the time the client allows the query to run for.
+	var timeout time.Duration
+	if s := r.FormValue("timeout"); s != "" {
+		d, err := time.ParseDuration(s)
+		if err != nil || d <= 0 {
+			h.httpError(rw, fmt.Sprintf("invalid timeout: %q", s), http.StatusBadRequest)
+			return
+		}
+		timeout = d
+	}
+
 	opts := query.ExecutionOptions{
 		Database:        db,
 		RetentionPolicy: r.FormValue("rp"),
@@ -675,6 +764,7 @@ func (h *Handler) serveQuery(w http.ResponseWriter, r *http.Request, user meta.U
 		ReadOnly:        r.Method == "GET",
 		NodeID:          nodeID,
 		Authorizer:      fineAuthorizer,
+		Timeout:         timeout,
 	}
 
 	if h.Config.AuthEnabled {
@@ -714,6 +804,21 @@ func (h *Handler) serveQuery(w http.ResponseWriter, r *http.Request, user meta.U
 		}
 	}
 
+	// Shed queries to a database whose queries keep timing out. A timeout is
+	// the client's own, and not counted, when its timeout is the one applied.
+	var queryFailed bool
+	serverTimeout := h.QueryExecutor.TaskManager.QueryTimeout
+	clientTimeout := timeout > 0 && (serverTimeout == 0 || timeout < serverTimeout)
+	if h.queryBreakers != nil && db != "" && !async {
+		generation, ok := h.queryBreakers.Allow(db)
+		if !ok {
+			atomic.AddInt64(&h.stats.RequestsShed, 1)
+			h.httpError(rw, fmt.Sprintf("circuit breaker open for database %q", db), http.StatusServiceUnavailable)
+			return
+		}
+		defer func() { h.queryBreakers.Done(db, generation, queryFailed) }()
+	}
+
 	// Execute query.
 	results := h.QueryExecutor.ExecuteQuery(q, opts, closing)
 
@@ -736,12 +841,38 @@ func (h *Handler) serveQuery(w http.ResponseWriter, r *http.Request, user meta.U
 		w.Flush()
 	}
 
+	// Send empty lines while waiting for the next chunk of a JSON response so
+	// clients and proxies do not close the connection of a slow query.
+	var keepAlive <-chan time.Time
+	if chunked && h.Config.QueryKeepAliveInterval > 0 && rw.Header().Get("Content-Type") == "application/json" {
+		ticker := time.NewTicker(time.Duration(h.Config.QueryKeepAliveInterval))
+		defer ticker.Stop()
+		keepAlive = ticker.C
+	}
+
 	// pull all results from the channel
 	rows := 0
-	for r := range results {
+RESULTS:
+	for {
+		var r *query.Result
+		select {
+		case res, ok := <-results:
+			if !ok {
+				break RESULTS
+			}
+			r = res
+		case <-keepAlive:
+			n, _ := rw.Write([]byte("\n"))
+			atomic.AddInt64(&h.stats.QueryRequestBytesTransmitted, int64(n))
+			w.(http.Flusher).Flush()
+			continue
+		}
+
 		// Ignore nil results.
 		if r == nil {
 			continue
+		} else if r.Err == query.ErrQueryTimeoutLimitExceeded && !clientTimeout {
+			queryFailed = true
 		}
 
 		// if requested, convert result timestamps to epoch
@@ -1243,6 +1374,30 @@ func (h *Handler) serveDeleteBucketV2(w http.ResponseWriter, r *http.Request, us
 	}
 }
 
+// serveRestoreDatabase restores a soft-deleted database.
+func (h *Handler) serveRestoreDatabase(w http.ResponseWriter, r *http.Request, user meta.User) {
+	db := r.URL.Query().Get(":db")
+	if h.Config.AuthEnabled {
+		if user == nil {
+			h.httpError(w, fmt.Sprintf("restore database - user is required to restore %q", db), http.StatusForbidden)
+			return
+		}
+		if err := h.QueryAuthorizer.AuthorizeCreateDatabase(user); err != nil {
+			h.httpError(w, fmt.Sprintf("restore database - %q is not authorized to restore %q: %s", user.ID(), db, err.Error()), http.StatusForbidden)
+			return
+		}
+	}
+
+	if dbi := h.MetaClient.Database(db); dbi == nil {
+		h.httpError(w, fmt.Sprintf("restore database - not found: %q", db), http.StatusNotFound)
+		return
+	} else if err := h.MetaClient.RestoreDatabase(db); err != nil {
+		h.httpError(w, fmt.Sprintf("restore database %q: %s", db, err.Error()), http.StatusBadRequest)
+		return
+	}
+	w.WriteHeader(http.StatusNoContent)
+}
+
 func (h *Handler) serveUpdateBucketV2(w http.ResponseWriter, r *http.Request, user meta.User) {
 	var bs []byte
 	id := r.URL.Query().Get(":dbrp")
@@ -1533,6 +1688,7 @@ func makeBucket(rpi *meta.RetentionPolicyInfo, database string) *Bucket {
 					ShardGroupDurationSeconds: int64(rpi.ShardGroupDuration.Seconds()),
 				}},
 			},
+			OrgID:      DefaultOrgID,
 			Rp:         rpi.Name,
 			SchemaType: "implicit",
 		},
@@ -1556,6 +1712,66 @@ func (h *Handler) serveBucketOwnersNotAllowedV2(w http.ResponseWriter, r *http.R
 	return
 }
 
+// DefaultOrgID is the ID of the single organization of the 2.0 API, which
+// owns every bucket.
+const DefaultOrgID = "0000000000000001"
+
+// Org should match the 2.0 API definition of an organization.
+type Org struct {
+	ID          string `json:"id"`
+	Name        string `json:"name"`
+	Description string `json:"description"`
+}
+
+type Orgs struct {
+	Orgs []Org `json:"orgs"`
+}
+
+// defaultOrg is the single organization of the 2.0 API. Organizations are
+// not supported in this version, so the org and orgID parameters of the 2.0
+// API are ignored and every lookup returns this one.
+var defaultOrg = Org{
+	ID:          DefaultOrgID,
+	Name:        "default",
+	Description: "Organization owning every bucket",
+}
+
+func (h *Handler) serveListOrgsV2(w http.ResponseWriter, r *http.Request, user meta.User) {
+	if h.Config.AuthEnabled && user == nil {
+		h.httpError(w, "list orgs - user is required", http.StatusForbidden)
+		return
+	}
+
+	b, err := json.Marshal(Orgs{Orgs: []Org{defaultOrg}})
+	if err != nil {
+		h.httpError(w, fmt.Sprintf("list orgs marshaling error: %s", err.Error()), http.StatusInternalServerError)
+		return
+	}
+	if _, err := w.Write(b); err != nil {
+		h.Logger.Info("/api/v2/orgs: list orgs error writing response", zap.Error(err))
+	}
+}
+
+func (h *Handler) serveRetrieveOrgV2(w http.ResponseWriter, r *http.Request, user meta.User) {
+	if h.Config.AuthEnabled && user == nil {
+		h.httpError(w, "retrieve org - user is required", http.StatusForbidden)
+		return
+	}
+
+	if id := r.URL.Query().Get(":orgID"); id != DefaultOrgID {
+		h.httpError(w, fmt.Sprintf("org not found: %q", id), http.StatusNotFound)
+		return
+	}
+	b, err := json.Marshal(defaultOrg)
+	if err != nil {
+		h.httpError(w, fmt.Sprintf("retrieve org marshaling error: %s", err.Error()), http.StatusInternalServerError)
+		return
+	}
+	if _, err := w.Write(b); err != nil {
+		h.Logger.Info("/api/v2/orgs: retrieve org error writing response", zap.Error(err))
+	}
+}
+
 // serveWriteV2 maps v2 write parameters to a v1 style handler.  the concepts
 // of a "bucket" is mapped to v1 "database" and "retention
 // policies".
@@ -1633,6 +1849,54 @@ func (h *Handler) serveWrite(database, retentionPolicy, precision string, w http
 		}
 	}
 
+	// Shed writes to a database whose writes keep failing.
+	var writeFailed bool
+	if h.writeBreakers != nil {
+		generation, ok := h.writeBreakers.Allow(database)
+		if !ok {
+			atomic.AddInt64(&h.stats.RequestsShed, 1)
+			h.httpError(w, fmt.Sprintf("circuit breaker open for database %q", database), http.StatusServiceUnavailable)
+			return
+		}
+		defer func() { h.writeBreakers.Done(database, generation, writeFailed) }()
+	}
+
+	// Acknowledge retries of an already acknowledged write without applying them again.
+	var idempotencyKey string
+	if key := r.Header.Get(IdempotencyKeyHeader); key != "" && h.writeKeys != nil {
+		key = writeKey(database, retentionPolicy, user, key)
+		if ok, err := h.writeKeys.Begin(key); err == errTooManyWriteKeys {
+			h.httpError(w, err.Error(), http.StatusServiceUnavailable)
+			return
+		} else if err != nil {
+			h.httpError(w, err.Error(), http.StatusConflict)
+			return
+		} else if !ok {
+			atomic.AddInt64(&h.stats.WriteRequestsDeduplicated, 1)
+			h.writeHeader(w, http.StatusNoContent)
+			return
+		}
+
+		// Release the key unless the write is acknowledged.
+		idempotencyKey = key
+		defer func() {
+			if idempotencyKey != "" {
+				h.writeKeys.Abort(idempotencyKey)
+			}
+		}()
+	}
+
+	// Checksum the body as sent if the client provided its checksum.
+	contentMD5 := r.Header.Get("Content-MD5")
+	var checksum hash.Hash
+	if contentMD5 != "" {
+		checksum = md5.New()
+		r.Body = struct {
+			io.Reader
+			io.Closer
+		}{io.TeeReader(r.Body, checksum), r.Body}
+	}
+
 	body := r.Body
 	if h.Config.MaxBodySize > 0 {
 		body = truncateReader(body, int64(h.Config.MaxBodySize))
@@ -1662,7 +1926,9 @@ func (h *Handler) serveWrite(database, retentionPolicy, precision string, w http
 	}
 	buf := bytes.NewBuffer(bs)
 
+	readStart := time.Now()
 	_, err := buf.ReadFrom(body)
+	timing := writeTiming{read: time.Since(readStart)}
 	if err != nil {
 		if err == errTruncated {
 			h.httpError(w, http.StatusText(http.StatusRequestEntityTooLarge), http.StatusRequestEntityTooLarge)
@@ -1675,23 +1941,44 @@ func (h *Handler) serveWrite(database, retentionPolicy, precision string, w http
 		h.httpError(w, err.Error(), http.StatusBadRequest)
 		return
 	}
+	if checksum != nil {
+		if exp, err := base64.StdEncoding.DecodeString(contentMD5); err != nil || !bytes.Equal(exp, checksum.Sum(nil)) {
+			h.httpError(w, "body does not match its Content-MD5 header", http.StatusBadRequest)
+			return
+		}
+	}
 	atomic.AddInt64(&h.stats.WriteRequestBytesReceived, int64(buf.Len()))
 
 	if h.Config.WriteTracing {
 		h.Logger.Info("Write body received by handler", zap.ByteString("body", buf.Bytes()))
 	}
 
-	points, parseError := models.ParsePointsWithPrecision(buf.Bytes(), time.Now().UTC(), precision)
+	parseStart := time.Now()
+	points, parseError := models.ParsePointsWithPrecision(buf.Bytes(), parseStart.UTC(), precision)
+	timing.parse = time.Since(parseStart)
 	// Not points parsed correctly so return the error now
 	if parseError != nil && len(points) == 0 {
+		h.recordWriteTiming(w, timing)
 		if parseError.Error() == "EOF" {
 			h.writeHeader(w, http.StatusOK)
 			return
 		}
+		h.logRejectedWrite(database, retentionPolicy, user, parseError.Error(), buf.Bytes())
 		h.httpError(w, parseError.Error(), http.StatusBadRequest)
 		return
 	}
 
+	// Writes exceeding soft limits are written with a warning.
+	if warnings := h.softLimitWarnings(buf.Len(), points); len(warnings) > 0 {
+		atomic.AddInt64(&h.stats.WriteRequestsSoftLimited, 1)
+		for _, warning := range warnings {
+			w.Header().Add(WriteWarningHeader, warning)
+		}
+		h.Logger.Info("Write request exceeds soft limits",
+			logger.Database(database),
+			zap.Strings("warnings", warnings))
+	}
+
 	// Determine required consistency level.
 	level := r.URL.Query().Get("consistency")
 	consistency := models.ConsistencyLevelOne
@@ -1705,22 +1992,34 @@ func (h *Handler) serveWrite(database, retentionPolicy, precision string, w http
 	}
 
 	// Write points.
-	if err := h.PointsWriter.WritePoints(database, retentionPolicy, consistency, user, points); influxdb.IsClientError(err) {
+	writeStart := time.Now()
+	err = h.PointsWriter.WritePoints(database, retentionPolicy, consistency, user, points)
+	timing.write = time.Since(writeStart)
+	h.recordWriteTiming(w, timing)
+
+	if influxdb.IsClientError(err) {
 		atomic.AddInt64(&h.stats.PointsWrittenFail, int64(len(points)))
+		h.logRejectedWrite(database, retentionPolicy, user, err.Error(), buf.Bytes())
 		h.httpError(w, err.Error(), http.StatusBadRequest)
 		return
-	} else if influxdb.IsAuthorizationError(err) {
+	} else if influxdb.IsAuthorizationError(err) || influxdb.IsWriteFrozenError(err) {
 		atomic.AddInt64(&h.stats.PointsWrittenFail, int64(len(points)))
 		h.httpError(w, err.Error(), http.StatusForbidden)
 		return
+	} else if influxdb.IsDatabaseDeletedError(err) {
+		atomic.AddInt64(&h.stats.PointsWrittenFail, int64(len(points)))
+		h.httpError(w, err.Error(), http.StatusNotFound)
+		return
 	} else if werr, ok := err.(tsdb.PartialWriteError); ok {
 		// Note - we don't always collect all the errors before returning from the call,
 		// so PointsWrittenOK might overestimate the number of successful points if multiple shards have errors
 		atomic.AddInt64(&h.stats.PointsWrittenOK, int64(len(points)-werr.Dropped))
 		atomic.AddInt64(&h.stats.PointsWrittenDropped, int64(werr.Dropped))
+		h.logRejectedWrite(database, retentionPolicy, user, werr.Error(), buf.Bytes())
 		h.httpError(w, werr.Error(), http.StatusBadRequest)
 		return
 	} else if err != nil {
+		writeFailed = true
 		atomic.AddInt64(&h.stats.PointsWrittenFail, int64(len(points)))
 		h.httpError(w, err.Error(), http.StatusInternalServerError)
 		return
@@ -1729,14 +2028,82 @@ func (h *Handler) serveWrite(database, retentionPolicy, precision string, w http
 		atomic.AddInt64(&h.stats.PointsWrittenOK, int64(len(points)))
 		// The other points failed to parse which means the client sent invalid line protocol.  We return a 400
 		// response code as well as the lines that failed to parse.
+		h.logRejectedWrite(database, retentionPolicy, user, parseError.Error(), buf.Bytes())
 		h.httpError(w, tsdb.PartialWriteError{Reason: parseError.Error()}.Error(), http.StatusBadRequest)
 		return
 	}
 
 	atomic.AddInt64(&h.stats.PointsWrittenOK, int64(len(points)))
+	if idempotencyKey != "" {
+		h.writeKeys.Commit(idempotencyKey)
+		idempotencyKey = ""
+	}
 	h.writeHeader(w, http.StatusNoContent)
 }
 
+// writeTiming is the time spent in each stage of a write request.
+type writeTiming struct {
+	read  time.Duration
+	parse time.Duration
+	write time.Duration
+}
+
+// recordWriteTiming adds the time spent in each stage of a write request to
+// the statistics and, if enabled, reports them in a response header.
+func (h *Handler) recordWriteTiming(w http.ResponseWriter, t writeTiming) {
+	atomic.AddInt64(&h.stats.WriteRequestReadDuration, t.read.Nanoseconds())
+	atomic.AddInt64(&h.stats.WriteRequestParseDuration, t.parse.Nanoseconds())
+	atomic.AddInt64(&h.stats.WriteRequestWriteDuration, t.write.Nanoseconds())
+
+	if h.Config.WriteTimingHeader {
+		w.Header().Set(WriteTimingHeader, fmt.Sprintf("read=%s, parse=%s, write=%s", t.read, t.parse, t.write))
+	}
+}
+
+// softLimitWarnings returns a warning for each soft limit exceeded by a write
+// request of n bytes with points.
+func (h *Handler) softLimitWarnings(n int, points []models.Point) []string {
+	var warnings []string
+	if max := h.Config.SoftMaxBodySize; max > 0 && n > max {
+		warnings = append(warnings, fmt.Sprintf("body size %d exceeds soft limit %d", n, max))
+	}
+	if max := h.Config.SoftMaxColumnsPerPoint; max > 0 {
+		for _, p := range points {
+			columns := len(p.Tags())
+			for iter := p.FieldIterator(); iter.Next(); {
+				columns++
+			}
+			if columns > max {
+				warnings = append(warnings, fmt.Sprintf("point on measurement %q has %d columns, exceeding soft limit %d", p.Name(), columns, max))
+				break
+			}
+		}
+	}
+	return warnings
+}
+
+// logRejectedWrite records a rejected write request if a rejected writes log
+// is configured.
+func (h *Handler) logRejectedWrite(database, retentionPolicy string, user meta.User, reason string, body []byte) {
+	if h.rejectedWrites == nil {
+		return
+	}
+
+	rec := RejectedWrite{
+		Time:            time.Now().UTC(),
+		Database:        database,
+		RetentionPolicy: retentionPolicy,
+		Reason:          reason,
+		Body:            string(body),
+	}
+	if user != nil {
+		rec.User = user.ID()
+	}
+	if err := h.rejectedWrites.Log(rec); err != nil {
+		h.Logger.Error("unable to record rejected write", zap.Error(err))
+	}
+}
+
 // serveOptions returns an empty response to comply with OPTIONS pre-flight requests
 func (h *Handler) serveOptions(w http.ResponseWriter, r *http.Request) {
 	h.writeHeader(w, http.StatusNoContent)
@@ -1921,10 +2288,14 @@ func (h *Handler) servePromWrite(w http.ResponseWriter, r *http.Request, user me
 		atomic.AddInt64(&h.stats.PointsWrittenFail, int64(len(points)))
 		h.httpError(w, err.Error(), http.StatusBadRequest)
 		return
-	} else if influxdb.IsAuthorizationError(err) {
+	} else if influxdb.IsAuthorizationError(err) || influxdb.IsWriteFrozenError(err) {
 		atomic.AddInt64(&h.stats.PointsWrittenFail, int64(len(points)))
 		h.httpError(w, err.Error(), http.StatusForbidden)
 		return
+	} else if influxdb.IsDatabaseDeletedError(err) {
+		atomic.AddInt64(&h.stats.PointsWrittenFail, int64(len(points)))
+		h.httpError(w, err.Error(), http.StatusNotFound)
+		return
 	} else if werr, ok := err.(tsdb.PartialWriteError); ok {
 		atomic.AddInt64(&h.stats.PointsWrittenOK, int64(len(points)-werr.Dropped))
 		atomic.AddInt64(&h.stats.PointsWrittenDropped, int64(werr.Dropped))
@@ -2347,6 +2718,76 @@ func (h *Handler) serveExpvar(w http.ResponseWriter, r *http.Request) {
 	fmt.Fprintln(w, "\n}")
 }
 
+// adminOnly returns a handler serving handler only to admin users when
+// authentication is enabled, whatever the pprof authentication setting.
+func (h *Handler) adminOnly(handler func(http.ResponseWriter, *http.Request)) func(http.ResponseWriter, *http.Request, meta.User) {
+	return func(w http.ResponseWriter, r *http.Request, user meta.User) {
+		if h.Config.AuthEnabled && (user == nil || !user.AuthorizeUnrestricted()) {
+			h.Logger.Info("Unauthorized request", zap.String("path", r.URL.Path))
+			h.httpError(w, "error authorizing admin access", http.StatusForbidden)
+			return
+		}
+		handler(w, r)
+	}
+}
+
+// serveDebugQueries serves the history of completed queries as JSON.
+func (h *Handler) serveDebugQueries(w http.ResponseWriter, r *http.Request) {
+	w.Header().Set("Content-Type", "application/json; charset=utf-8")
+	if err := json.NewEncoder(w).Encode(h.QueryExecutor.TaskManager.QueryHistory()); err != nil {
+		h.Logger.Info("Error writing query history", zap.Error(err))
+	}
+}
+
+// MemoryUsage is the memory of the process broken down by subsystem, in bytes.
+type MemoryUsage struct {
+	HeapAlloc    int64 `json:"heapAlloc"`
+	HeapInUse    int64 `json:"heapInUse"`
+	HeapIdle     int64 `json:"heapIdle"`
+	HeapReleased int64 `json:"heapReleased"`
+	Sys          int64 `json:"sys"`
+
+	// Caches are the sizes of the TSM caches keyed by database.
+	Caches      map[string]int64 `json:"caches"`
+	CachesTotal int64            `json:"cachesTotal"`
+
+	// Other is the allocated heap not accounted for by the caches.
+	Other int64 `json:"other"`
+}
+
+// serveDebugMemory reports the memory of the process by subsystem.
+func (h *Handler) serveDebugMemory(w http.ResponseWriter, r *http.Request) {
+	stats, err := h.Monitor.Statistics(nil)
+	if err != nil {
+		h.httpError(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+
+	usage := MemoryUsage{Caches: make(map[string]int64)}
+	for _, s := range stats {
+		switch s.Name {
+		case "runtime":
+			usage.HeapAlloc, _ = s.Values["HeapAlloc"].(int64)
+			usage.HeapInUse, _ = s.Values["HeapInUse"].(int64)
+			usage.HeapIdle, _ = s.Values["HeapIdle"].(int64)
+			usage.HeapReleased, _ = s.Values["HeapReleased"].(int64)
+			usage.Sys, _ = s.Values["Sys"].(int64)
+		case "tsm1_cache":
+			n, _ := s.Values["memBytes"].(int64)
+			usage.Caches[s.Tags["database"]] += n
+			usage.CachesTotal += n
+		}
+	}
+	if usage.HeapAlloc > usage.CachesTotal {
+		usage.Other = usage.HeapAlloc - usage.CachesTotal
+	}
+
+	w.Header().Set("Content-Type", "application/json; charset=utf-8")
+	if err := json.NewEncoder(w).Encode(usage); err != nil {
+		h.Logger.Info("Error writing memory usage", zap.Error(err))
+	}
+}
+
 // serveDebugRequests will track requests for a period of time.
 func (h *Handler) serveDebugRequests(w http.ResponseWriter, r *http.Request) {
 	var d time.Duration
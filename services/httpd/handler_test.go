@@ -3,6 +3,8 @@ package httpd_test
 import (
 	"bytes"
 	"context"
+	"crypto/md5"
+	"encoding/base64"
 	"encoding/json"
 	"errors"
 	"fmt"
@@ -14,6 +16,7 @@ import (
 	"net/http/httptest"
 	"net/url"
 	"os"
+	"path/filepath"
 	"reflect"
 	"sort"
 	"strings"
@@ -27,6 +30,7 @@ import (
 	"github.com/influxdata/flux"
 	"github.com/influxdata/flux/lang"
 	"github.com/influxdata/flux/mock"
+	"github.com/influxdata/influxdb/coordinator"
 	"github.com/influxdata/influxdb/flux/client"
 	"github.com/influxdata/influxdb/internal"
 	"github.com/influxdata/influxdb/logger"
@@ -39,6 +43,7 @@ import (
 	"github.com/influxdata/influxdb/services/meta"
 	"github.com/influxdata/influxdb/storage/reads"
 	"github.com/influxdata/influxdb/storage/reads/datatypes"
+	itoml "github.com/influxdata/influxdb/toml"
 	"github.com/influxdata/influxdb/tsdb"
 	"github.com/influxdata/influxql"
 	"github.com/prometheus/prometheus/prompb"
@@ -378,6 +383,46 @@ func TestHandler_Query_Chunked(t *testing.T) {
 	}
 }
 
+// Ensure the handler limits the chunk size and sends keep-alives between chunks.
+func TestHandler_Query_Chunked_KeepAlive(t *testing.T) {
+	config := NewHandlerConfig()
+	config.MaxChunkSize = 2
+	config.QueryKeepAliveInterval = itoml.Duration(time.Millisecond)
+	h := NewHandlerWithConfig(config)
+	h.StatementExecutor.ExecuteStatementFn = func(stmt influxql.Statement, ctx *query.ExecutionContext) error {
+		if ctx.ChunkSize != 2 {
+			t.Fatalf("unexpected chunk size: %d", ctx.ChunkSize)
+		}
+		ctx.Results <- &query.Result{StatementID: 1, Series: models.Rows([]*models.Row{{Name: "series0"}})}
+		time.Sleep(20 * time.Millisecond)
+		ctx.Results <- &query.Result{StatementID: 1, Series: models.Rows([]*models.Row{{Name: "series1"}})}
+		return nil
+	}
+
+	w := httptest.NewRecorder()
+	h.ServeHTTP(w, MustNewJSONRequest("GET", "/query?db=foo&q=SELECT+*+FROM+bar&chunked=true&chunk_size=1000", nil))
+	if w.Code != http.StatusOK {
+		t.Fatalf("unexpected status: %d", w.Code)
+	}
+
+	body := w.Body.String()
+	if !strings.Contains(body, "}\n\n") {
+		t.Fatalf("expected keep-alive in body: %q", body)
+	}
+	var chunks []string
+	for _, line := range strings.Split(body, "\n") {
+		if line != "" {
+			chunks = append(chunks, line)
+		}
+	}
+	if !reflect.DeepEqual(chunks, []string{
+		`{"results":[{"statement_id":1,"series":[{"name":"series0"}]}]}`,
+		`{"results":[{"statement_id":1,"series":[{"name":"series1"}]}]}`,
+	}) {
+		t.Fatalf("unexpected chunks: %q", chunks)
+	}
+}
+
 // Ensure the handler can accept an async query.
 func TestHandler_Query_Async(t *testing.T) {
 	done := make(chan struct{})
@@ -672,6 +717,40 @@ func TestHandler_Ping_ErrAuthorize(t *testing.T) {
 	}
 }
 
+// Ensure the handler reports the memory of the process by subsystem.
+func TestHandler_DebugMemory(t *testing.T) {
+	h := NewHandler(false)
+	h.Monitor.StatisticsFn = func(_ map[string]string) ([]*monitor.Statistic, error) {
+		return []*monitor.Statistic{
+			{Statistic: models.Statistic{Name: "runtime", Values: map[string]interface{}{"HeapAlloc": int64(1000), "Sys": int64(4000)}}},
+			{Statistic: models.Statistic{Name: "tsm1_cache", Tags: map[string]string{"database": "db0", "id": "1"}, Values: map[string]interface{}{"memBytes": int64(100)}}},
+			{Statistic: models.Statistic{Name: "tsm1_cache", Tags: map[string]string{"database": "db0", "id": "2"}, Values: map[string]interface{}{"memBytes": int64(200)}}},
+			{Statistic: models.Statistic{Name: "tsm1_cache", Tags: map[string]string{"database": "db1", "id": "3"}, Values: map[string]interface{}{"memBytes": int64(50)}}},
+		}, nil
+	}
+
+	w := httptest.NewRecorder()
+	h.ServeHTTP(w, MustNewRequest("GET", "/debug/memory", nil))
+	if w.Code != http.StatusOK {
+		t.Fatalf("unexpected status: %d", w.Code)
+	}
+
+	var usage httpd.MemoryUsage
+	if err := json.Unmarshal(w.Body.Bytes(), &usage); err != nil {
+		t.Fatal(err)
+	}
+	exp := httpd.MemoryUsage{
+		HeapAlloc:   1000,
+		Sys:         4000,
+		Caches:      map[string]int64{"db0": 300, "db1": 50},
+		CachesTotal: 350,
+		Other:       650,
+	}
+	if !reflect.DeepEqual(usage, exp) {
+		t.Fatalf("unexpected memory usage: %+v", usage)
+	}
+}
+
 // Ensure the handler returns an appropriate 403 status when authentication or
 // authorization fails on debug endpoints.
 func TestHandler_Debug_ErrAuthorize(t *testing.T) {
@@ -748,6 +827,85 @@ func TestHandler_Debug_ErrAuthorize(t *testing.T) {
 	}
 }
 
+// Ensure the debug endpoints exposing the queries of other users require an
+// admin user even when pprof authentication is disabled.
+func TestHandler_DebugQueries_ErrAuthorize(t *testing.T) {
+	h := NewHandlerWithConfig(NewHandlerConfig(WithAuthentication()))
+	h.MetaClient.AdminUserExistsFn = func() bool { return true }
+	h.MetaClient.AuthenticateFn = func(u, p string) (meta.User, error) {
+		users := []meta.UserInfo{
+			{
+				Name:  "admin",
+				Hash:  "admin",
+				Admin: true,
+			},
+			{
+				Name: "user1",
+				Hash: "abcd",
+				Privileges: map[string]influxql.Privilege{
+					"db0": influxql.ReadPrivilege,
+				},
+			},
+		}
+
+		for _, user := range users {
+			if u == user.Name {
+				if p == user.Hash {
+					return &user, nil
+				}
+				return nil, meta.ErrAuthenticate
+			}
+		}
+		return nil, meta.ErrUserNotFound
+	}
+
+	for i, tt := range []struct {
+		user     string
+		password string
+		query    string
+		code     int
+	}{
+		{
+			query: "/debug/queries",
+			code:  http.StatusUnauthorized,
+		},
+		{
+			user:     "user1",
+			password: "abcd",
+			query:    "/debug/queries",
+			code:     http.StatusForbidden,
+		},
+		{
+			user:     "user1",
+			password: "abcd",
+			query:    "/debug/memory",
+			code:     http.StatusForbidden,
+		},
+		{
+			user:     "admin",
+			password: "admin",
+			query:    "/debug/queries",
+			code:     http.StatusOK,
+		},
+	} {
+		w := httptest.NewRecorder()
+		r := MustNewJSONRequest("GET", tt.query, nil)
+		params := r.URL.Query()
+		if tt.user != "" {
+			params.Set("u", tt.user)
+		}
+		if tt.password != "" {
+			params.Set("p", tt.password)
+		}
+		r.URL.RawQuery = params.Encode()
+
+		h.ServeHTTP(w, r)
+		if w.Code != tt.code {
+			t.Errorf("%d. unexpected status: got=%d exp=%d\noutput: %s", i, w.Code, tt.code, w.Body.String())
+		}
+	}
+}
+
 // Ensure the prometheus remote write works with valid values.
 func TestHandler_PromWrite(t *testing.T) {
 	req := &prompb.WriteRequest{
@@ -1714,6 +1872,331 @@ func TestHandler_Write_V2_Precision(t *testing.T) {
 	}
 }
 
+// TestHandler_Write_IdempotencyKey verifies retried writes are acknowledged without being applied again.
+func TestHandler_Write_IdempotencyKey(t *testing.T) {
+	config := NewHandlerConfig()
+	config.WriteIdempotencyWindow = itoml.Duration(time.Minute)
+	h := NewHandlerWithConfig(config)
+	h.MetaClient.DatabaseFn = func(name string) *meta.DatabaseInfo {
+		return &meta.DatabaseInfo{}
+	}
+
+	var writes int
+	var fail bool
+	h.PointsWriter.WritePointsFn = func(_, _ string, _ models.ConsistencyLevel, _ meta.User, _ []models.Point) error {
+		writes++
+		if fail {
+			return errors.New("write failed")
+		}
+		return nil
+	}
+
+	write := func(url, key string) int {
+		req := MustNewRequest("POST", url, bytes.NewReader([]byte(`foo n=1`)))
+		req.Header.Set(httpd.IdempotencyKeyHeader, key)
+		w := httptest.NewRecorder()
+		h.ServeHTTP(w, req)
+		return w.Code
+	}
+
+	tests := []struct {
+		url    string
+		key    string
+		fail   bool
+		status int
+		writes int
+	}{
+		// A failed write releases the key so it can be retried.
+		{"/write?db=foo", "a", true, http.StatusInternalServerError, 1},
+		{"/write?db=foo", "a", false, http.StatusNoContent, 2},
+		// Retries of an acknowledged write are not applied.
+		{"/write?db=foo", "a", false, http.StatusNoContent, 2},
+		// Keys are scoped to the database.
+		{"/write?db=bar", "a", false, http.StatusNoContent, 3},
+		{"/write?db=foo", "b", false, http.StatusNoContent, 4},
+	}
+
+	for i, tt := range tests {
+		fail = tt.fail
+		if code := write(tt.url, tt.key); code != tt.status {
+			t.Fatalf("%d. unexpected status: exp %d, got %d", i, tt.status, code)
+		} else if writes != tt.writes {
+			t.Fatalf("%d. unexpected writes: exp %d, got %d", i, tt.writes, writes)
+		}
+	}
+}
+
+// TestHandler_Query_CircuitBreaker_Timeout verifies only timeouts of the server count toward the breaker of queries.
+func TestHandler_Query_CircuitBreaker_Timeout(t *testing.T) {
+	config := NewHandlerConfig()
+	config.CircuitBreakerFailures = 1
+	config.CircuitBreakerCooldown = itoml.Duration(time.Minute)
+	h := NewHandlerWithConfig(config)
+	h.QueryExecutor.TaskManager.QueryTimeout = 50 * time.Millisecond
+	h.StatementExecutor.ExecuteStatementFn = func(stmt influxql.Statement, ctx *query.ExecutionContext) error {
+		<-ctx.Done()
+		return query.ErrQueryInterrupted
+	}
+
+	for i, tt := range []struct {
+		timeout string
+		status  int
+	}{
+		// The timeout of the client applies, so it is not counted.
+		{"10ms", http.StatusOK},
+		// The timeout of the server applies although the client set a longer one.
+		{"1h", http.StatusOK},
+		{"1h", http.StatusServiceUnavailable},
+	} {
+		w := httptest.NewRecorder()
+		h.ServeHTTP(w, MustNewJSONRequest("GET", "/query?db=foo&q=SELECT+*+FROM+bar&timeout="+tt.timeout, nil))
+		if w.Code != tt.status {
+			t.Fatalf("%d. unexpected status: exp %d, got %d: %s", i, tt.status, w.Code, w.Body.String())
+		}
+	}
+}
+
+// TestHandler_Write_CircuitBreaker verifies writes to a failing database are shed until a probe succeeds.
+func TestHandler_Write_CircuitBreaker(t *testing.T) {
+	config := NewHandlerConfig()
+	config.CircuitBreakerFailures = 2
+	config.CircuitBreakerCooldown = itoml.Duration(50 * time.Millisecond)
+	h := NewHandlerWithConfig(config)
+	h.MetaClient.DatabaseFn = func(name string) *meta.DatabaseInfo {
+		return &meta.DatabaseInfo{}
+	}
+
+	var writes int
+	var fail bool
+	h.PointsWriter.WritePointsFn = func(database, _ string, _ models.ConsistencyLevel, _ meta.User, _ []models.Point) error {
+		writes++
+		if fail && database == "foo" {
+			return errors.New("write failed")
+		}
+		return nil
+	}
+
+	write := func(url string) int {
+		w := httptest.NewRecorder()
+		h.ServeHTTP(w, MustNewRequest("POST", url, bytes.NewReader([]byte(`foo n=1`))))
+		return w.Code
+	}
+
+	tests := []struct {
+		url    string
+		fail   bool
+		wait   bool
+		status int
+		writes int
+	}{
+		{"/write?db=foo", true, false, http.StatusInternalServerError, 1},
+		{"/write?db=foo", true, false, http.StatusInternalServerError, 2},
+		// The breaker is open so writes are rejected without being attempted.
+		{"/write?db=foo", false, false, http.StatusServiceUnavailable, 2},
+		// Other databases are unaffected.
+		{"/write?db=bar", true, false, http.StatusNoContent, 3},
+		// A failed probe opens the breaker again.
+		{"/write?db=foo", true, true, http.StatusInternalServerError, 4},
+		{"/write?db=foo", false, false, http.StatusServiceUnavailable, 4},
+		// A successful probe closes it.
+		{"/write?db=foo", false, true, http.StatusNoContent, 5},
+		{"/write?db=foo", false, false, http.StatusNoContent, 6},
+	}
+
+	h.StatementExecutor.ExecuteStatementFn = func(stmt influxql.Statement, ctx *query.ExecutionContext) error {
+		ctx.Results <- &query.Result{StatementID: 1}
+		return nil
+	}
+
+	for i, tt := range tests {
+		fail = tt.fail
+		if tt.wait {
+			time.Sleep(2 * time.Duration(config.CircuitBreakerCooldown))
+		}
+		if code := write(tt.url); code != tt.status {
+			t.Fatalf("%d. unexpected status: exp %d, got %d", i, tt.status, code)
+		} else if writes != tt.writes {
+			t.Fatalf("%d. unexpected writes: exp %d, got %d", i, tt.writes, writes)
+		}
+
+		// Queries are not shed by the breaker of writes.
+		w := httptest.NewRecorder()
+		h.ServeHTTP(w, MustNewJSONRequest("GET", "/query?db=foo&q=SELECT+*+FROM+bar", nil))
+		if w.Code != http.StatusOK {
+			t.Fatalf("%d. unexpected query status: %d", i, w.Code)
+		}
+	}
+}
+
+func TestHandler_Write_TimingHeader(t *testing.T) {
+	config := NewHandlerConfig()
+	config.WriteTimingHeader = true
+	h := NewHandlerWithConfig(config)
+	h.MetaClient.DatabaseFn = func(name string) *meta.DatabaseInfo {
+		return &meta.DatabaseInfo{}
+	}
+	h.PointsWriter.WritePointsFn = func(_, _ string, _ models.ConsistencyLevel, _ meta.User, _ []models.Point) error {
+		time.Sleep(time.Millisecond)
+		return nil
+	}
+
+	w := httptest.NewRecorder()
+	h.ServeHTTP(w, MustNewRequest("POST", "/write?db=foo", bytes.NewReader([]byte(`foo n=1`))))
+	if w.Code != http.StatusNoContent {
+		t.Fatalf("unexpected status: %d", w.Code)
+	}
+
+	timing := w.Header().Get(httpd.WriteTimingHeader)
+	if !strings.HasPrefix(timing, "read=") || !strings.Contains(timing, ", parse=") || !strings.Contains(timing, ", write=") {
+		t.Fatalf("unexpected timing header: %q", timing)
+	}
+
+	stats := h.Statistics(nil)[0].Values
+	if d := stats["writeReqWriteDurationNs"].(int64); d < int64(time.Millisecond) {
+		t.Fatalf("unexpected write duration: %d", d)
+	}
+}
+
+func TestHandler_Write_SoftLimits(t *testing.T) {
+	config := NewHandlerConfig()
+	config.SoftMaxBodySize = 20
+	config.SoftMaxColumnsPerPoint = 2
+	h := NewHandlerWithConfig(config)
+	h.MetaClient.DatabaseFn = func(name string) *meta.DatabaseInfo {
+		return &meta.DatabaseInfo{}
+	}
+	var written int
+	h.PointsWriter.WritePointsFn = func(_, _ string, _ models.ConsistencyLevel, _ meta.User, points []models.Point) error {
+		written += len(points)
+		return nil
+	}
+
+	w := httptest.NewRecorder()
+	h.ServeHTTP(w, MustNewRequest("POST", "/write?db=foo", bytes.NewReader([]byte("cpu n=1\ncpu,host=a n=1,m=2"))))
+	if w.Code != http.StatusNoContent {
+		t.Fatalf("unexpected status: %d", w.Code)
+	} else if written != 2 {
+		t.Fatalf("unexpected points written: %d", written)
+	}
+
+	warnings := w.Header().Values(httpd.WriteWarningHeader)
+	if len(warnings) != 2 || !strings.Contains(warnings[0], "body size 27") || !strings.Contains(warnings[1], "3 columns") {
+		t.Fatalf("unexpected warnings: %q", warnings)
+	}
+	if n := h.Statistics(nil)[0].Values["writeReqSoftLimited"].(int64); n != 1 {
+		t.Fatalf("unexpected soft limited requests: %d", n)
+	}
+
+	// Writes within the soft limits carry no warning.
+	w = httptest.NewRecorder()
+	h.ServeHTTP(w, MustNewRequest("POST", "/write?db=foo", bytes.NewReader([]byte("cpu n=1"))))
+	if w.Code != http.StatusNoContent {
+		t.Fatalf("unexpected status: %d", w.Code)
+	} else if warnings := w.Header().Values(httpd.WriteWarningHeader); len(warnings) != 0 {
+		t.Fatalf("unexpected warnings: %q", warnings)
+	}
+}
+
+func TestHandler_Write_ContentMD5(t *testing.T) {
+	h := NewHandler(false)
+	h.MetaClient.DatabaseFn = func(name string) *meta.DatabaseInfo {
+		return &meta.DatabaseInfo{}
+	}
+	var written int
+	h.PointsWriter.WritePointsFn = func(_, _ string, _ models.ConsistencyLevel, _ meta.User, points []models.Point) error {
+		written += len(points)
+		return nil
+	}
+
+	body := []byte("cpu n=1")
+	sum := md5.Sum(body)
+	for _, tt := range []struct {
+		checksum string
+		code     int
+	}{
+		{base64.StdEncoding.EncodeToString(sum[:]), http.StatusNoContent},
+		{base64.StdEncoding.EncodeToString(sum[1:]), http.StatusBadRequest},
+		{"not base64", http.StatusBadRequest},
+	} {
+		req := MustNewRequest("POST", "/write?db=foo", bytes.NewReader(body))
+		req.Header.Set("Content-MD5", tt.checksum)
+		w := httptest.NewRecorder()
+		h.ServeHTTP(w, req)
+		if w.Code != tt.code {
+			t.Fatalf("%q: unexpected status: %d", tt.checksum, w.Code)
+		}
+	}
+	if written != 1 {
+		t.Fatalf("unexpected points written: %d", written)
+	}
+}
+
+func TestHandler_Write_Frozen(t *testing.T) {
+	h := NewHandler(false)
+	h.MetaClient.DatabaseFn = func(name string) *meta.DatabaseInfo {
+		return &meta.DatabaseInfo{}
+	}
+	h.PointsWriter.WritePointsFn = func(_, _ string, _ models.ConsistencyLevel, _ meta.User, points []models.Point) error {
+		return coordinator.WriteFrozenError{Database: "foo", Reason: "migrating"}
+	}
+
+	w := httptest.NewRecorder()
+	h.ServeHTTP(w, MustNewRequest("POST", "/write?db=foo", strings.NewReader("cpu n=1")))
+	if w.Code != http.StatusForbidden {
+		t.Fatalf("unexpected status: %d", w.Code)
+	} else if body := strings.TrimSpace(w.Body.String()); body != `{"error":"database \"foo\" is frozen for writes: migrating"}` {
+		t.Fatalf("unexpected body: %s", body)
+	}
+}
+
+func TestHandler_Write_RejectedWritesLog(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "rejected.log")
+
+	config := NewHandlerConfig()
+	config.RejectedWritesPath = path
+	h := NewHandlerWithConfig(config)
+	h.MetaClient.DatabaseFn = func(name string) *meta.DatabaseInfo {
+		return &meta.DatabaseInfo{}
+	}
+	h.PointsWriter.WritePointsFn = func(_, _ string, _ models.ConsistencyLevel, _ meta.User, points []models.Point) error {
+		return tsdb.PartialWriteError{Reason: "field type conflict", Dropped: len(points)}
+	}
+	h.Open()
+
+	for _, body := range []string{`foo n=1`, `foo n=`} {
+		w := httptest.NewRecorder()
+		h.ServeHTTP(w, MustNewRequest("POST", "/write?db=foo&rp=bar", strings.NewReader(body)))
+		if w.Code != http.StatusBadRequest {
+			t.Fatalf("unexpected status: %d", w.Code)
+		}
+	}
+	h.Close()
+
+	f, err := os.Open(path)
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer f.Close()
+
+	var recs []httpd.RejectedWrite
+	dec := json.NewDecoder(f)
+	for dec.More() {
+		var rec httpd.RejectedWrite
+		if err := dec.Decode(&rec); err != nil {
+			t.Fatal(err)
+		}
+		recs = append(recs, rec)
+	}
+
+	if len(recs) != 2 {
+		t.Fatalf("unexpected rejected writes: %+v", recs)
+	} else if rec := recs[0]; rec.Database != "foo" || rec.RetentionPolicy != "bar" || rec.Body != `foo n=1` || rec.Reason != "partial write: field type conflict dropped=1" {
+		t.Fatalf("unexpected rejected write: %+v", rec)
+	} else if rec := recs[1]; rec.Body != `foo n=` || !strings.Contains(rec.Reason, "unable to parse") {
+		t.Fatalf("unexpected rejected write: %+v", rec)
+	}
+}
+
 func TestHandler_Delete_V2(t *testing.T) {
 	var errUnexpectedMeasurement = errors.New("unexpected measurement")
 	type test struct {
@@ -2342,6 +2825,93 @@ func TestHandler_RetrieveBucket(t *testing.T) {
 	}
 }
 
+func TestHandler_Orgs(t *testing.T) {
+	h := NewHandler(false)
+
+	w := httptest.NewRecorder()
+	h.ServeHTTP(w, MustNewRequest("GET", "/api/v2/orgs?org=my-org", nil))
+	if w.Code != http.StatusOK {
+		t.Fatalf("unexpected status: %d", w.Code)
+	}
+	var orgs httpd.Orgs
+	if err := json.Unmarshal(w.Body.Bytes(), &orgs); err != nil {
+		t.Fatal(err)
+	} else if len(orgs.Orgs) != 1 || orgs.Orgs[0].ID != httpd.DefaultOrgID {
+		t.Fatalf("unexpected orgs: %+v", orgs)
+	}
+
+	w = httptest.NewRecorder()
+	h.ServeHTTP(w, MustNewRequest("GET", "/api/v2/orgs/"+httpd.DefaultOrgID, nil))
+	if w.Code != http.StatusOK {
+		t.Fatalf("unexpected status: %d", w.Code)
+	}
+	var org httpd.Org
+	if err := json.Unmarshal(w.Body.Bytes(), &org); err != nil {
+		t.Fatal(err)
+	} else if org != orgs.Orgs[0] {
+		t.Fatalf("unexpected org: %+v", org)
+	}
+
+	w = httptest.NewRecorder()
+	h.ServeHTTP(w, MustNewRequest("GET", "/api/v2/orgs/0000000000000002", nil))
+	if w.Code != http.StatusNotFound {
+		t.Fatalf("unexpected status: %d", w.Code)
+	}
+}
+
+func TestHandler_RestoreDatabase(t *testing.T) {
+	dbs := map[string]*meta.DatabaseInfo{
+		"db0": {Name: "db0", DeletedAt: time.Now()},
+		"db1": {Name: "db1"},
+	}
+
+	h := NewHandler(false)
+	h.MetaClient.DatabaseFn = func(name string) *meta.DatabaseInfo {
+		return dbs[name]
+	}
+	h.MetaClient.RestoreDatabaseFn = func(name string) error {
+		if !dbs[name].Deleted() {
+			return meta.ErrDatabaseNotDeleted
+		}
+		dbs[name].DeletedAt = time.Time{}
+		return nil
+	}
+	h.PointsWriter.WritePointsFn = func(db, _ string, _ models.ConsistencyLevel, _ meta.User, _ []models.Point) error {
+		if dbs[db].Deleted() {
+			return coordinator.DatabaseDeletedError{Database: db}
+		}
+		return nil
+	}
+
+	// Writes to a deleted database are rejected.
+	w := httptest.NewRecorder()
+	h.ServeHTTP(w, MustNewRequest("POST", "/write?db=db0", bytes.NewBufferString("cpu value=1")))
+	if w.Code != http.StatusNotFound {
+		t.Fatalf("unexpected status: %d", w.Code)
+	} else if got, exp := w.Header().Get("X-InfluxDB-Error"), `database is deleted: "db0"`; got != exp {
+		t.Fatalf("unexpected error: got %q, exp %q", got, exp)
+	}
+
+	for _, tt := range []struct {
+		db     string
+		status int
+	}{
+		{db: "db0", status: http.StatusNoContent},
+		{db: "db1", status: http.StatusBadRequest},
+		{db: "db2", status: http.StatusNotFound},
+	} {
+		w := httptest.NewRecorder()
+		h.ServeHTTP(w, MustNewRequest("POST", "/api/v1/databases/"+tt.db+"/restore", nil))
+		if w.Code != tt.status {
+			t.Fatalf("%s: unexpected status: got %d, exp %d", tt.db, w.Code, tt.status)
+		}
+	}
+
+	if dbs["db0"].Deleted() {
+		t.Fatal("expected db0 to be restored")
+	}
+}
+
 func TestHandler_UnsupportedV2API(t *testing.T) {
 	type test struct {
 		method string
@@ -0,0 +1,109 @@
+package httpd
+
+import (
+	"errors"
+	"sync"
+	"time"
+
+	"github.com/influxdata/influxdb/services/meta"
+)
+
+// IdempotencyKeyHeader is the request header carrying a client-supplied key
+// that identifies retries of the same write request.
+const IdempotencyKeyHeader = "Idempotency-Key"
+
+// errWriteInProgress is returned when a write arrives while another write with
+// the same idempotency key has not completed yet.
+var errWriteInProgress = errors.New("write with the same idempotency key is in progress")
+
+// errTooManyWriteKeys is returned when a write carries a new idempotency key
+// while the maximum number of keys are remembered.
+var errTooManyWriteKeys = errors.New("too many idempotency keys remembered, retry later")
+
+// writeKeyCache remembers the idempotency keys of recently acknowledged writes
+// so that client retries within the window are acknowledged without being
+// applied again.
+type writeKeyCache struct {
+	mu        sync.Mutex
+	window    time.Duration
+	maxKeys   int                  // maximum number of keys remembered, 0 for no limit
+	keys      map[string]time.Time // key to expiry, zero while the write is in progress
+	lastSweep time.Time
+
+	now func() time.Time
+}
+
+// newWriteKeyCache returns a cache remembering at most maxKeys keys for window.
+func newWriteKeyCache(window time.Duration, maxKeys int) *writeKeyCache {
+	return &writeKeyCache{
+		window:  window,
+		maxKeys: maxKeys,
+		keys:    make(map[string]time.Time),
+		now:     time.Now,
+	}
+}
+
+// writeKey scopes a client-supplied idempotency key to the target of the write
+// and the user performing it.
+func writeKey(database, retentionPolicy string, user meta.User, key string) string {
+	var userID string
+	if user != nil {
+		userID = user.ID()
+	}
+	return database + "\x00" + retentionPolicy + "\x00" + userID + "\x00" + key
+}
+
+// Begin reserves key for a new write. It returns false if a write with the same
+// key was acknowledged within the window, errWriteInProgress if one has not
+// completed yet and errTooManyWriteKeys if key is new and the cache is full.
+// A reserved key must be released with Commit or Abort.
+func (c *writeKeyCache) Begin(key string) (bool, error) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	now := c.now()
+	c.sweep(now, false)
+
+	expires, ok := c.keys[key]
+	if ok {
+		if expires.IsZero() {
+			return false, errWriteInProgress
+		} else if expires.After(now) {
+			return false, nil
+		}
+	} else if c.maxKeys > 0 && len(c.keys) >= c.maxKeys {
+		// Remove the keys expired since the last sweep before rejecting it.
+		if c.sweep(now, true); len(c.keys) >= c.maxKeys {
+			return false, errTooManyWriteKeys
+		}
+	}
+	c.keys[key] = time.Time{}
+	return true, nil
+}
+
+// Commit records that the write reserved with key was acknowledged.
+func (c *writeKeyCache) Commit(key string) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	c.keys[key] = c.now().Add(c.window)
+}
+
+// Abort releases key after a failed write so the client can retry it.
+func (c *writeKeyCache) Abort(key string) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	delete(c.keys, key)
+}
+
+// sweep removes expired keys at most once per window, unless forced.
+func (c *writeKeyCache) sweep(now time.Time, force bool) {
+	if !force && now.Sub(c.lastSweep) < c.window {
+		return
+	}
+	for key, expires := range c.keys {
+		if !expires.IsZero() && !expires.After(now) {
+			delete(c.keys, key)
+		}
+	}
+	c.lastSweep = now
+}
@@ -0,0 +1,33 @@
+package httpd
+
+import (
+	"testing"
+	"time"
+)
+
+func TestWriteKeyCache_MaxKeys(t *testing.T) {
+	now := time.Unix(0, 0)
+	c := newWriteKeyCache(time.Minute, 2)
+	c.now = func() time.Time { return now }
+
+	for _, key := range []string{"a", "b"} {
+		if ok, err := c.Begin(key); !ok || err != nil {
+			t.Fatalf("unexpected result for key %q: %v, %v", key, ok, err)
+		}
+		c.Commit(key)
+	}
+
+	// A new key is rejected while the cache is full, but a retry of a
+	// remembered one is still acknowledged.
+	if _, err := c.Begin("c"); err != errTooManyWriteKeys {
+		t.Fatalf("unexpected error: %v", err)
+	} else if ok, err := c.Begin("a"); ok || err != nil {
+		t.Fatalf("unexpected result for retry: %v, %v", ok, err)
+	}
+
+	// Once the remembered keys expire, they make room for new ones.
+	now = now.Add(time.Minute)
+	if ok, err := c.Begin("c"); !ok || err != nil {
+		t.Fatalf("unexpected result once keys expired: %v, %v", ok, err)
+	}
+}
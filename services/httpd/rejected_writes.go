@@ -0,0 +1,101 @@
+package httpd
+
+import (
+	"encoding/json"
+	"os"
+	"sync"
+	"time"
+)
+
+// RejectedWrite is the record of a write request that was rejected, in whole
+// or in part. Body holds the line protocol of the request so the points can be
+// corrected and written again. Truncated is set if Body holds only the start
+// of the request.
+type RejectedWrite struct {
+	Time            time.Time `json:"time"`
+	Database        string    `json:"database"`
+	RetentionPolicy string    `json:"retention_policy,omitempty"`
+	User            string    `json:"user,omitempty"`
+	Reason          string    `json:"reason"`
+	Body            string    `json:"body"`
+	Truncated       bool      `json:"truncated,omitempty"`
+}
+
+// rejectedWriteLog appends rejected writes to a file as JSON, one record per
+// line. The body of a record is truncated to maxBodySize bytes, and the file
+// is rotated to path.1 once it would exceed maxSize bytes, replacing the
+// previous rotated file. A zero limit disables it.
+type rejectedWriteLog struct {
+	mu          sync.Mutex
+	path        string
+	maxBodySize int
+	maxSize     int64
+
+	f    *os.File
+	size int64
+}
+
+// openRejectedWriteLog returns a log appending records to the file at path.
+func openRejectedWriteLog(path string, maxBodySize int, maxSize int64) (*rejectedWriteLog, error) {
+	l := &rejectedWriteLog{path: path, maxBodySize: maxBodySize, maxSize: maxSize}
+	if err := l.open(); err != nil {
+		return nil, err
+	}
+	return l, nil
+}
+
+// open opens the file at the path of the log for appending.
+func (l *rejectedWriteLog) open() error {
+	f, err := os.OpenFile(l.path, os.O_WRONLY|os.O_APPEND|os.O_CREATE, 0600)
+	if err != nil {
+		return err
+	}
+	fi, err := f.Stat()
+	if err != nil {
+		f.Close()
+		return err
+	}
+	l.f, l.size = f, fi.Size()
+	return nil
+}
+
+// Log appends rec to the log.
+func (l *rejectedWriteLog) Log(rec RejectedWrite) error {
+	if l.maxBodySize > 0 && len(rec.Body) > l.maxBodySize {
+		rec.Body, rec.Truncated = rec.Body[:l.maxBodySize], true
+	}
+	b, err := json.Marshal(rec)
+	if err != nil {
+		return err
+	}
+	b = append(b, '\n')
+
+	l.mu.Lock()
+	defer l.mu.Unlock()
+
+	if l.maxSize > 0 && l.size > 0 && l.size+int64(len(b)) > l.maxSize {
+		if err := l.rotate(); err != nil {
+			return err
+		}
+	}
+	n, err := l.f.Write(b)
+	l.size += int64(n)
+	return err
+}
+
+// rotate moves the file of the log to path.1 and opens a new one.
+func (l *rejectedWriteLog) rotate() error {
+	if err := l.f.Close(); err != nil {
+		return err
+	} else if err := os.Rename(l.path, l.path+".1"); err != nil {
+		return err
+	}
+	return l.open()
+}
+
+// Close closes the file of the log.
+func (l *rejectedWriteLog) Close() error {
+	l.mu.Lock()
+	defer l.mu.Unlock()
+	return l.f.Close()
+}
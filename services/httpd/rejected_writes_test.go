@@ -0,0 +1,52 @@
+package httpd
+
+import (
+	"bufio"
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func TestRejectedWriteLog_Limits(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "rejected.log")
+	l, err := openRejectedWriteLog(path, 8, 200)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	for i := 0; i < 3; i++ {
+		if err := l.Log(RejectedWrite{Database: "db0", Reason: "field type conflict", Body: strings.Repeat("cpu value=1\n", 100)}); err != nil {
+			t.Fatal(err)
+		}
+	}
+	if err := l.Close(); err != nil {
+		t.Fatal(err)
+	}
+
+	// Each record is larger than half of the maximum size, so the log is
+	// rotated before each of them but the first.
+	for _, p := range []string{path, path + ".1"} {
+		f, err := os.Open(p)
+		if err != nil {
+			t.Fatal(err)
+		}
+		defer f.Close()
+
+		var n int
+		scanner := bufio.NewScanner(f)
+		for scanner.Scan() {
+			var rec RejectedWrite
+			if err := json.Unmarshal(scanner.Bytes(), &rec); err != nil {
+				t.Fatal(err)
+			} else if rec.Body != "cpu valu" || !rec.Truncated {
+				t.Fatalf("unexpected record: %+v", rec)
+			}
+			n++
+		}
+		if n != 1 {
+			t.Fatalf("%s: unexpected record count: %d", p, n)
+		}
+	}
+}
@@ -174,6 +174,9 @@ func (c *Client) CreateDatabase(name string) (*DatabaseInfo, error) {
 	data := c.cacheData.Clone()
 
 	if db := data.Database(name); db != nil {
+		if db.Deleted() {
+			return nil, ErrDatabaseDeleted
+		}
 		return db, nil
 	}
 
@@ -229,6 +232,8 @@ func (c *Client) CreateDatabaseWithRetentionPolicy(name string, spec *RetentionP
 			return nil, err
 		}
 		db = data.Database(name)
+	} else if db.Deleted() {
+		return nil, ErrDatabaseDeleted
 	}
 
 	// No existing retention policies, so we can create the provided policy as
@@ -282,6 +287,42 @@ func (c *Client) DropDatabase(name string) error {
 	return nil
 }
 
+// MarkDatabaseDeleted soft-deletes a database.
+func (c *Client) MarkDatabaseDeleted(name string) error {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	data := c.cacheData.Clone()
+
+	if err := data.MarkDatabaseDeleted(name, time.Now()); err != nil {
+		return err
+	}
+
+	if err := c.commit(data); err != nil {
+		return err
+	}
+
+	return nil
+}
+
+// RestoreDatabase restores a soft-deleted database.
+func (c *Client) RestoreDatabase(name string) error {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	data := c.cacheData.Clone()
+
+	if err := data.RestoreDatabase(name); err != nil {
+		return err
+	}
+
+	if err := c.commit(data); err != nil {
+		return err
+	}
+
+	return nil
+}
+
 // CreateRetentionPolicy creates a retention policy on the specified database.
 func (c *Client) CreateRetentionPolicy(database string, spec *RetentionPolicySpec, makeDefault bool) (*RetentionPolicyInfo, error) {
 	c.mu.Lock()
@@ -693,6 +734,10 @@ func (c *Client) PruneShardGroups() error {
 func (c *Client) CreateShardGroup(database, policy string, timestamp time.Time) (*ShardGroupInfo, error) {
 	// Check under a read-lock
 	c.mu.RLock()
+	if di := c.cacheData.Database(database); di != nil && di.Deleted() {
+		c.mu.RUnlock()
+		return nil, ErrDatabaseDeleted
+	}
 	if sg, _ := c.cacheData.ShardGroupByTimestamp(database, policy, timestamp); sg != nil {
 		c.mu.RUnlock()
 		return sg, nil
@@ -721,6 +766,10 @@ func (c *Client) CreateShardGroup(database, policy string, timestamp time.Time)
 }
 
 func createShardGroup(data *Data, database, policy string, timestamp time.Time) (*ShardGroupInfo, error) {
+	if di := data.Database(database); di != nil && di.Deleted() {
+		return nil, ErrDatabaseDeleted
+	}
+
 	// It is the responsibility of the caller to check if it exists before calling this method.
 	if sg, _ := data.ShardGroupByTimestamp(database, policy, timestamp); sg != nil {
 		return nil, ErrShardGroupExists
@@ -770,6 +819,10 @@ func (c *Client) PrecreateShardGroups(from, to time.Time) error {
 	var changed bool
 
 	for _, di := range data.Databases {
+		if di.Deleted() {
+			// Soft-deleted databases do not accept writes.
+			continue
+		}
 		for _, rp := range di.RetentionPolicies {
 			if len(rp.ShardGroups) == 0 {
 				// No data was ever written to this group, or all groups have been deleted.
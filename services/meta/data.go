@@ -84,7 +84,10 @@ func (data *Data) CreateDatabase(name string) error {
 		return ErrDatabaseNameRequired
 	} else if len(name) > MaxNameLen {
 		return ErrNameTooLong
-	} else if data.Database(name) != nil {
+	} else if di := data.Database(name); di != nil {
+		if di.Deleted() {
+			return ErrDatabaseDeleted
+		}
 		return nil
 	}
 
@@ -111,6 +114,32 @@ func (data *Data) DropDatabase(name string) error {
 	return nil
 }
 
+// MarkDatabaseDeleted soft-deletes a database. The database remains in the
+// meta store, with its retention policies and shard groups, until it is
+// restored or dropped.
+func (data *Data) MarkDatabaseDeleted(name string, deletedAt time.Time) error {
+	di := data.Database(name)
+	if di == nil {
+		return influxdb.ErrDatabaseNotFound(name)
+	} else if di.Deleted() {
+		return nil
+	}
+	di.DeletedAt = deletedAt.UTC()
+	return nil
+}
+
+// RestoreDatabase restores a soft-deleted database.
+func (data *Data) RestoreDatabase(name string) error {
+	di := data.Database(name)
+	if di == nil {
+		return influxdb.ErrDatabaseNotFound(name)
+	} else if !di.Deleted() {
+		return ErrDatabaseNotDeleted
+	}
+	di.DeletedAt = time.Time{}
+	return nil
+}
+
 // RetentionPolicy returns a retention policy for a database by name.
 func (data *Data) RetentionPolicy(database, name string) (*RetentionPolicyInfo, error) {
 	di := data.Database(database)
@@ -972,6 +1001,15 @@ type DatabaseInfo struct {
 	DefaultRetentionPolicy string
 	RetentionPolicies      []RetentionPolicyInfo
 	ContinuousQueries      []ContinuousQueryInfo
+
+	// DeletedAt is the time the database was soft-deleted. Its data is
+	// retained until it is restored or the delete grace period elapses.
+	DeletedAt time.Time
+}
+
+// Deleted returns whether the database has been soft-deleted.
+func (di DatabaseInfo) Deleted() bool {
+	return !di.DeletedAt.IsZero()
 }
 
 // RetentionPolicy returns a retention policy by name.
@@ -1053,6 +1091,10 @@ func (di DatabaseInfo) marshal() *internal.DatabaseInfo {
 	for i := range di.ContinuousQueries {
 		pb.ContinuousQueries[i] = di.ContinuousQueries[i].marshal()
 	}
+
+	if !di.DeletedAt.IsZero() {
+		pb.DeletedAt = proto.Int64(MarshalTime(di.DeletedAt))
+	}
 	return pb
 }
 
@@ -1074,6 +1116,10 @@ func (di *DatabaseInfo) unmarshal(pb *internal.DatabaseInfo) {
 			di.ContinuousQueries[i].unmarshal(x)
 		}
 	}
+
+	if pb.DeletedAt != nil {
+		di.DeletedAt = UnmarshalTime(pb.GetDeletedAt())
+	}
 }
 
 // RetentionPolicySpec represents the specification for a new retention policy.
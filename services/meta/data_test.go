@@ -85,6 +85,47 @@ func Test_Data_CreateDatabase(t *testing.T) {
 	}
 }
 
+func Test_Data_MarkDatabaseDeleted(t *testing.T) {
+	data := meta.Data{}
+	if err := data.CreateDatabase("db0"); err != nil {
+		t.Fatal(err)
+	}
+
+	deletedAt := time.Unix(0, 1000000000).UTC()
+	if err := data.MarkDatabaseDeleted("db0", deletedAt); err != nil {
+		t.Fatal(err)
+	} else if di := data.Database("db0"); !di.Deleted() || !di.DeletedAt.Equal(deletedAt) {
+		t.Fatalf("unexpected deleted at: %v", di.DeletedAt)
+	}
+
+	// The deleted time is persisted.
+	buf, err := data.MarshalBinary()
+	if err != nil {
+		t.Fatal(err)
+	}
+	var other meta.Data
+	if err := other.UnmarshalBinary(buf); err != nil {
+		t.Fatal(err)
+	} else if got, exp := other.Database("db0").DeletedAt, deletedAt; !got.Equal(exp) {
+		t.Fatalf("got %v, expected %v", got, exp)
+	}
+
+	// A deleted database cannot be recreated until it is restored.
+	if err := data.CreateDatabase("db0"); err != meta.ErrDatabaseDeleted {
+		t.Fatalf("exp: %v, got: %v", meta.ErrDatabaseDeleted, err)
+	}
+
+	if err := data.RestoreDatabase("db0"); err != nil {
+		t.Fatal(err)
+	} else if di := data.Database("db0"); di.Deleted() {
+		t.Fatalf("unexpected deleted at: %v", di.DeletedAt)
+	} else if err := data.RestoreDatabase("db0"); err != meta.ErrDatabaseNotDeleted {
+		t.Fatalf("exp: %v, got: %v", meta.ErrDatabaseNotDeleted, err)
+	} else if err := data.CreateDatabase("db0"); err != nil {
+		t.Fatal(err)
+	}
+}
+
 func Test_Data_CreateRetentionPolicy(t *testing.T) {
 	data := meta.Data{}
 
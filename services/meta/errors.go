@@ -29,6 +29,12 @@ var (
 
 	// ErrInvalidName is returned when attempting to create a database or retention policy with an invalid name
 	ErrInvalidName = errors.New("invalid name")
+
+	// ErrDatabaseDeleted is returned when creating or writing to a soft-deleted database.
+	ErrDatabaseDeleted = errors.New("database is deleted")
+
+	// ErrDatabaseNotDeleted is returned when restoring a database that is not soft-deleted.
+	ErrDatabaseNotDeleted = errors.New("database is not deleted")
 )
 
 var (
@@ -2,6 +2,7 @@ package retention
 
 import (
 	"errors"
+	"fmt"
 	"time"
 
 	"github.com/influxdata/influxdb/monitor/diagnostics"
@@ -12,6 +13,55 @@ import (
 type Config struct {
 	Enabled       bool          `toml:"enabled"`
 	CheckInterval toml.Duration `toml:"check-interval"`
+
+	// DeleteGracePeriod is how long the shards of an expired shard group are
+	// kept on local storage after the group has been marked deleted. The data
+	// is no longer queryable during this period but can still be recovered
+	// from disk. A value of 0 removes the shards on the same check.
+	DeleteGracePeriod toml.Duration `toml:"delete-grace-period"`
+
+	// OrphanedShardGracePeriod is how long a local shard must remain
+	// unreferenced by the meta store before it is removed. Orphaned shards are
+	// left behind by failed shard deletions and interrupted restores. A value
+	// of 0 disables the orphaned shard check.
+	OrphanedShardGracePeriod toml.Duration `toml:"orphaned-shard-grace-period"`
+
+	// OrphanedShardDryRun logs the orphaned shards that would be removed
+	// instead of removing them.
+	OrphanedShardDryRun bool `toml:"orphaned-shard-dry-run"`
+
+	// DatabaseDeleteGracePeriod is how long a dropped database is kept,
+	// rejecting writes, before it is removed. It can be restored during this
+	// period. A value of 0 removes databases when they are dropped.
+	DatabaseDeleteGracePeriod toml.Duration `toml:"database-delete-grace-period"`
+
+	// FieldTTLs remove the values of individual fields sooner than the
+	// retention policy of their database removes the rest of the data.
+	FieldTTLs []FieldTTL `toml:"field-ttl"`
+}
+
+// FieldTTL is the configuration of a field whose values are removed once they
+// are older than a duration. Removed values are tombstoned by the retention
+// check and dropped from the TSM files when the shard is next compacted.
+type FieldTTL struct {
+	Database    string        `toml:"database"`
+	Measurement string        `toml:"measurement"`
+	Field       string        `toml:"field"`
+	Duration    toml.Duration `toml:"duration"`
+}
+
+// Validate returns an error if the field TTL is invalid.
+func (f FieldTTL) Validate() error {
+	if f.Database == "" {
+		return errors.New("field-ttl database must be specified")
+	} else if f.Measurement == "" {
+		return errors.New("field-ttl measurement must be specified")
+	} else if f.Field == "" {
+		return errors.New("field-ttl field must be specified")
+	} else if f.Duration <= 0 {
+		return fmt.Errorf("field-ttl duration for field %q must be positive", f.Field)
+	}
+	return nil
 }
 
 // NewConfig returns an instance of Config with defaults.
@@ -31,6 +81,24 @@ func (c Config) Validate() error {
 		return errors.New("check-interval must be positive")
 	}
 
+	if c.DeleteGracePeriod < 0 {
+		return errors.New("delete-grace-period must be non-negative")
+	}
+
+	if c.OrphanedShardGracePeriod < 0 {
+		return errors.New("orphaned-shard-grace-period must be non-negative")
+	}
+
+	if c.DatabaseDeleteGracePeriod < 0 {
+		return errors.New("database-delete-grace-period must be non-negative")
+	}
+
+	for _, f := range c.FieldTTLs {
+		if err := f.Validate(); err != nil {
+			return err
+		}
+	}
+
 	return nil
 }
 
@@ -43,7 +111,12 @@ func (c Config) Diagnostics() (*diagnostics.Diagnostics, error) {
 	}
 
 	return diagnostics.RowFromMap(map[string]interface{}{
-		"enabled":        true,
-		"check-interval": c.CheckInterval,
+		"enabled":                      true,
+		"check-interval":               c.CheckInterval,
+		"delete-grace-period":          c.DeleteGracePeriod,
+		"orphaned-shard-grace-period":  c.OrphanedShardGracePeriod,
+		"orphaned-shard-dry-run":       c.OrphanedShardDryRun,
+		"database-delete-grace-period": c.DatabaseDeleteGracePeriod,
+		"field-ttls":                   len(c.FieldTTLs),
 	}), nil
 }
@@ -6,6 +6,7 @@ import (
 
 	"github.com/BurntSushi/toml"
 	"github.com/influxdata/influxdb/services/retention"
+	itoml "github.com/influxdata/influxdb/toml"
 )
 
 func TestConfig_Parse(t *testing.T) {
@@ -14,6 +15,7 @@ func TestConfig_Parse(t *testing.T) {
 	if _, err := toml.Decode(`
 enabled = true
 check-interval = "1s"
+delete-grace-period = "1h"
 `, &c); err != nil {
 		t.Fatal(err)
 	}
@@ -23,6 +25,8 @@ check-interval = "1s"
 		t.Fatalf("unexpected enabled state: %v", c.Enabled)
 	} else if time.Duration(c.CheckInterval) != time.Second {
 		t.Fatalf("unexpected check interval: %v", c.CheckInterval)
+	} else if time.Duration(c.DeleteGracePeriod) != time.Hour {
+		t.Fatalf("unexpected delete grace period: %v", c.DeleteGracePeriod)
 	}
 }
 
@@ -44,6 +48,18 @@ func TestConfig_Validate(t *testing.T) {
 		t.Fatal("expected error for negative check-interval, got nil")
 	}
 
+	c = retention.NewConfig()
+	c.DeleteGracePeriod = itoml.Duration(-time.Hour)
+	if err := c.Validate(); err == nil {
+		t.Fatal("expected error for negative delete-grace-period, got nil")
+	}
+
+	c = retention.NewConfig()
+	c.FieldTTLs = []retention.FieldTTL{{Database: "db0", Measurement: "m0", Field: "f0"}}
+	if err := c.Validate(); err == nil {
+		t.Fatal("expected error for field-ttl without duration, got nil")
+	}
+
 	c.Enabled = false
 	if err := c.Validate(); err != nil {
 		t.Fatalf("unexpected validation fail from disabled config: %s", err)
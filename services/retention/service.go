@@ -4,18 +4,28 @@ package retention // import "github.com/influxdata/influxdb/services/retention"
 import (
 	"errors"
 	"fmt"
+	"math"
 	"sync"
+	"sync/atomic"
 	"time"
 
 	"github.com/influxdata/influxdb/logger"
+	"github.com/influxdata/influxdb/models"
 	"github.com/influxdata/influxdb/services/meta"
 	"github.com/influxdata/influxdb/tsdb"
 	"go.uber.org/zap"
 )
 
+// Statistics for the retention service.
+const (
+	statOrphanedShards        = "orphanedShards"
+	statOrphanedShardsDeleted = "orphanedShardsDeleted"
+)
+
 type MetaClient interface {
 	Databases() []meta.DatabaseInfo
 	DeleteShardGroup(database, policy string, id uint64) error
+	DropDatabase(name string) error
 	DropShard(id uint64) error
 	PruneShardGroups() error
 }
@@ -29,6 +39,9 @@ type Service struct {
 
 		SetShardNewReadersBlocked(shardID uint64, blocked bool) error
 		ShardInUse(shardID uint64) (bool, error)
+
+		DeleteFieldRange(database, name, field string, min, max int64) error
+		DeleteDatabase(name string) error
 	}
 
 	// DropShardRef is a function that takes a shard ID and removes the
@@ -41,8 +54,17 @@ type Service struct {
 	// a lot of unnecessary RPC calls.
 	DropShardMetaRef func(shardID uint64, owners []uint64) error
 
+	// Now returns the current time of the checks. It defaults to time.Now
+	// and can be replaced to simulate the passing of time in tests.
+	Now func() time.Time
+
 	config Config
 
+	// orphans maps the ID of each local shard not referenced by the meta
+	// store to the time it was first found unreferenced.
+	orphans map[uint64]time.Time
+	stats   *Statistics
+
 	wg   sync.WaitGroup
 	done chan struct{}
 
@@ -52,8 +74,11 @@ type Service struct {
 // NewService returns a configured retention policy enforcement service.
 func NewService(c Config) *Service {
 	return &Service{
-		config: c,
-		logger: zap.NewNop(),
+		Now:     time.Now,
+		config:  c,
+		orphans: make(map[uint64]time.Time),
+		stats:   &Statistics{},
+		logger:  zap.NewNop(),
 	}
 }
 
@@ -102,6 +127,24 @@ func (s *Service) WithLogger(log *zap.Logger) {
 	s.logger = log.With(zap.String("service", "retention"))
 }
 
+// Statistics maintains the statistics for the retention service.
+type Statistics struct {
+	OrphanedShards        int64
+	OrphanedShardsDeleted int64
+}
+
+// Statistics returns statistics for periodic monitoring.
+func (s *Service) Statistics(tags map[string]string) []models.Statistic {
+	return []models.Statistic{{
+		Name: "retention",
+		Tags: tags,
+		Values: map[string]interface{}{
+			statOrphanedShards:        atomic.LoadInt64(&s.stats.OrphanedShards),
+			statOrphanedShardsDeleted: atomic.LoadInt64(&s.stats.OrphanedShardsDeleted),
+		},
+	}}
+}
+
 func (s *Service) run() {
 	ticker := time.NewTicker(time.Duration(s.config.CheckInterval))
 	defer ticker.Stop()
@@ -111,11 +154,25 @@ func (s *Service) run() {
 			return
 
 		case <-ticker.C:
-			s.DeletionCheck()
+			s.Check()
 		}
 	}
 }
 
+// Check runs each enabled retention check once.
+func (s *Service) Check() {
+	s.DeletionCheck()
+	if s.config.OrphanedShardGracePeriod > 0 {
+		s.OrphanCheck()
+	}
+	if len(s.config.FieldTTLs) > 0 {
+		s.FieldTTLCheck()
+	}
+	if s.config.DatabaseDeleteGracePeriod > 0 {
+		s.DatabaseDeletionCheck()
+	}
+}
+
 func (s *Service) DeletionCheck() {
 	log, logEnd := logger.NewOperation(s.logger, "Retention policy deletion check", "retention_delete_check")
 	defer logEnd()
@@ -139,18 +196,24 @@ func (s *Service) DeletionCheck() {
 	// Without the message, they may see the error message and assume they
 	// have to do it manually.
 	var retryNeeded bool
+	now := s.Now().UTC()
 	dbs := s.MetaClient.Databases()
 	for _, d := range dbs {
 		for _, r := range d.RetentionPolicies {
 			// Build list of already deleted shards.
 			for _, g := range r.DeletedShardGroups() {
+				if !s.gracePeriodElapsed(g.DeletedAt, now) {
+					log.Debug("Retaining shards of deleted shard group during grace period",
+						logger.Database(d.Name), logger.ShardGroup(g.ID), logger.RetentionPolicy(r.Name))
+					continue
+				}
 				for _, sh := range g.Shards {
 					deletedShardIDs[sh.ID] = newDeletionInfo(d.Name, r.Name, sh)
 				}
 			}
 
 			// Determine all shards that have expired and need to be deleted.
-			for _, g := range r.ExpiredShardGroups(time.Now().UTC()) {
+			for _, g := range r.ExpiredShardGroups(now) {
 				func() {
 					log, logEnd := logger.NewOperation(log, "Deleting expired shard group", "retention_delete_expired_shard_group",
 						logger.Database(d.Name), logger.ShardGroup(g.ID), logger.RetentionPolicy(r.Name))
@@ -163,6 +226,14 @@ func (s *Service) DeletionCheck() {
 
 					log.Info("Deleted shard group")
 
+					// Shards are only removed here if there is no grace period, otherwise
+					// a later check picks them up from the deleted shard groups.
+					if !s.gracePeriodElapsed(now, now) {
+						log.Info("Group's shards will be removed from local storage after the grace period",
+							logger.DurationLiteral("delete_grace_period", time.Duration(s.config.DeleteGracePeriod)))
+						return
+					}
+
 					// Store all the shard IDs that may possibly need to be removed locally.
 					groupShards := make([]uint64, len(g.Shards))
 					for _, sh := range g.Shards {
@@ -269,3 +340,126 @@ func (s *Service) DeletionCheck() {
 		log.Info("One or more errors occurred during shard deletion and will be retried on the next check", logger.DurationLiteral("check_interval", time.Duration(s.config.CheckInterval)))
 	}
 }
+
+// DatabaseDeletionCheck removes the databases that were soft-deleted longer
+// than the database delete grace period ago.
+func (s *Service) DatabaseDeletionCheck() {
+	log, logEnd := logger.NewOperation(s.logger, "Retention policy database deletion check", "retention_database_delete_check")
+	defer logEnd()
+
+	now := s.Now().UTC()
+	for _, d := range s.MetaClient.Databases() {
+		if !d.Deleted() || d.DeletedAt.Add(time.Duration(s.config.DatabaseDeleteGracePeriod)).After(now) {
+			continue
+		}
+
+		if err := s.TSDBStore.DeleteDatabase(d.Name); err != nil {
+			log.Error("Error deleting database from store", logger.Database(d.Name), zap.Error(err))
+			continue
+		}
+		if err := s.MetaClient.DropDatabase(d.Name); err != nil {
+			log.Error("Error dropping database", logger.Database(d.Name), zap.Error(err))
+			continue
+		}
+		log.Info("Deleted database after its grace period", logger.Database(d.Name), zap.Time("deleted_at", d.DeletedAt))
+	}
+}
+
+// FieldTTLCheck removes the values of each field with a TTL that are older
+// than its duration. Each check removes all of them, rather than only those
+// that expired since the previous check, so values written late or
+// backfilled with old timestamps expire too. Values already removed by a
+// previous check are not tombstoned again.
+func (s *Service) FieldTTLCheck() {
+	log, logEnd := logger.NewOperation(s.logger, "Retention policy field TTL check", "retention_field_ttl_check")
+	defer logEnd()
+
+	now := s.Now().UTC()
+	for _, f := range s.config.FieldTTLs {
+		max := now.Add(-time.Duration(f.Duration)).UnixNano()
+		if err := s.TSDBStore.DeleteFieldRange(f.Database, f.Measurement, f.Field, math.MinInt64, max); err != nil {
+			log.Error("Error removing expired field values", logger.Database(f.Database),
+				zap.String("measurement", f.Measurement), zap.String("field", f.Field), zap.Error(err))
+		}
+	}
+}
+
+// gracePeriodElapsed returns true if a shard group deleted at deletedAt may
+// have its shards removed from local storage at now.
+func (s *Service) gracePeriodElapsed(deletedAt, now time.Time) bool {
+	return !deletedAt.Add(time.Duration(s.config.DeleteGracePeriod)).After(now)
+}
+
+// OrphanCheck removes local shards that have not been referenced by the meta
+// store for longer than the orphaned shard grace period.
+func (s *Service) OrphanCheck() {
+	log, logEnd := logger.NewOperation(s.logger, "Retention policy orphaned shard check", "retention_orphan_check")
+	defer logEnd()
+
+	referenced := make(map[uint64]struct{})
+	for _, d := range s.MetaClient.Databases() {
+		for _, r := range d.RetentionPolicies {
+			for _, g := range r.ShardGroups {
+				for _, sh := range g.Shards {
+					referenced[sh.ID] = struct{}{}
+				}
+			}
+		}
+	}
+
+	now := s.Now().UTC()
+	orphans := make(map[uint64]time.Time)
+	for _, id := range s.TSDBStore.ShardIDs() {
+		if _, ok := referenced[id]; ok {
+			continue
+		}
+		firstSeen, ok := s.orphans[id]
+		if !ok {
+			firstSeen = now
+			log.Info("Found local shard not referenced by meta store", logger.Shard(id))
+		}
+		orphans[id] = firstSeen
+	}
+	s.orphans = orphans
+	atomic.StoreInt64(&s.stats.OrphanedShards, int64(len(orphans)))
+
+	for id, firstSeen := range orphans {
+		if firstSeen.Add(time.Duration(s.config.OrphanedShardGracePeriod)).After(now) {
+			continue
+		}
+
+		if s.config.OrphanedShardDryRun {
+			log.Info("Orphaned shard would be deleted (dry run)", logger.Shard(id), zap.Time("first_seen", firstSeen))
+			continue
+		}
+
+		err := func() (rErr error) {
+			if err := s.TSDBStore.SetShardNewReadersBlocked(id, true); err != nil {
+				return fmt.Errorf("error blocking new readers for shard: %w", err)
+			}
+			defer func() {
+				if rErr != nil && !errors.Is(rErr, tsdb.ErrShardNotFound) {
+					if unblockErr := s.TSDBStore.SetShardNewReadersBlocked(id, false); unblockErr != nil {
+						log.Error("Error unblocking new readers for shard", logger.Shard(id), zap.Error(unblockErr))
+					}
+				}
+			}()
+
+			if inUse, err := s.TSDBStore.ShardInUse(id); err != nil {
+				return fmt.Errorf("error checking if shard is in-use: %w", err)
+			} else if inUse {
+				return errors.New("can not delete an in-use shard")
+			}
+			return s.TSDBStore.DeleteShard(id)
+		}()
+		if err != nil && !errors.Is(err, tsdb.ErrShardNotFound) {
+			log.Error("Error deleting orphaned shard", logger.Shard(id), zap.Error(err))
+			continue
+		}
+
+		delete(s.orphans, id)
+		atomic.AddInt64(&s.stats.OrphanedShardsDeleted, 1)
+		log.Info("Deleted orphaned shard", logger.Shard(id))
+	}
+	atomic.StoreInt64(&s.stats.OrphanedShards, int64(len(s.orphans)))
+}
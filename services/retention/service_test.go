@@ -306,6 +306,266 @@ func TestRetention_DeletionCheck(t *testing.T) {
 	require.NoError(t, s.Close())
 }
 
+func TestRetention_DeletionCheck_GracePeriod(t *testing.T) {
+	cfg := retention.NewConfig()
+	cfg.CheckInterval = toml.Duration(24 * time.Hour)
+	cfg.DeleteGracePeriod = toml.Duration(time.Hour)
+
+	now := time.Now().UTC()
+	data := &meta.Data{
+		Databases: []meta.DatabaseInfo{
+			{
+				Name:                   "db0",
+				DefaultRetentionPolicy: "rp0",
+				RetentionPolicies: []meta.RetentionPolicyInfo{
+					{
+						Name:               "rp0",
+						Duration:           time.Hour,
+						ShardGroupDuration: time.Hour,
+						ShardGroups: []meta.ShardGroupInfo{
+							// Shard group 1 was deleted within the grace period.
+							{
+								ID:        1,
+								StartTime: now.Add(-4 * time.Hour),
+								EndTime:   now.Add(-3 * time.Hour),
+								DeletedAt: now.Add(-30 * time.Minute),
+								Shards:    []meta.ShardInfo{{ID: 2}},
+							},
+							// Shard group 3 was deleted before the grace period.
+							{
+								ID:        3,
+								StartTime: now.Add(-6 * time.Hour),
+								EndTime:   now.Add(-5 * time.Hour),
+								DeletedAt: now.Add(-2 * time.Hour),
+								Shards:    []meta.ShardInfo{{ID: 4}},
+							},
+							// Shard group 5 expires during this check.
+							{
+								ID:        5,
+								StartTime: now.Add(-3 * time.Hour),
+								EndTime:   now.Add(-2 * time.Hour),
+								Shards:    []meta.ShardInfo{{ID: 6}},
+							},
+						},
+					},
+				},
+			},
+		},
+	}
+
+	s := NewService(cfg)
+	s.MetaClient.DatabasesFn = func() []meta.DatabaseInfo { return data.Databases }
+	s.MetaClient.DeleteShardGroupFn = func(database, policy string, id uint64) error {
+		return helpers.DataDeleteShardGroup(data, now, database, policy, id)
+	}
+	s.MetaClient.DropShardFn = func(id uint64) error {
+		data.DropShard(id)
+		return nil
+	}
+	s.MetaClient.PruneShardGroupsFn = func() error { return nil }
+
+	localShards := map[uint64]struct{}{2: {}, 4: {}, 6: {}}
+	s.TSDBStore.ShardIDsFn = func() []uint64 { return maps.Keys(localShards) }
+	s.TSDBStore.SetShardNewReadersBlockedFn = func(shardID uint64, blocked bool) error { return nil }
+	s.TSDBStore.ShardInUseFn = func(shardID uint64) (bool, error) { return false, nil }
+	s.TSDBStore.DeleteShardFn = func(shardID uint64) error {
+		delete(localShards, shardID)
+		return nil
+	}
+
+	s.DeletionCheck()
+
+	// Only the shard of the group deleted before the grace period is removed.
+	require.Equal(t, map[uint64]struct{}{2: {}, 6: {}}, localShards)
+	require.True(t, data.Databases[0].RetentionPolicies[0].ShardGroups[2].Deleted())
+}
+
+func TestRetention_OrphanCheck(t *testing.T) {
+	for _, dryRun := range []bool{false, true} {
+		t.Run(fmt.Sprintf("dry-run=%v", dryRun), func(t *testing.T) {
+			cfg := retention.NewConfig()
+			cfg.OrphanedShardGracePeriod = toml.Duration(time.Nanosecond)
+			cfg.OrphanedShardDryRun = dryRun
+
+			data := &meta.Data{
+				Databases: []meta.DatabaseInfo{
+					{
+						Name: "db0",
+						RetentionPolicies: []meta.RetentionPolicyInfo{
+							{
+								Name: "rp0",
+								ShardGroups: []meta.ShardGroupInfo{
+									{ID: 1, Shards: []meta.ShardInfo{{ID: 2}}},
+								},
+							},
+						},
+					},
+				},
+			}
+
+			s := NewService(cfg)
+			s.MetaClient.DatabasesFn = func() []meta.DatabaseInfo { return data.Databases }
+
+			localShards := map[uint64]struct{}{2: {}, 3: {}}
+			s.TSDBStore.ShardIDsFn = func() []uint64 { return maps.Keys(localShards) }
+			s.TSDBStore.SetShardNewReadersBlockedFn = func(shardID uint64, blocked bool) error { return nil }
+			s.TSDBStore.ShardInUseFn = func(shardID uint64) (bool, error) { return false, nil }
+			s.TSDBStore.DeleteShardFn = func(shardID uint64) error {
+				delete(localShards, shardID)
+				return nil
+			}
+
+			// The first check only records the orphan.
+			s.OrphanCheck()
+			require.Equal(t, map[uint64]struct{}{2: {}, 3: {}}, localShards)
+
+			// The grace period has elapsed by the second check.
+			time.Sleep(time.Millisecond)
+			s.OrphanCheck()
+
+			stats := s.Statistics(nil)[0].Values
+			if dryRun {
+				require.Equal(t, map[uint64]struct{}{2: {}, 3: {}}, localShards)
+				require.Equal(t, int64(1), stats["orphanedShards"])
+				require.Equal(t, int64(0), stats["orphanedShardsDeleted"])
+			} else {
+				require.Equal(t, map[uint64]struct{}{2: {}}, localShards)
+				require.Equal(t, int64(0), stats["orphanedShards"])
+				require.Equal(t, int64(1), stats["orphanedShardsDeleted"])
+			}
+		})
+	}
+}
+
+func TestRetention_FieldTTLCheck(t *testing.T) {
+	cfg := retention.NewConfig()
+	cfg.FieldTTLs = []retention.FieldTTL{
+		{Database: "db0", Measurement: "requests", Field: "payload", Duration: toml.Duration(time.Hour)},
+	}
+
+	now := time.Date(2020, 1, 1, 0, 0, 0, 0, time.UTC)
+	s := NewService(cfg)
+	s.Now = func() time.Time { return now }
+
+	// The timestamps of the values of the field stored.
+	values := map[int64]struct{}{
+		now.Add(-2 * time.Hour).UnixNano(): {},
+		now.UnixNano():                     {},
+	}
+	s.TSDBStore.DeleteFieldRangeFn = func(database, name, field string, min, max int64) error {
+		require.Equal(t, []string{"db0", "requests", "payload"}, []string{database, name, field})
+		require.Equal(t, int64(math.MinInt64), min)
+		require.Equal(t, s.Now().Add(-time.Hour).UnixNano(), max)
+		for ts := range values {
+			if ts >= min && ts <= max {
+				delete(values, ts)
+			}
+		}
+		return nil
+	}
+
+	s.FieldTTLCheck()
+	require.Equal(t, map[int64]struct{}{now.UnixNano(): {}}, values)
+
+	// A value backfilled after the check, older than the values it removed,
+	// is removed by the next check.
+	values[now.Add(-3*time.Hour).UnixNano()] = struct{}{}
+	now = now.Add(time.Minute)
+	s.FieldTTLCheck()
+	require.Equal(t, map[int64]struct{}{now.Add(-time.Minute).UnixNano(): {}}, values)
+}
+
+func TestRetention_Check_SimulatedTime(t *testing.T) {
+	cfg := retention.NewConfig()
+	cfg.OrphanedShardGracePeriod = toml.Duration(6 * time.Hour)
+	cfg.FieldTTLs = []retention.FieldTTL{
+		{Database: "db0", Measurement: "requests", Field: "payload", Duration: toml.Duration(time.Hour)},
+	}
+
+	start := time.Date(2020, 1, 1, 0, 0, 0, 0, time.UTC)
+	now := start
+
+	s := NewService(cfg)
+	s.Now = func() time.Time { return now }
+	s.MetaClient.DatabasesFn = func() []meta.DatabaseInfo {
+		return []meta.DatabaseInfo{
+			{
+				Name: "db0",
+				RetentionPolicies: []meta.RetentionPolicyInfo{
+					{
+						Name: "rp0",
+						ShardGroups: []meta.ShardGroupInfo{
+							{ID: 1, Shards: []meta.ShardInfo{{ID: 2}}},
+						},
+					},
+				},
+			},
+		}
+	}
+	s.MetaClient.PruneShardGroupsFn = func() error { return nil }
+
+	localShards := map[uint64]struct{}{2: {}, 3: {}}
+	var orphanDeletedAt time.Time
+	s.TSDBStore.ShardIDsFn = func() []uint64 { return maps.Keys(localShards) }
+	s.TSDBStore.SetShardNewReadersBlockedFn = func(shardID uint64, blocked bool) error { return nil }
+	s.TSDBStore.ShardInUseFn = func(shardID uint64) (bool, error) { return false, nil }
+	s.TSDBStore.DeleteShardFn = func(shardID uint64) error {
+		require.Equal(t, uint64(3), shardID)
+		delete(localShards, shardID)
+		orphanDeletedAt = now
+		return nil
+	}
+
+	var cutoffs []int64
+	s.TSDBStore.DeleteFieldRangeFn = func(database, name, field string, min, max int64) error {
+		require.Equal(t, int64(math.MinInt64), min)
+		cutoffs = append(cutoffs, max)
+		return nil
+	}
+
+	// Simulate a day of checks every 30 minutes.
+	for i := 0; i < 48; i++ {
+		s.Check()
+		now = now.Add(30 * time.Minute)
+	}
+
+	require.Equal(t, start.Add(6*time.Hour), orphanDeletedAt)
+	require.Len(t, cutoffs, 48)
+	for i, max := range cutoffs {
+		require.Equal(t, start.Add(time.Duration(i)*30*time.Minute-time.Hour).UnixNano(), max)
+	}
+}
+
+func TestRetention_DatabaseDeletionCheck(t *testing.T) {
+	cfg := retention.NewConfig()
+	cfg.DatabaseDeleteGracePeriod = toml.Duration(time.Hour)
+
+	now := time.Now().UTC()
+	s := NewService(cfg)
+	s.MetaClient.DatabasesFn = func() []meta.DatabaseInfo {
+		return []meta.DatabaseInfo{
+			{Name: "db0"},
+			{Name: "db1", DeletedAt: now.Add(-2 * time.Hour)},
+			{Name: "db2", DeletedAt: now.Add(-time.Minute)},
+		}
+	}
+
+	var storeDeleted, metaDropped []string
+	s.TSDBStore.DeleteDatabaseFn = func(name string) error {
+		storeDeleted = append(storeDeleted, name)
+		return nil
+	}
+	s.MetaClient.DropDatabaseFn = func(name string) error {
+		metaDropped = append(metaDropped, name)
+		return nil
+	}
+
+	// Only the database deleted before the grace period is removed.
+	s.DatabaseDeletionCheck()
+	require.Equal(t, []string{"db1"}, storeDeleted)
+	require.Equal(t, []string{"db1"}, metaDropped)
+}
+
 func TestService_CheckShards(t *testing.T) {
 	now := time.Now()
 	// Account for any time difference that could cause some of the logic in
@@ -3,6 +3,7 @@ package tsdb
 import (
 	"errors"
 	"fmt"
+	"strings"
 	"time"
 
 	"github.com/influxdata/influxdb/monitor/diagnostics"
@@ -113,6 +114,16 @@ type Config struct {
 	CompactThroughput              toml.Size     `toml:"compact-throughput"`
 	CompactThroughputBurst         toml.Size     `toml:"compact-throughput-burst"`
 
+	// CacheSnapshotTotalMemorySize is the combined size of the caches of all shards
+	// above which the largest caches are snapshotted, regardless of their own size.
+	// A value of 0 disables the limit.
+	CacheSnapshotTotalMemorySize toml.Size `toml:"cache-snapshot-total-memory-size"`
+
+	// CompactWindow is the daily window of local time, formatted as "HH:MM-HH:MM",
+	// during which level and full compactions run. An empty value lets them run at
+	// any time. Snapshot compactions are not affected.
+	CompactWindow string `toml:"compact-window"`
+
 	// Options for ingress metrics
 	IngressMetricByMeasurement bool `toml:"ingress-metric-by-measurement-enabled"`
 	IngressMetricByLogin       bool `toml:"ingress-metric-by-login-enabled"`
@@ -124,6 +135,11 @@ type Config struct {
 	// A value of 0 disables the limit. This limit only applies when using the "inmem" index.
 	MaxSeriesPerDatabase int `toml:"max-series-per-database"`
 
+	// MaxSeriesPerMeasurement is the maximum number of series a node can hold per measurement.
+	// When this limit is exceeded, points creating new series in the measurement are dropped.
+	// A value of 0 disables the limit. This limit only applies when using the "inmem" index.
+	MaxSeriesPerMeasurement int `toml:"max-series-per-measurement"`
+
 	// MaxValuesPerTag is the maximum number of tag values a single tag key can have within
 	// a measurement.  When the limit is execeeded, writes return an error.
 	// A value of 0 disables the limit.
@@ -209,6 +225,10 @@ func (c *Config) Validate() error {
 		return errors.New("max-concurrent-compactions must be non-negative")
 	}
 
+	if _, err := ParseCompactWindow(c.CompactWindow); err != nil {
+		return err
+	}
+
 	if c.MaxConcurrentDeletes <= 0 {
 		return errors.New("max-concurrent-deletes must be positive")
 	}
@@ -256,8 +276,11 @@ func (c Config) Diagnostics() (*diagnostics.Diagnostics, error) {
 		"cache-max-memory-size":                  c.CacheMaxMemorySize,
 		"cache-snapshot-memory-size":             c.CacheSnapshotMemorySize,
 		"cache-snapshot-write-cold-duration":     c.CacheSnapshotWriteColdDuration,
+		"cache-snapshot-total-memory-size":       c.CacheSnapshotTotalMemorySize,
 		"compact-full-write-cold-duration":       c.CompactFullWriteColdDuration,
+		"compact-window":                         c.CompactWindow,
 		"max-series-per-database":                c.MaxSeriesPerDatabase,
+		"max-series-per-measurement":             c.MaxSeriesPerMeasurement,
 		"max-values-per-tag":                     c.MaxValuesPerTag,
 		"max-concurrent-compactions":             c.MaxConcurrentCompactions,
 		"max-index-log-file-size":                c.MaxIndexLogFileSize,
@@ -265,3 +288,49 @@ func (c Config) Diagnostics() (*diagnostics.Diagnostics, error) {
 		"series-file-max-concurrent-compactions": c.SeriesFileMaxConcurrentSnapshotCompactions,
 	}), nil
 }
+
+// CompactWindow is a daily window of local time during which level and full
+// compactions run. The window wraps around midnight if it ends before it
+// starts. The zero value is open at any time.
+type CompactWindow struct {
+	Start time.Duration // offset from midnight
+	End   time.Duration // offset from midnight
+}
+
+// ParseCompactWindow parses a window formatted as "HH:MM-HH:MM".
+// An empty string returns the zero window.
+func ParseCompactWindow(s string) (CompactWindow, error) {
+	if s == "" {
+		return CompactWindow{}, nil
+	}
+
+	start, end, ok := strings.Cut(s, "-")
+	if !ok {
+		return CompactWindow{}, fmt.Errorf("compact-window %q must be formatted as HH:MM-HH:MM", s)
+	}
+	t0, err := time.Parse("15:04", start)
+	if err != nil {
+		return CompactWindow{}, fmt.Errorf("compact-window %q must be formatted as HH:MM-HH:MM", s)
+	}
+	t1, err := time.Parse("15:04", end)
+	if err != nil {
+		return CompactWindow{}, fmt.Errorf("compact-window %q must be formatted as HH:MM-HH:MM", s)
+	}
+	return CompactWindow{
+		Start: time.Duration(t0.Hour())*time.Hour + time.Duration(t0.Minute())*time.Minute,
+		End:   time.Duration(t1.Hour())*time.Hour + time.Duration(t1.Minute())*time.Minute,
+	}, nil
+}
+
+// Contains returns true if t is within the window.
+func (w CompactWindow) Contains(t time.Time) bool {
+	if w.Start == w.End {
+		return true
+	}
+
+	d := time.Duration(t.Hour())*time.Hour + time.Duration(t.Minute())*time.Minute + time.Duration(t.Second())*time.Second
+	if w.Start < w.End {
+		return d >= w.Start && d < w.End
+	}
+	return d >= w.Start || d < w.End
+}
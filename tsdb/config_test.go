@@ -75,6 +75,40 @@ func TestConfig_Validate_Error(t *testing.T) {
 	if err := c.Validate(); err == nil || err.Error() != "series-id-set-cache-size must be non-negative" {
 		t.Errorf("unexpected error: %s", err)
 	}
+
+	c.SeriesIDSetCacheSize = 0
+	c.CompactWindow = "22:00"
+	if err := c.Validate(); err == nil || err.Error() != `compact-window "22:00" must be formatted as HH:MM-HH:MM` {
+		t.Errorf("unexpected error: %s", err)
+	}
+}
+
+func TestCompactWindow_Contains(t *testing.T) {
+	at := func(hour, min int) time.Time {
+		return time.Date(2000, 1, 1, hour, min, 0, 0, time.Local)
+	}
+
+	for _, tt := range []struct {
+		window string
+		t      time.Time
+		exp    bool
+	}{
+		{"", at(12, 0), true},
+		{"01:00-05:00", at(1, 0), true},
+		{"01:00-05:00", at(4, 59), true},
+		{"01:00-05:00", at(5, 0), false},
+		{"01:00-05:00", at(0, 59), false},
+		{"22:00-06:00", at(23, 30), true},
+		{"22:00-06:00", at(3, 0), true},
+		{"22:00-06:00", at(12, 0), false},
+	} {
+		w, err := tsdb.ParseCompactWindow(tt.window)
+		if err != nil {
+			t.Fatal(err)
+		} else if got := w.Contains(tt.t); got != tt.exp {
+			t.Errorf("%q contains %s: exp %v, got %v", tt.window, tt.t.Format("15:04"), tt.exp, got)
+		}
+	}
 }
 
 func TestConfig_ByteSizes(t *testing.T) {
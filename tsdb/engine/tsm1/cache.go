@@ -128,6 +128,19 @@ func (e *entry) filter(min, max int64) {
 	e.mu.Unlock()
 }
 
+// overlaps returns true if any values of the entry have timestamps between
+// min and max inclusive.
+func (e *entry) overlaps(min, max int64) bool {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+	for _, v := range e.values {
+		if ts := v.UnixNano(); ts >= min && ts <= max {
+			return true
+		}
+	}
+	return false
+}
+
 // size returns the size of this entry in bytes.
 func (e *entry) size() int {
 	e.mu.RLock()
@@ -68,7 +68,11 @@ var (
 	numberOfRefCursorsCounter  = metrics.MustRegisterCounter("cursors_ref", metrics.WithGroup(tsmGroup))
 	numberOfAuxCursorsCounter  = metrics.MustRegisterCounter("cursors_aux", metrics.WithGroup(tsmGroup))
 	numberOfCondCursorsCounter = metrics.MustRegisterCounter("cursors_cond", metrics.WithGroup(tsmGroup))
+	cacheValuesCounter         = metrics.MustRegisterCounter("cache_values", metrics.WithGroup(tsmGroup))
+	tsmValuesCounter           = metrics.MustRegisterCounter("tsm_values", metrics.WithGroup(tsmGroup))
+	dedupValuesCounter         = metrics.MustRegisterCounter("values_deduplicated", metrics.WithGroup(tsmGroup))
 	planningTimer              = metrics.MustRegisterTimer("planning_time", metrics.WithGroup(tsmGroup))
+	executionTimer             = metrics.MustRegisterTimer("execution_time", metrics.WithGroup(tsmGroup))
 )
 
 // NewContextWithMetricsGroup creates a new context with a tsm1 metrics.Group for tracking
@@ -92,7 +96,9 @@ const (
 	// deleteFlushThreshold is the size in bytes of a batch of series keys to delete.
 	deleteFlushThreshold = 50 * 1024 * 1024
 
-	// DoNotCompactFile is the name of the file that disables compactions.
+	// DoNotCompactFile is the name of the file that disables the level and full
+	// compactions of a shard when in its directory, or of all shards of a
+	// database when in the database directory.
 	DoNotCompactFile = "do_not_compact"
 )
 
@@ -191,6 +197,9 @@ type Engine struct {
 	// Controls whether to enabled compactions when the engine is open
 	enableCompactionsOnOpen bool
 
+	// The daily window during which level and full compactions run.
+	compactWindow tsdb.CompactWindow
+
 	stats *EngineStatistics
 
 	// Limiter for concurrent compactions.
@@ -235,6 +244,9 @@ func NewEngine(id uint64, idx tsdb.Index, path string, walPath string, sfile *ts
 		planner.SetFileStore(fs)
 	}
 
+	// The window was validated with the rest of the configuration.
+	compactWindow, _ := tsdb.ParseCompactWindow(opt.Config.CompactWindow)
+
 	logger := zap.NewNop()
 	stats := &EngineStatistics{}
 	e := &Engine{
@@ -256,6 +268,7 @@ func NewEngine(id uint64, idx tsdb.Index, path string, walPath string, sfile *ts
 		CacheFlushMemorySizeThreshold: uint64(opt.Config.CacheSnapshotMemorySize),
 		CacheFlushWriteColdDuration:   time.Duration(opt.Config.CacheSnapshotWriteColdDuration),
 		enableCompactionsOnOpen:       true,
+		compactWindow:                 compactWindow,
 		WALEnabled:                    opt.WALEnabled,
 		formatFileName:                DefaultFormatFileName,
 		stats:                         stats,
@@ -731,6 +744,11 @@ func (e *Engine) Statistics(tags map[string]string) []models.Statistic {
 	return statistics
 }
 
+// CacheSize returns the size in bytes of the cache, including snapshots being written.
+func (e *Engine) CacheSize() int64 {
+	return int64(e.Cache.Size())
+}
+
 // DiskSize returns the total size in bytes of all TSM and WAL segments on disk.
 func (e *Engine) DiskSize() int64 {
 	var walDiskSizeBytes int64
@@ -1899,6 +1917,112 @@ func (e *Engine) DeleteMeasurement(name []byte) error {
 	return e.DeleteSeriesRange(tsdb.NewSeriesIteratorAdapter(e.sfile, itr), math.MinInt64, math.MaxInt64)
 }
 
+// DeleteFieldRange removes the values of field between min and max (inclusive)
+// from all series of the measurement name. The series and the field remain in
+// the index; the values are tombstoned and dropped when the TSM files are
+// next compacted.
+func (e *Engine) DeleteFieldRange(name []byte, field string, min, max int64) error {
+	encodedName := models.EscapeMeasurement(name)
+	sep := len(encodedName)
+	suffix := append(append([]byte(nil), keyFieldSeparatorBytes...), field...)
+
+	// matches returns true if the composite key k is field of a series of name.
+	matches := func(k []byte) bool {
+		return bytes.HasPrefix(k, encodedName) && len(k) > sep &&
+			(k[sep] == ',' || k[sep] == keyFieldSeparator[0]) && bytes.HasSuffix(k, suffix)
+	}
+
+	// Disable and abort running level compactions so that the tombstones
+	// added to existing TSM files are not lost by a compaction rewriting them.
+	e.disableLevelCompactions(true)
+	defer e.enableLevelCompactions(true)
+
+	// Run the delete on each TSM file in parallel.
+	if err := e.FileStore.Apply(func(r TSMFile) error {
+		if !r.OverlapsTimeRange(min, max) {
+			return nil
+		}
+
+		batch := r.BatchDelete()
+		n := r.KeyCount()
+		for i := r.Seek(encodedName); i < n; i++ {
+			indexKey, _ := r.KeyAt(i)
+			if !bytes.HasPrefix(indexKey, encodedName) {
+				break
+			} else if !matches(indexKey) || tombstoned(r, indexKey, min, max) {
+				continue
+			}
+
+			if err := batch.DeleteRange([][]byte{indexKey}, min, max); err != nil {
+				batch.Rollback()
+				return err
+			}
+		}
+		return batch.Commit()
+	}); err != nil {
+		return err
+	}
+
+	// Find the keys in the cache and remove them.
+	var deleteKeys [][]byte
+	_ = e.Cache.ApplyEntryFn(func(k []byte, entry *entry) error {
+		if matches(k) && entry.overlaps(min, max) {
+			deleteKeys = append(deleteKeys, k)
+		}
+		return nil
+	})
+	if len(deleteKeys) == 0 {
+		return nil
+	}
+
+	// Sort the keys because ApplyEntryFn iterates over the keys randomly.
+	bytesutil.Sort(deleteKeys)
+
+	e.Cache.DeleteRange(deleteKeys, min, max)
+
+	if e.WALEnabled {
+		if _, err := e.WAL.DeleteRange(deleteKeys, min, max); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// tombstoned returns true if no values of key in r between min and max
+// remain, because the blocks of key holding them are already covered by the
+// tombstones of r.
+func tombstoned(r TSMFile, key []byte, min, max int64) bool {
+	var tombstones []TimeRange
+	for _, e := range r.Entries(key) {
+		if !e.OverlapsTimeRange(min, max) {
+			continue
+		}
+
+		lo, hi := e.MinTime, e.MaxTime
+		if lo < min {
+			lo = min
+		}
+		if hi > max {
+			hi = max
+		}
+
+		if tombstones == nil {
+			tombstones = r.TombstoneRange(key)
+		}
+		covered := false
+		for _, t := range tombstones {
+			if t.Min <= lo && t.Max >= hi {
+				covered = true
+				break
+			}
+		}
+		if !covered {
+			return false
+		}
+	}
+	return true
+}
+
 // ForEachMeasurementName iterates over each measurement name in the engine.
 func (e *Engine) ForEachMeasurementName(fn func(name []byte) error) error {
 	return e.index.ForEachMeasurementName(fn)
@@ -2110,9 +2234,13 @@ func (e *Engine) compact(wg *sync.WaitGroup) {
 			return
 
 		case <-t.C:
-			// See if compactions are disabled.
+			// See if compactions are disabled for the shard or its database.
 			doNotCompactFile := filepath.Join(e.Path(), DoNotCompactFile)
 			_, err := os.Stat(doNotCompactFile)
+			if err != nil {
+				doNotCompactFile = filepath.Join(filepath.Dir(filepath.Dir(e.Path())), DoNotCompactFile)
+				_, err = os.Stat(doNotCompactFile)
+			}
 			if err == nil {
 				now := time.Now()
 				if now.After(nextDisabledMsg) {
@@ -2122,6 +2250,11 @@ func (e *Engine) compact(wg *sync.WaitGroup) {
 				continue
 			}
 
+			// Level and full compactions wait for the compaction window.
+			if !e.compactWindow.Contains(time.Now()) {
+				continue
+			}
+
 			// Find our compaction plans
 			level1Groups, len1 := e.CompactionPlan.PlanLevel(1)
 			level2Groups, len2 := e.CompactionPlan.PlanLevel(2)
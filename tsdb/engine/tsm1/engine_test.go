@@ -22,6 +22,7 @@ import (
 	"github.com/influxdata/influxdb/logger"
 	"github.com/influxdata/influxdb/models"
 	"github.com/influxdata/influxdb/pkg/deep"
+	"github.com/influxdata/influxdb/pkg/tracing"
 	"github.com/influxdata/influxdb/query"
 	"github.com/influxdata/influxdb/tsdb"
 	"github.com/influxdata/influxdb/tsdb/engine/tsm1"
@@ -991,6 +992,81 @@ func TestEngine_CreateIterator_TSM_Descending(t *testing.T) {
 	}
 }
 
+// Ensure a traced iterator reports the values read from the cache and TSM files.
+func TestEngine_CreateIterator_Metrics(t *testing.T) {
+	t.Parallel()
+
+	for _, index := range tsdb.RegisteredIndexes() {
+		t.Run(index, func(t *testing.T) {
+			e := MustOpenEngine(index)
+			defer e.Close()
+
+			e.MeasurementFields([]byte("cpu")).CreateFieldIfNotExists([]byte("value"), influxql.Float)
+			e.CreateSeriesIfNotExists([]byte("cpu,host=A"), []byte("cpu"), models.NewTags(map[string]string{"host": "A"}), notrack)
+
+			if err := e.WritePointsString(
+				`cpu,host=A value=1.1 1000000000`,
+				`cpu,host=A value=1.2 2000000000`,
+			); err != nil {
+				t.Fatalf("failed to write points: %s", err.Error())
+			}
+			e.MustWriteSnapshot()
+
+			// Overwrite a value in the TSM file with one in the cache.
+			if err := e.WritePointsString(
+				`cpu,host=A value=1.5 2000000000`,
+				`cpu,host=A value=1.3 3000000000`,
+			); err != nil {
+				t.Fatalf("failed to write points: %s", err.Error())
+			}
+
+			trace, span := tracing.NewTrace("select")
+			ctx := tracing.NewContextWithTrace(context.Background(), trace)
+			ctx = tracing.NewContextWithSpan(ctx, span)
+
+			itr, err := e.CreateIterator(ctx, "cpu", query.IteratorOptions{
+				Expr:       influxql.MustParseExpr(`value`),
+				Dimensions: []string{"host"},
+				StartTime:  influxql.MinTime,
+				EndTime:    influxql.MaxTime,
+				Ascending:  true,
+			})
+			if err != nil {
+				t.Fatal(err)
+			}
+			fitr := itr.(query.FloatIterator)
+			for i := 0; ; i++ {
+				if p, err := fitr.Next(); err != nil {
+					t.Fatalf("unexpected error(%d): %v", i, err)
+				} else if p == nil {
+					break
+				}
+			}
+			if err := itr.Close(); err != nil {
+				t.Fatal(err)
+			}
+			span.Finish()
+
+			tree := trace.Tree()
+			if len(tree.Children) != 1 || tree.Children[0].Raw.Name != "create_iterator" {
+				t.Fatalf("unexpected spans: %s", tree)
+			}
+			got := make(map[string]interface{})
+			for _, f := range tree.Children[0].Raw.Fields {
+				got[f.Key()] = f.Value()
+			}
+			for k, v := range map[string]int64{"cache_values": 2, "tsm_values": 1, "values_deduplicated": 1} {
+				if got[k] != v {
+					t.Fatalf("unexpected %s: got %v, exp %d", k, got[k], v)
+				}
+			}
+			if _, ok := got["execution_time"]; !ok {
+				t.Fatalf("missing execution_time: %v", got)
+			}
+		})
+	}
+}
+
 // Ensure engine can create an iterator with auxiliary fields.
 func TestEngine_CreateIterator_Aux(t *testing.T) {
 	t.Parallel()
@@ -1371,6 +1447,122 @@ func TestEngine_DeleteSeriesRange(t *testing.T) {
 	}
 }
 
+func TestEngine_DeleteFieldRange(t *testing.T) {
+	for _, index := range tsdb.RegisteredIndexes() {
+		t.Run(index, func(t *testing.T) {
+			p1 := MustParsePointString(`cpu,host=A value=1,payload="a" 1000000000`)
+			p2 := MustParsePointString(`cpu,host=B payload="b" 5000000000`)  // Should not be deleted
+			p3 := MustParsePointString(`cpu2,host=A payload="c" 1000000000`) // Should not be deleted
+			p4 := MustParsePointString(`cpu,host=A payload="d" 2000000000`)
+			p5 := MustParsePointString(`cpu,host=A payload="e" 6000000000`) // Should not be deleted
+
+			e, err := NewEngine(index)
+			if err != nil {
+				t.Fatal(err)
+			}
+
+			// mock the planner so compactions don't run during the test
+			e.CompactionPlan = &mockPlanner{}
+			if err := e.Open(); err != nil {
+				t.Fatal(err)
+			}
+			defer e.Close()
+
+			for _, p := range []models.Point{p1, p2, p3, p4, p5} {
+				if err := e.CreateSeriesIfNotExists(p.Key(), p.Name(), p.Tags(), notrack); err != nil {
+					t.Fatalf("create series index error: %v", err)
+				}
+			}
+
+			// The first points are written to a TSM file, the others remain in the cache.
+			if err := e.WritePoints([]models.Point{p1, p2, p3}, tsdb.NoopStatsTracker()); err != nil {
+				t.Fatalf("failed to write points: %s", err.Error())
+			}
+			if err := e.WriteSnapshot(); err != nil {
+				t.Fatalf("failed to snapshot: %s", err.Error())
+			}
+			if err := e.WritePoints([]models.Point{p4, p5}, tsdb.NoopStatsTracker()); err != nil {
+				t.Fatalf("failed to write points: %s", err.Error())
+			}
+
+			if err := e.DeleteFieldRange([]byte("cpu"), "payload", 0, 3000000000); err != nil {
+				t.Fatalf("failed to delete field: %v", err)
+			}
+
+			keys := e.FileStore.Keys()
+			for _, exp := range []string{"cpu,host=A#!~#value", "cpu,host=B#!~#payload", "cpu2,host=A#!~#payload"} {
+				if _, ok := keys[exp]; !ok {
+					t.Fatalf("wrong field deleted: exp %v, got %v", exp, keys)
+				}
+			}
+			if exp, got := 3, len(keys); exp != got {
+				t.Fatalf("key count mismatch: exp %v, got %v", exp, got)
+			}
+
+			values := e.Cache.Values([]byte("cpu,host=A#!~#payload"))
+			if exp, got := 1, len(values); exp != got {
+				t.Fatalf("cache value count mismatch: exp %v, got %v", exp, got)
+			} else if exp, got := int64(6000000000), values[0].UnixNano(); exp != got {
+				t.Fatalf("wrong cache value deleted: exp %v, got %v", exp, got)
+			}
+		})
+	}
+}
+
+// Ensure deleting a field range already removed does not add tombstones.
+func TestEngine_DeleteFieldRange_Tombstoned(t *testing.T) {
+	for _, index := range tsdb.RegisteredIndexes() {
+		t.Run(index, func(t *testing.T) {
+			p1 := MustParsePointString(`cpu,host=A payload="a" 1000000000`)
+			p2 := MustParsePointString(`cpu,host=A payload="b" 5000000000`)
+
+			e, err := NewEngine(index)
+			if err != nil {
+				t.Fatal(err)
+			}
+
+			// mock the planner so compactions don't run during the test
+			e.CompactionPlan = &mockPlanner{}
+			if err := e.Open(); err != nil {
+				t.Fatal(err)
+			}
+			defer e.Close()
+
+			for _, p := range []models.Point{p1, p2} {
+				if err := e.CreateSeriesIfNotExists(p.Key(), p.Name(), p.Tags(), notrack); err != nil {
+					t.Fatalf("create series index error: %v", err)
+				}
+			}
+			if err := e.WritePoints([]models.Point{p1, p2}, tsdb.NoopStatsTracker()); err != nil {
+				t.Fatalf("failed to write points: %s", err.Error())
+			}
+			if err := e.WriteSnapshot(); err != nil {
+				t.Fatalf("failed to snapshot: %s", err.Error())
+			}
+
+			tombstones := func() int {
+				var n int
+				if err := e.FileStore.Apply(func(r tsm1.TSMFile) error {
+					n += len(r.TombstoneRange([]byte("cpu,host=A#!~#payload")))
+					return nil
+				}); err != nil {
+					t.Fatal(err)
+				}
+				return n
+			}
+
+			for _, max := range []int64{3000000000, 3000000000, 2000000000} {
+				if err := e.DeleteFieldRange([]byte("cpu"), "payload", math.MinInt64, max); err != nil {
+					t.Fatalf("failed to delete field: %v", err)
+				}
+				if exp, got := 1, tombstones(); exp != got {
+					t.Fatalf("tombstone count mismatch after deleting up to %d: exp %v, got %v", max, exp, got)
+				}
+			}
+		})
+	}
+}
+
 func TestEngine_DeleteSeriesRangeWithPredicate(t *testing.T) {
 	for _, index := range tsdb.RegisteredIndexes() {
 		t.Run(index, func(t *testing.T) {
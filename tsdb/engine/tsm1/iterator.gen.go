@@ -11,6 +11,7 @@ import (
 	"runtime"
 	"sort"
 	"sync"
+	"time"
 
 	"github.com/influxdata/influxdb/pkg/metrics"
 	"github.com/influxdata/influxdb/pkg/tracing"
@@ -153,15 +154,28 @@ func (itr *floatFinalizerIterator) Close() error {
 
 type floatInstrumentedIterator struct {
 	query.FloatIterator
-	span  *tracing.Span
-	group *metrics.Group
+	span     *tracing.Span
+	group    *metrics.Group
+	execTime time.Duration
 }
 
 func newFloatInstrumentedIterator(inner query.FloatIterator, span *tracing.Span, group *metrics.Group) *floatInstrumentedIterator {
 	return &floatInstrumentedIterator{FloatIterator: inner, span: span, group: group}
 }
 
+// Next returns the next point and tracks the time spent reading points.
+func (itr *floatInstrumentedIterator) Next() (*query.FloatPoint, error) {
+	start := time.Now()
+	p, err := itr.FloatIterator.Next()
+	itr.execTime += time.Since(start)
+	return p, err
+}
+
 func (itr *floatInstrumentedIterator) Close() error {
+	// Close the inner iterator first so its cursors record their metrics.
+	err := itr.FloatIterator.Close()
+	itr.group.GetTimer(executionTimer).Update(itr.execTime)
+
 	var f fields.Fields
 	itr.group.ForEach(func(v metrics.Metric) {
 		switch m := v.(type) {
@@ -178,7 +192,7 @@ func (itr *floatInstrumentedIterator) Close() error {
 	itr.span.SetFields(f)
 	itr.span.Finish()
 
-	return itr.FloatIterator.Close()
+	return err
 }
 
 type floatIterator struct {
@@ -383,6 +397,8 @@ type floatAscendingCursor struct {
 		pos       int
 		keyCursor *KeyCursor
 	}
+
+	stats cursorStats
 }
 
 func newFloatAscendingCursor(seek int64, cacheValues Values, tsmKeyCursor *KeyCursor) *floatAscendingCursor {
@@ -428,6 +444,7 @@ func (c *floatAscendingCursor) close() error {
 		return nil
 	}
 
+	c.stats.flush(c.tsm.keyCursor)
 	c.tsm.keyCursor.Close()
 	c.tsm.keyCursor = nil
 	c.cache.values = nil
@@ -450,6 +467,8 @@ func (c *floatAscendingCursor) nextFloat() (int64, float64) {
 
 	// Both cache and tsm files have the same key, cache takes precedence.
 	if ckey == tkey {
+		c.stats.cacheN++
+		c.stats.dedupN++
 		c.nextCache()
 		c.nextTSM()
 		return ckey, cvalue
@@ -457,11 +476,13 @@ func (c *floatAscendingCursor) nextFloat() (int64, float64) {
 
 	// Buffered cache key precedes that in TSM file.
 	if ckey != tsdb.EOF && (ckey < tkey || tkey == tsdb.EOF) {
+		c.stats.cacheN++
 		c.nextCache()
 		return ckey, cvalue
 	}
 
 	// Buffered TSM key precedes that in cache.
+	c.stats.tsmN++
 	c.nextTSM()
 	return tkey, tvalue
 }
@@ -498,6 +519,8 @@ type floatDescendingCursor struct {
 		pos       int
 		keyCursor *KeyCursor
 	}
+
+	stats cursorStats
 }
 
 func newFloatDescendingCursor(seek int64, cacheValues Values, tsmKeyCursor *KeyCursor) *floatDescendingCursor {
@@ -549,6 +572,7 @@ func (c *floatDescendingCursor) close() error {
 		return nil
 	}
 
+	c.stats.flush(c.tsm.keyCursor)
 	c.tsm.keyCursor.Close()
 	c.tsm.keyCursor = nil
 	c.cache.values = nil
@@ -571,6 +595,8 @@ func (c *floatDescendingCursor) nextFloat() (int64, float64) {
 
 	// Both cache and tsm files have the same key, cache takes precedence.
 	if ckey == tkey {
+		c.stats.cacheN++
+		c.stats.dedupN++
 		c.nextCache()
 		c.nextTSM()
 		return ckey, cvalue
@@ -578,11 +604,13 @@ func (c *floatDescendingCursor) nextFloat() (int64, float64) {
 
 	// Buffered cache key precedes that in TSM file.
 	if ckey != tsdb.EOF && (ckey > tkey || tkey == tsdb.EOF) {
+		c.stats.cacheN++
 		c.nextCache()
 		return ckey, cvalue
 	}
 
 	// Buffered TSM key precedes that in cache.
+	c.stats.tsmN++
 	c.nextTSM()
 	return tkey, tvalue
 }
@@ -633,15 +661,28 @@ func (itr *integerFinalizerIterator) Close() error {
 
 type integerInstrumentedIterator struct {
 	query.IntegerIterator
-	span  *tracing.Span
-	group *metrics.Group
+	span     *tracing.Span
+	group    *metrics.Group
+	execTime time.Duration
 }
 
 func newIntegerInstrumentedIterator(inner query.IntegerIterator, span *tracing.Span, group *metrics.Group) *integerInstrumentedIterator {
 	return &integerInstrumentedIterator{IntegerIterator: inner, span: span, group: group}
 }
 
+// Next returns the next point and tracks the time spent reading points.
+func (itr *integerInstrumentedIterator) Next() (*query.IntegerPoint, error) {
+	start := time.Now()
+	p, err := itr.IntegerIterator.Next()
+	itr.execTime += time.Since(start)
+	return p, err
+}
+
 func (itr *integerInstrumentedIterator) Close() error {
+	// Close the inner iterator first so its cursors record their metrics.
+	err := itr.IntegerIterator.Close()
+	itr.group.GetTimer(executionTimer).Update(itr.execTime)
+
 	var f fields.Fields
 	itr.group.ForEach(func(v metrics.Metric) {
 		switch m := v.(type) {
@@ -658,7 +699,7 @@ func (itr *integerInstrumentedIterator) Close() error {
 	itr.span.SetFields(f)
 	itr.span.Finish()
 
-	return itr.IntegerIterator.Close()
+	return err
 }
 
 type integerIterator struct {
@@ -863,6 +904,8 @@ type integerAscendingCursor struct {
 		pos       int
 		keyCursor *KeyCursor
 	}
+
+	stats cursorStats
 }
 
 func newIntegerAscendingCursor(seek int64, cacheValues Values, tsmKeyCursor *KeyCursor) *integerAscendingCursor {
@@ -908,6 +951,7 @@ func (c *integerAscendingCursor) close() error {
 		return nil
 	}
 
+	c.stats.flush(c.tsm.keyCursor)
 	c.tsm.keyCursor.Close()
 	c.tsm.keyCursor = nil
 	c.cache.values = nil
@@ -930,6 +974,8 @@ func (c *integerAscendingCursor) nextInteger() (int64, int64) {
 
 	// Both cache and tsm files have the same key, cache takes precedence.
 	if ckey == tkey {
+		c.stats.cacheN++
+		c.stats.dedupN++
 		c.nextCache()
 		c.nextTSM()
 		return ckey, cvalue
@@ -937,11 +983,13 @@ func (c *integerAscendingCursor) nextInteger() (int64, int64) {
 
 	// Buffered cache key precedes that in TSM file.
 	if ckey != tsdb.EOF && (ckey < tkey || tkey == tsdb.EOF) {
+		c.stats.cacheN++
 		c.nextCache()
 		return ckey, cvalue
 	}
 
 	// Buffered TSM key precedes that in cache.
+	c.stats.tsmN++
 	c.nextTSM()
 	return tkey, tvalue
 }
@@ -978,6 +1026,8 @@ type integerDescendingCursor struct {
 		pos       int
 		keyCursor *KeyCursor
 	}
+
+	stats cursorStats
 }
 
 func newIntegerDescendingCursor(seek int64, cacheValues Values, tsmKeyCursor *KeyCursor) *integerDescendingCursor {
@@ -1029,6 +1079,7 @@ func (c *integerDescendingCursor) close() error {
 		return nil
 	}
 
+	c.stats.flush(c.tsm.keyCursor)
 	c.tsm.keyCursor.Close()
 	c.tsm.keyCursor = nil
 	c.cache.values = nil
@@ -1051,6 +1102,8 @@ func (c *integerDescendingCursor) nextInteger() (int64, int64) {
 
 	// Both cache and tsm files have the same key, cache takes precedence.
 	if ckey == tkey {
+		c.stats.cacheN++
+		c.stats.dedupN++
 		c.nextCache()
 		c.nextTSM()
 		return ckey, cvalue
@@ -1058,11 +1111,13 @@ func (c *integerDescendingCursor) nextInteger() (int64, int64) {
 
 	// Buffered cache key precedes that in TSM file.
 	if ckey != tsdb.EOF && (ckey > tkey || tkey == tsdb.EOF) {
+		c.stats.cacheN++
 		c.nextCache()
 		return ckey, cvalue
 	}
 
 	// Buffered TSM key precedes that in cache.
+	c.stats.tsmN++
 	c.nextTSM()
 	return tkey, tvalue
 }
@@ -1113,15 +1168,28 @@ func (itr *unsignedFinalizerIterator) Close() error {
 
 type unsignedInstrumentedIterator struct {
 	query.UnsignedIterator
-	span  *tracing.Span
-	group *metrics.Group
+	span     *tracing.Span
+	group    *metrics.Group
+	execTime time.Duration
 }
 
 func newUnsignedInstrumentedIterator(inner query.UnsignedIterator, span *tracing.Span, group *metrics.Group) *unsignedInstrumentedIterator {
 	return &unsignedInstrumentedIterator{UnsignedIterator: inner, span: span, group: group}
 }
 
+// Next returns the next point and tracks the time spent reading points.
+func (itr *unsignedInstrumentedIterator) Next() (*query.UnsignedPoint, error) {
+	start := time.Now()
+	p, err := itr.UnsignedIterator.Next()
+	itr.execTime += time.Since(start)
+	return p, err
+}
+
 func (itr *unsignedInstrumentedIterator) Close() error {
+	// Close the inner iterator first so its cursors record their metrics.
+	err := itr.UnsignedIterator.Close()
+	itr.group.GetTimer(executionTimer).Update(itr.execTime)
+
 	var f fields.Fields
 	itr.group.ForEach(func(v metrics.Metric) {
 		switch m := v.(type) {
@@ -1138,7 +1206,7 @@ func (itr *unsignedInstrumentedIterator) Close() error {
 	itr.span.SetFields(f)
 	itr.span.Finish()
 
-	return itr.UnsignedIterator.Close()
+	return err
 }
 
 type unsignedIterator struct {
@@ -1343,6 +1411,8 @@ type unsignedAscendingCursor struct {
 		pos       int
 		keyCursor *KeyCursor
 	}
+
+	stats cursorStats
 }
 
 func newUnsignedAscendingCursor(seek int64, cacheValues Values, tsmKeyCursor *KeyCursor) *unsignedAscendingCursor {
@@ -1388,6 +1458,7 @@ func (c *unsignedAscendingCursor) close() error {
 		return nil
 	}
 
+	c.stats.flush(c.tsm.keyCursor)
 	c.tsm.keyCursor.Close()
 	c.tsm.keyCursor = nil
 	c.cache.values = nil
@@ -1410,6 +1481,8 @@ func (c *unsignedAscendingCursor) nextUnsigned() (int64, uint64) {
 
 	// Both cache and tsm files have the same key, cache takes precedence.
 	if ckey == tkey {
+		c.stats.cacheN++
+		c.stats.dedupN++
 		c.nextCache()
 		c.nextTSM()
 		return ckey, cvalue
@@ -1417,11 +1490,13 @@ func (c *unsignedAscendingCursor) nextUnsigned() (int64, uint64) {
 
 	// Buffered cache key precedes that in TSM file.
 	if ckey != tsdb.EOF && (ckey < tkey || tkey == tsdb.EOF) {
+		c.stats.cacheN++
 		c.nextCache()
 		return ckey, cvalue
 	}
 
 	// Buffered TSM key precedes that in cache.
+	c.stats.tsmN++
 	c.nextTSM()
 	return tkey, tvalue
 }
@@ -1458,6 +1533,8 @@ type unsignedDescendingCursor struct {
 		pos       int
 		keyCursor *KeyCursor
 	}
+
+	stats cursorStats
 }
 
 func newUnsignedDescendingCursor(seek int64, cacheValues Values, tsmKeyCursor *KeyCursor) *unsignedDescendingCursor {
@@ -1509,6 +1586,7 @@ func (c *unsignedDescendingCursor) close() error {
 		return nil
 	}
 
+	c.stats.flush(c.tsm.keyCursor)
 	c.tsm.keyCursor.Close()
 	c.tsm.keyCursor = nil
 	c.cache.values = nil
@@ -1531,6 +1609,8 @@ func (c *unsignedDescendingCursor) nextUnsigned() (int64, uint64) {
 
 	// Both cache and tsm files have the same key, cache takes precedence.
 	if ckey == tkey {
+		c.stats.cacheN++
+		c.stats.dedupN++
 		c.nextCache()
 		c.nextTSM()
 		return ckey, cvalue
@@ -1538,11 +1618,13 @@ func (c *unsignedDescendingCursor) nextUnsigned() (int64, uint64) {
 
 	// Buffered cache key precedes that in TSM file.
 	if ckey != tsdb.EOF && (ckey > tkey || tkey == tsdb.EOF) {
+		c.stats.cacheN++
 		c.nextCache()
 		return ckey, cvalue
 	}
 
 	// Buffered TSM key precedes that in cache.
+	c.stats.tsmN++
 	c.nextTSM()
 	return tkey, tvalue
 }
@@ -1593,15 +1675,28 @@ func (itr *stringFinalizerIterator) Close() error {
 
 type stringInstrumentedIterator struct {
 	query.StringIterator
-	span  *tracing.Span
-	group *metrics.Group
+	span     *tracing.Span
+	group    *metrics.Group
+	execTime time.Duration
 }
 
 func newStringInstrumentedIterator(inner query.StringIterator, span *tracing.Span, group *metrics.Group) *stringInstrumentedIterator {
 	return &stringInstrumentedIterator{StringIterator: inner, span: span, group: group}
 }
 
+// Next returns the next point and tracks the time spent reading points.
+func (itr *stringInstrumentedIterator) Next() (*query.StringPoint, error) {
+	start := time.Now()
+	p, err := itr.StringIterator.Next()
+	itr.execTime += time.Since(start)
+	return p, err
+}
+
 func (itr *stringInstrumentedIterator) Close() error {
+	// Close the inner iterator first so its cursors record their metrics.
+	err := itr.StringIterator.Close()
+	itr.group.GetTimer(executionTimer).Update(itr.execTime)
+
 	var f fields.Fields
 	itr.group.ForEach(func(v metrics.Metric) {
 		switch m := v.(type) {
@@ -1618,7 +1713,7 @@ func (itr *stringInstrumentedIterator) Close() error {
 	itr.span.SetFields(f)
 	itr.span.Finish()
 
-	return itr.StringIterator.Close()
+	return err
 }
 
 type stringIterator struct {
@@ -1823,6 +1918,8 @@ type stringAscendingCursor struct {
 		pos       int
 		keyCursor *KeyCursor
 	}
+
+	stats cursorStats
 }
 
 func newStringAscendingCursor(seek int64, cacheValues Values, tsmKeyCursor *KeyCursor) *stringAscendingCursor {
@@ -1868,6 +1965,7 @@ func (c *stringAscendingCursor) close() error {
 		return nil
 	}
 
+	c.stats.flush(c.tsm.keyCursor)
 	c.tsm.keyCursor.Close()
 	c.tsm.keyCursor = nil
 	c.cache.values = nil
@@ -1890,6 +1988,8 @@ func (c *stringAscendingCursor) nextString() (int64, string) {
 
 	// Both cache and tsm files have the same key, cache takes precedence.
 	if ckey == tkey {
+		c.stats.cacheN++
+		c.stats.dedupN++
 		c.nextCache()
 		c.nextTSM()
 		return ckey, cvalue
@@ -1897,11 +1997,13 @@ func (c *stringAscendingCursor) nextString() (int64, string) {
 
 	// Buffered cache key precedes that in TSM file.
 	if ckey != tsdb.EOF && (ckey < tkey || tkey == tsdb.EOF) {
+		c.stats.cacheN++
 		c.nextCache()
 		return ckey, cvalue
 	}
 
 	// Buffered TSM key precedes that in cache.
+	c.stats.tsmN++
 	c.nextTSM()
 	return tkey, tvalue
 }
@@ -1938,6 +2040,8 @@ type stringDescendingCursor struct {
 		pos       int
 		keyCursor *KeyCursor
 	}
+
+	stats cursorStats
 }
 
 func newStringDescendingCursor(seek int64, cacheValues Values, tsmKeyCursor *KeyCursor) *stringDescendingCursor {
@@ -1989,6 +2093,7 @@ func (c *stringDescendingCursor) close() error {
 		return nil
 	}
 
+	c.stats.flush(c.tsm.keyCursor)
 	c.tsm.keyCursor.Close()
 	c.tsm.keyCursor = nil
 	c.cache.values = nil
@@ -2011,6 +2116,8 @@ func (c *stringDescendingCursor) nextString() (int64, string) {
 
 	// Both cache and tsm files have the same key, cache takes precedence.
 	if ckey == tkey {
+		c.stats.cacheN++
+		c.stats.dedupN++
 		c.nextCache()
 		c.nextTSM()
 		return ckey, cvalue
@@ -2018,11 +2125,13 @@ func (c *stringDescendingCursor) nextString() (int64, string) {
 
 	// Buffered cache key precedes that in TSM file.
 	if ckey != tsdb.EOF && (ckey > tkey || tkey == tsdb.EOF) {
+		c.stats.cacheN++
 		c.nextCache()
 		return ckey, cvalue
 	}
 
 	// Buffered TSM key precedes that in cache.
+	c.stats.tsmN++
 	c.nextTSM()
 	return tkey, tvalue
 }
@@ -2073,15 +2182,28 @@ func (itr *booleanFinalizerIterator) Close() error {
 
 type booleanInstrumentedIterator struct {
 	query.BooleanIterator
-	span  *tracing.Span
-	group *metrics.Group
+	span     *tracing.Span
+	group    *metrics.Group
+	execTime time.Duration
 }
 
 func newBooleanInstrumentedIterator(inner query.BooleanIterator, span *tracing.Span, group *metrics.Group) *booleanInstrumentedIterator {
 	return &booleanInstrumentedIterator{BooleanIterator: inner, span: span, group: group}
 }
 
+// Next returns the next point and tracks the time spent reading points.
+func (itr *booleanInstrumentedIterator) Next() (*query.BooleanPoint, error) {
+	start := time.Now()
+	p, err := itr.BooleanIterator.Next()
+	itr.execTime += time.Since(start)
+	return p, err
+}
+
 func (itr *booleanInstrumentedIterator) Close() error {
+	// Close the inner iterator first so its cursors record their metrics.
+	err := itr.BooleanIterator.Close()
+	itr.group.GetTimer(executionTimer).Update(itr.execTime)
+
 	var f fields.Fields
 	itr.group.ForEach(func(v metrics.Metric) {
 		switch m := v.(type) {
@@ -2098,7 +2220,7 @@ func (itr *booleanInstrumentedIterator) Close() error {
 	itr.span.SetFields(f)
 	itr.span.Finish()
 
-	return itr.BooleanIterator.Close()
+	return err
 }
 
 type booleanIterator struct {
@@ -2303,6 +2425,8 @@ type booleanAscendingCursor struct {
 		pos       int
 		keyCursor *KeyCursor
 	}
+
+	stats cursorStats
 }
 
 func newBooleanAscendingCursor(seek int64, cacheValues Values, tsmKeyCursor *KeyCursor) *booleanAscendingCursor {
@@ -2348,6 +2472,7 @@ func (c *booleanAscendingCursor) close() error {
 		return nil
 	}
 
+	c.stats.flush(c.tsm.keyCursor)
 	c.tsm.keyCursor.Close()
 	c.tsm.keyCursor = nil
 	c.cache.values = nil
@@ -2370,6 +2495,8 @@ func (c *booleanAscendingCursor) nextBoolean() (int64, bool) {
 
 	// Both cache and tsm files have the same key, cache takes precedence.
 	if ckey == tkey {
+		c.stats.cacheN++
+		c.stats.dedupN++
 		c.nextCache()
 		c.nextTSM()
 		return ckey, cvalue
@@ -2377,11 +2504,13 @@ func (c *booleanAscendingCursor) nextBoolean() (int64, bool) {
 
 	// Buffered cache key precedes that in TSM file.
 	if ckey != tsdb.EOF && (ckey < tkey || tkey == tsdb.EOF) {
+		c.stats.cacheN++
 		c.nextCache()
 		return ckey, cvalue
 	}
 
 	// Buffered TSM key precedes that in cache.
+	c.stats.tsmN++
 	c.nextTSM()
 	return tkey, tvalue
 }
@@ -2418,6 +2547,8 @@ type booleanDescendingCursor struct {
 		pos       int
 		keyCursor *KeyCursor
 	}
+
+	stats cursorStats
 }
 
 func newBooleanDescendingCursor(seek int64, cacheValues Values, tsmKeyCursor *KeyCursor) *booleanDescendingCursor {
@@ -2469,6 +2600,7 @@ func (c *booleanDescendingCursor) close() error {
 		return nil
 	}
 
+	c.stats.flush(c.tsm.keyCursor)
 	c.tsm.keyCursor.Close()
 	c.tsm.keyCursor = nil
 	c.cache.values = nil
@@ -2491,6 +2623,8 @@ func (c *booleanDescendingCursor) nextBoolean() (int64, bool) {
 
 	// Both cache and tsm files have the same key, cache takes precedence.
 	if ckey == tkey {
+		c.stats.cacheN++
+		c.stats.dedupN++
 		c.nextCache()
 		c.nextTSM()
 		return ckey, cvalue
@@ -2498,11 +2632,13 @@ func (c *booleanDescendingCursor) nextBoolean() (int64, bool) {
 
 	// Buffered cache key precedes that in TSM file.
 	if ckey != tsdb.EOF && (ckey > tkey || tkey == tsdb.EOF) {
+		c.stats.cacheN++
 		c.nextCache()
 		return ckey, cvalue
 	}
 
 	// Buffered TSM key precedes that in cache.
+	c.stats.tsmN++
 	c.nextTSM()
 	return tkey, tvalue
 }
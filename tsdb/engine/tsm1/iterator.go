@@ -154,6 +154,24 @@ func (c cursorsAt) close() {
 	}
 }
 
+// cursorStats counts the values returned by a cursor from the cache and from
+// TSM files.
+type cursorStats struct {
+	cacheN int64 // values read from the cache
+	tsmN   int64 // values read from TSM files
+	dedupN int64 // TSM values replaced by a cache value with the same timestamp
+}
+
+// flush adds the counts to the metrics group of the key cursor, if any.
+func (s *cursorStats) flush(c *KeyCursor) {
+	if c == nil || c.col == nil {
+		return
+	}
+	c.col.GetCounter(cacheValuesCounter).Add(s.cacheN)
+	c.col.GetCounter(tsmValuesCounter).Add(s.tsmN)
+	c.col.GetCounter(dedupValuesCounter).Add(s.dedupN)
+}
+
 // newMergeFinalizerIterator creates a new Merge iterator from the inputs. If the call to Merge succeeds,
 // the resulting Iterator will be wrapped in a finalizer iterator.
 // If Merge returns an error, the inputs will be closed.
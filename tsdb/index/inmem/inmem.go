@@ -364,6 +364,18 @@ func (i *Index) TagValueN(name, key []byte) int {
 	return mm.CardinalityBytes(key)
 }
 
+// MeasurementSeriesN returns the number of series in a measurement.
+func (i *Index) MeasurementSeriesN(name []byte) int {
+	i.mu.RLock()
+	mm := i.measurements[string(name)]
+	i.mu.RUnlock()
+
+	if mm == nil {
+		return 0
+	}
+	return mm.SeriesN()
+}
+
 // MeasurementTagKeysByExpr returns an ordered set of tag keys filtered by an expression.
 func (i *Index) MeasurementTagKeysByExpr(name []byte, expr influxql.Expr) (map[string]struct{}, error) {
 	i.mu.RLock()
@@ -1226,6 +1238,37 @@ func (idx *ShardIndex) CreateSeriesListIfNotExists(keys, names [][]byte, tagsSli
 		keys, names, tagsSlice = keys[:n], names[:n], tagsSlice[:n]
 	}
 
+	// Ensure that no measurement goes over the maximum series cardinality.
+	if maxSeriesPerMeasurement := idx.opt.Config.MaxSeriesPerMeasurement; maxSeriesPerMeasurement > 0 {
+		var n int
+		seriesN := make(map[string]int)
+		for i, name := range names {
+			cnt, ok := seriesN[string(name)]
+			if !ok {
+				cnt = idx.MeasurementSeriesN(name)
+			}
+
+			if cnt >= maxSeriesPerMeasurement {
+				if reason == "" {
+					reason = fmt.Sprintf("max-series-per-measurement limit exceeded (%d/%d): measurement=%q",
+						cnt, maxSeriesPerMeasurement, name)
+				}
+				seriesN[string(name)] = cnt
+				droppedKeys = append(droppedKeys, keys[i])
+				continue
+			}
+			seriesN[string(name)] = cnt + 1
+
+			if n != i {
+				keys[n], names[n], tagsSlice[n] = keys[i], names[i], tagsSlice[i]
+			}
+			n++
+		}
+
+		// Slice to only include successful points.
+		keys, names, tagsSlice = keys[:n], names[:n], tagsSlice[:n]
+	}
+
 	if err := idx.Index.CreateSeriesListIfNotExists(idx.seriesIDSet, idx.measurements, keys, names, tagsSlice, &idx.opt, idx.opt.Config.MaxSeriesPerDatabase == 0, tracker); err != nil {
 		reason = err.Error()
 		droppedKeys = append(droppedKeys, keys...)
@@ -123,6 +123,9 @@ type PartialWriteError struct {
 
 	// A sorted slice of series keys that were dropped.
 	DroppedKeys [][]byte
+
+	// The points that were dropped, when known.
+	DroppedPoints []models.Point
 }
 
 func (e PartialWriteError) Error() string {
@@ -457,6 +460,24 @@ func (s *Shard) LastModified() time.Time {
 	return engine.LastModified()
 }
 
+// CacheSize returns the size in bytes of the shard's in-memory cache.
+func (s *Shard) CacheSize() int64 {
+	engine, err := s.Engine()
+	if err != nil {
+		return 0
+	}
+	return engine.CacheSize()
+}
+
+// WriteSnapshot writes the shard's in-memory cache to a TSM file.
+func (s *Shard) WriteSnapshot() error {
+	engine, err := s.Engine()
+	if err != nil {
+		return err
+	}
+	return engine.WriteSnapshot()
+}
+
 // Index returns a reference to the underlying index. It returns an error if
 // the index is nil.
 func (s *Shard) Index() (Index, error) {
@@ -598,6 +619,7 @@ func (s *Shard) validateSeriesAndFields(points []models.Point, tracker StatsTrac
 		fieldsToCreate []*FieldCreate
 		err            error
 		dropped        int
+		droppedPoints  []models.Point
 		reason         string // only first error reason is set unless returned from CreateSeriesListIfNotExists
 	)
 
@@ -616,6 +638,7 @@ func (s *Shard) validateSeriesAndFields(points []models.Point, tracker StatsTrac
 		// Drop any series w/ a "time" tag, these are illegal
 		if v := tags.Get(timeBytes); v != nil {
 			dropped++
+			droppedPoints = append(droppedPoints, p)
 			if reason == "" {
 				reason = fmt.Sprintf(
 					"invalid tag key: input tag \"%s\" on measurement \"%s\" is invalid",
@@ -627,6 +650,7 @@ func (s *Shard) validateSeriesAndFields(points []models.Point, tracker StatsTrac
 		// Drop any series with invalid unicode characters in the key.
 		if validateKeys && !models.ValidKeyTokens(string(p.Name()), tags) {
 			dropped++
+			droppedPoints = append(droppedPoints, p)
 			if reason == "" {
 				reason = fmt.Sprintf("key contains invalid unicode: %q", makePrintable(string(p.Key())))
 			}
@@ -685,11 +709,13 @@ func (s *Shard) validateSeriesAndFields(points []models.Point, tracker StatsTrac
 					"time", string(p.Name()))
 			}
 			dropped++
+			droppedPoints = append(droppedPoints, p)
 			continue
 		}
 
 		// Skip any points whos keys have been dropped. Dropped has already been incremented for them.
 		if len(droppedKeys) > 0 && bytesutil.Contains(droppedKeys, keys[i]) {
+			droppedPoints = append(droppedPoints, p)
 			continue
 		}
 
@@ -704,6 +730,7 @@ func (s *Shard) validateSeriesAndFields(points []models.Point, tracker StatsTrac
 					reason = err.Reason
 				}
 				dropped += err.Dropped
+				droppedPoints = append(droppedPoints, p)
 				atomic.AddInt64(&s.stats.WritePointsDropped, int64(err.Dropped))
 			default:
 				return nil, nil, err
@@ -744,7 +771,7 @@ func (s *Shard) validateSeriesAndFields(points []models.Point, tracker StatsTrac
 	}
 
 	if dropped > 0 {
-		err = PartialWriteError{Reason: reason, Dropped: dropped}
+		err = PartialWriteError{Reason: reason, Dropped: dropped, DroppedPoints: droppedPoints}
 	}
 
 	return points[:j], fieldsToCreate, err
@@ -827,6 +854,15 @@ func (s *Shard) DeleteMeasurement(name []byte) error {
 	return engine.DeleteMeasurement(name)
 }
 
+// DeleteFieldRange deletes the values of a field of a measurement between min and max (inclusive).
+func (s *Shard) DeleteFieldRange(name []byte, field string, min, max int64) error {
+	engine, err := s.Engine()
+	if err != nil {
+		return err
+	}
+	return engine.DeleteFieldRange(name, field, min, max)
+}
+
 // SeriesN returns the unique number of series in the shard.
 func (s *Shard) SeriesN() int64 {
 	engine, err := s.Engine()
@@ -291,11 +291,72 @@ func TestShard_MaxTagValuesLimit(t *testing.T) {
 		t.Fatal("expected error")
 	} else if exp, got := `partial write: max-values-per-tag limit exceeded (1000/1000): measurement="cpu" tag="host" value="server9999" dropped=1`, err.Error(); exp != got {
 		t.Fatalf("unexpected error message:\n\texp = %s\n\tgot = %s", exp, got)
+	} else if pwe := err.(tsdb.PartialWriteError); len(pwe.DroppedPoints) != 1 || pwe.DroppedPoints[0] != pt {
+		t.Fatalf("unexpected dropped points: %v", pwe.DroppedPoints)
 	}
 
 	sh.Close()
 }
 
+func TestShard_MaxSeriesPerMeasurementLimit(t *testing.T) {
+	tmpDir, _ := os.MkdirTemp("", "shard_test")
+	defer os.RemoveAll(tmpDir)
+	tmpShard := filepath.Join(tmpDir, "db", "rp", "1")
+	tmpWal := filepath.Join(tmpDir, "wal")
+
+	sfile := MustOpenSeriesFile()
+	defer sfile.Close()
+
+	opts := tsdb.NewEngineOptions()
+	opts.Config.WALDir = filepath.Join(tmpDir, "wal")
+	opts.Config.MaxSeriesPerMeasurement = 100
+	opts.InmemIndex = inmem.NewIndex(filepath.Base(tmpDir), sfile.SeriesFile)
+
+	sh := tsdb.NewShard(1, tmpShard, tmpWal, sfile.SeriesFile, opts)
+
+	if err := sh.Open(); err != nil {
+		t.Fatalf("error opening shard: %s", err.Error())
+	}
+	defer sh.Close()
+
+	// Writing 100 series should succeed.
+	points := []models.Point{}
+
+	for i := 0; i < 100; i++ {
+		pt := models.MustNewPoint(
+			"cpu",
+			models.Tags{{Key: []byte("host"), Value: []byte(fmt.Sprintf("server%d", i))}},
+			map[string]interface{}{"value": 1.0},
+			time.Unix(1, 2),
+		)
+		points = append(points, pt)
+	}
+
+	err := sh.WritePoints(points, tsdb.NoopStatsTracker())
+	if err != nil {
+		t.Fatalf(err.Error())
+	}
+
+	// Writing to existing series and to another measurement should succeed,
+	// while a new series in the full measurement is dropped.
+	points = []models.Point{
+		models.MustNewPoint("cpu", models.Tags{{Key: []byte("host"), Value: []byte("server0")}}, map[string]interface{}{"value": 2.0}, time.Unix(2, 0)),
+		models.MustNewPoint("cpu", models.Tags{{Key: []byte("host"), Value: []byte("server9999")}}, map[string]interface{}{"value": 2.0}, time.Unix(2, 0)),
+		models.MustNewPoint("mem", models.Tags{{Key: []byte("host"), Value: []byte("server9999")}}, map[string]interface{}{"value": 2.0}, time.Unix(2, 0)),
+	}
+
+	err = sh.WritePoints(points, tsdb.NoopStatsTracker())
+	if err == nil {
+		t.Fatal("expected error")
+	} else if exp, got := `partial write: max-series-per-measurement limit exceeded (100/100): measurement="cpu" dropped=1`, err.Error(); exp != got {
+		t.Fatalf("unexpected error message:\n\texp = %s\n\tgot = %s", exp, got)
+	}
+
+	if n := sh.SeriesN(); n != 101 {
+		t.Fatalf("unexpected series count: %d", n)
+	}
+}
+
 func TestWriteTimeTag(t *testing.T) {
 	tmpDir, _ := os.MkdirTemp("", "shard_test")
 	defer os.RemoveAll(tmpDir)
@@ -1122,6 +1122,28 @@ func (s *Store) DeleteMeasurement(database, name string) error {
 	})
 }
 
+// DeleteFieldRange removes the values of a field of a measurement between min
+// and max (inclusive) from all shards of a database.
+func (s *Store) DeleteFieldRange(database, name, field string, min, max int64) error {
+	s.mu.RLock()
+	shards := s.filterShards(byDatabase(database))
+	epochs := s.epochsForShards(shards)
+	s.mu.RUnlock()
+
+	limit := limiter.NewFixed(s.EngineOptions.Config.MaxConcurrentDeletes)
+	return s.walkShards(shards, func(sh *Shard) error {
+		limit.Take()
+		defer limit.Release()
+
+		guard := newGuard(min, max, []string{name}, nil)
+		waiter := epochs[sh.id].WaitDelete(guard)
+		waiter.Wait()
+		defer waiter.Done()
+
+		return sh.DeleteFieldRange([]byte(name), field, min, max)
+	})
+}
+
 // filterShards returns a slice of shards where fn returns true
 // for the shard. If the provided predicate is nil then all shards are returned.
 // filterShards should be called under a lock.
@@ -2187,11 +2209,32 @@ func (s *Store) monitorShards() {
 	defer t.Stop()
 	t2 := time.NewTicker(time.Minute)
 	defer t2.Stop()
+
+	// snapshotting is closed once the running round of cache snapshots
+	// completes. Snapshots run apart so a slow one does not delay the other
+	// checks, and a tick is skipped while the previous round is running.
+	var snapshotting chan struct{}
 	for {
 		select {
 		case <-s.closing:
 			return
 		case <-t.C:
+			if snapshotting != nil {
+				select {
+				case <-snapshotting:
+					snapshotting = nil
+				default:
+				}
+			}
+			if limit := int64(s.EngineOptions.Config.CacheSnapshotTotalMemorySize); limit > 0 && snapshotting == nil {
+				done := make(chan struct{})
+				snapshotting = done
+				go func() {
+					defer close(done)
+					s.snapshotLargestCaches(limit)
+				}()
+			}
+
 			s.mu.RLock()
 			for _, sh := range s.shards {
 				if isIdle, _ := sh.IsIdle(); isIdle {
@@ -2289,6 +2332,64 @@ func (s *Store) monitorShards() {
 	}
 }
 
+// snapshotLargestCaches snapshots the largest shard caches until the combined
+// size of all shard caches is no more than limit.
+func (s *Store) snapshotLargestCaches(limit int64) {
+	s.mu.RLock()
+	shards := make(map[uint64]*Shard, len(s.shards))
+	sizes := make(map[uint64]int64, len(s.shards))
+	for id, sh := range s.shards {
+		shards[id] = sh
+		sizes[id] = sh.CacheSize()
+	}
+	s.mu.RUnlock()
+
+	var total int64
+	for _, sz := range sizes {
+		total += sz
+	}
+
+	for _, id := range cacheSnapshotCandidates(sizes, limit) {
+		s.Logger.Info("Snapshotting cache to bring total cache size within limit",
+			logger.Shard(id),
+			zap.Int64("cache_size", sizes[id]),
+			zap.Int64("total_cache_size", total),
+			zap.Int64("limit", limit))
+		if err := shards[id].WriteSnapshot(); err != nil {
+			s.Logger.Info("Error writing snapshot", zap.Error(err), logger.Shard(id))
+		}
+	}
+}
+
+// cacheSnapshotCandidates returns the IDs of the shards whose caches must be
+// snapshotted for the combined size of sizes to be no more than limit,
+// largest cache first.
+func cacheSnapshotCandidates(sizes map[uint64]int64, limit int64) []uint64 {
+	var total int64
+	ids := make([]uint64, 0, len(sizes))
+	for id, sz := range sizes {
+		total += sz
+		ids = append(ids, id)
+	}
+	if total <= limit {
+		return nil
+	}
+
+	sort.Slice(ids, func(i, j int) bool {
+		if sizes[ids[i]] != sizes[ids[j]] {
+			return sizes[ids[i]] > sizes[ids[j]]
+		}
+		return ids[i] < ids[j]
+	})
+
+	var n int
+	for total > limit && n < len(ids) && sizes[ids[n]] > 0 {
+		total -= sizes[ids[n]]
+		n++
+	}
+	return ids[:n]
+}
+
 // KeyValue holds a string key and a string value.
 type KeyValue struct {
 	Key, Value string
@@ -0,0 +1,25 @@
+package tsdb
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestCacheSnapshotCandidates(t *testing.T) {
+	sizes := map[uint64]int64{1: 10, 2: 40, 3: 30, 4: 0, 5: 30}
+
+	for _, tt := range []struct {
+		limit int64
+		exp   []uint64
+	}{
+		{limit: 110, exp: nil},
+		{limit: 100, exp: []uint64{2}},
+		{limit: 70, exp: []uint64{2}},
+		{limit: 50, exp: []uint64{2, 3}},
+		{limit: 0, exp: []uint64{2, 3, 5, 1}},
+	} {
+		if got := cacheSnapshotCandidates(sizes, tt.limit); !reflect.DeepEqual(got, tt.exp) {
+			t.Fatalf("limit %d: unexpected candidates:\n\texp=%v\n\tgot=%v", tt.limit, tt.exp, got)
+		}
+	}
+}